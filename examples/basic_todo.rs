@@ -17,30 +17,27 @@ use redux_rs::{Selector, Store};
 // }
 //
 // Rest equivalent:
+#[allow(dead_code)]
 #[derive(Default, Debug)]
 struct State {
     todos: Vec<Todo>,
     visibility_filter: VisibilityFilter,
 }
 
+#[allow(dead_code)]
 #[derive(Debug)]
 struct Todo {
     text: String,
     completed: bool,
 }
 
-#[derive(Debug)]
+#[derive(Debug, Default)]
 enum VisibilityFilter {
     ShowAll,
+    #[default]
     ShowCompleted,
 }
 
-impl Default for VisibilityFilter {
-    fn default() -> Self {
-        VisibilityFilter::ShowCompleted
-    }
-}
-
 enum Action {
     AddTodo { text: String },
     ToggleTodo { index: usize },
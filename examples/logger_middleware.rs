@@ -6,6 +6,7 @@ use redux_rs::{Store, StoreApi};
 #[derive(Default)]
 struct EmptyStore;
 
+#[allow(dead_code)]
 #[derive(Debug)]
 struct LogableAction(&'static str);
 
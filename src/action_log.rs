@@ -0,0 +1,192 @@
+use serde::de::DeserializeOwned;
+use serde::Deserialize;
+use std::error::Error;
+use std::fmt::{Display, Formatter};
+
+/// A recorded sequence of actions, which can be replayed on a store with `StoreApi::dispatch_all_and_wait`.
+#[derive(Clone, Debug, PartialEq)]
+pub struct ActionLog<Action> {
+    actions: Vec<Action>,
+}
+
+impl<Action> ActionLog<Action> {
+    /// The actions in the order they were dispatched
+    pub fn actions(&self) -> &[Action] {
+        &self.actions
+    }
+
+    /// Returns the actions in the order they were dispatched
+    pub fn into_actions(self) -> Vec<Action> {
+        self.actions
+    }
+
+    /// Returns the number of actions in the log
+    pub fn len(&self) -> usize {
+        self.actions.len()
+    }
+
+    /// Returns true if the log contains no actions
+    pub fn is_empty(&self) -> bool {
+        self.actions.is_empty()
+    }
+}
+
+impl<Action> From<Vec<Action>> for ActionLog<Action> {
+    fn from(actions: Vec<Action>) -> Self {
+        ActionLog { actions }
+    }
+}
+
+/// Returned when a Redux DevTools export can't be parsed, see `ActionLog::from_devtools_export`
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ParseError(pub String);
+
+impl Display for ParseError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "invalid devtools export: {}", self.0)
+    }
+}
+
+impl Error for ParseError {}
+
+#[derive(Deserialize)]
+struct DevToolsExport {
+    actions: Vec<serde_json::Value>,
+}
+
+impl<Action> ActionLog<Action>
+where
+    Action: DeserializeOwned,
+{
+    /// Parse the actions of a log exported from the JavaScript Redux DevTools.
+    ///
+    /// The export has the shape `{ "actions": [{ "type": "...", "payload": ... }], "computedStates": [...] }`,
+    /// only the actions are used. Every action object is deserialized into an `Action`,
+    /// an enum with `#[serde(tag = "type", content = "payload")]` maps the `type` field to its variants.
+    /// Redux internal actions, with a type starting with `@@` like `@@INIT`, are skipped.
+    ///
+    /// ## Example
+    /// ```
+    /// use redux_rs::ActionLog;
+    /// use serde::Deserialize;
+    ///
+    /// #[derive(Debug, Deserialize, PartialEq)]
+    /// #[serde(tag = "type", content = "payload")]
+    /// enum Action {
+    ///     #[serde(rename = "counter/add")]
+    ///     Add(i32),
+    /// }
+    ///
+    /// let export = r#"{ "actions": [{ "type": "@@INIT" }, { "type": "counter/add", "payload": 2 }], "computedStates": [] }"#;
+    /// let log = ActionLog::<Action>::from_devtools_export(export).unwrap();
+    ///
+    /// assert_eq!(log.actions(), &[Action::Add(2)]);
+    /// ```
+    pub fn from_devtools_export(json: &str) -> Result<Self, ParseError> {
+        let export: DevToolsExport = serde_json::from_str(json).map_err(|error| ParseError(error.to_string()))?;
+
+        let actions = export
+            .actions
+            .into_iter()
+            .enumerate()
+            .filter(|(_, action)| !is_internal_action(action))
+            .map(|(index, action)| Action::deserialize(action).map_err(|error| ParseError(format!("action {}: {}", index, error))))
+            .collect::<Result<_, _>>()?;
+
+        Ok(ActionLog { actions })
+    }
+}
+
+// Redux dispatches actions like @@INIT and @@redux/REPLACE itself, they don't exist in the application
+fn is_internal_action(action: &serde_json::Value) -> bool {
+    action
+        .get("type")
+        .and_then(|action_type| action_type.as_str())
+        .is_some_and(|action_type| action_type.starts_with("@@"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Store;
+
+    #[derive(Clone, Debug, Deserialize, PartialEq)]
+    #[serde(tag = "type", content = "payload")]
+    enum TodoAction {
+        #[serde(rename = "todos/add")]
+        Add { id: u32, title: String },
+        #[serde(rename = "todos/toggle")]
+        Toggle(u32),
+        #[serde(rename = "todos/clearCompleted")]
+        ClearCompleted,
+    }
+
+    #[derive(Clone, Debug, Default, PartialEq)]
+    struct Todo {
+        id: u32,
+        title: String,
+        done: bool,
+    }
+
+    fn todo_reducer(mut todos: Vec<Todo>, action: TodoAction) -> Vec<Todo> {
+        match action {
+            TodoAction::Add { id, title } => todos.push(Todo { id, title, done: false }),
+            TodoAction::Toggle(id) => todos.iter_mut().filter(|todo| todo.id == id).for_each(|todo| todo.done = !todo.done),
+            TodoAction::ClearCompleted => todos.retain(|todo| !todo.done),
+        }
+        todos
+    }
+
+    const EXPORT: &str = r#"{
+        "actions": [
+            { "type": "@@INIT" },
+            { "type": "todos/add", "payload": { "id": 1, "title": "Write tests" } },
+            { "type": "todos/add", "payload": { "id": 2, "title": "Ship it" } },
+            { "type": "todos/toggle", "payload": 1 },
+            { "type": "todos/clearCompleted" }
+        ],
+        "computedStates": [
+            { "state": [] },
+            { "state": [{ "id": 1, "title": "Write tests", "done": false }] }
+        ]
+    }"#;
+
+    #[tokio::test]
+    async fn parse_and_replay_devtools_export() {
+        let log = ActionLog::<TodoAction>::from_devtools_export(EXPORT).unwrap();
+
+        assert_eq!(
+            log.actions(),
+            &[
+                TodoAction::Add {
+                    id: 1,
+                    title: "Write tests".to_string()
+                },
+                TodoAction::Add {
+                    id: 2,
+                    title: "Ship it".to_string()
+                },
+                TodoAction::Toggle(1),
+                TodoAction::ClearCompleted,
+            ]
+        );
+
+        let store = Store::new(todo_reducer);
+        store.dispatch_all_and_wait(log.into_actions()).await;
+
+        let titles = store
+            .select(|todos: &Vec<Todo>| todos.iter().map(|todo| todo.title.clone()).collect::<Vec<_>>())
+            .await;
+        assert_eq!(titles, vec!["Ship it"]);
+    }
+
+    #[test]
+    fn unknown_action_type_is_an_error() {
+        let export = r#"{ "actions": [{ "type": "todos/add", "payload": { "id": 1, "title": "a" } }, { "type": "todos/remove", "payload": 1 }] }"#;
+
+        let error = ActionLog::<TodoAction>::from_devtools_export(export).unwrap_err();
+        assert!(error.0.starts_with("action 1:"), "{}", error);
+
+        assert!(ActionLog::<TodoAction>::from_devtools_export("not json").is_err());
+    }
+}
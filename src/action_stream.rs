@@ -0,0 +1,77 @@
+use async_trait::async_trait;
+use futures_core::Stream;
+use std::pin::Pin;
+use std::task::{Context, Poll};
+use tokio::sync::mpsc::{unbounded_channel, UnboundedReceiver, UnboundedSender};
+
+// Called with every action before it's reduced, returns false once the observer is no longer interested
+pub(crate) type ActionObserver<Action> = Box<dyn Fn(&Action) -> bool + Send>;
+
+/// Observe the actions that reach the reducer of a store.
+///
+/// This is implemented by `Store` and by every `StoreWithMiddleware` around it.
+/// A wrapped store forwards to the underlying store, so the stream contains the actions as the reducer sees them,
+/// including the ones that were dispatched by middleware (for example by thunks).
+///
+/// ## Example
+/// ```
+/// use redux_rs::{ObserveActions, Store};
+///
+/// #[derive(Default)]
+/// struct Counter(i8);
+///
+/// #[derive(Clone, Debug, PartialEq)]
+/// enum Action {
+///     Increment,
+/// }
+///
+/// fn reducer(state: Counter, _action: Action) -> Counter {
+///     Counter(state.0 + 1)
+/// }
+///
+/// # #[tokio::main(flavor = "current_thread")]
+/// # async fn main() {
+/// let store = Store::new(reducer);
+/// let mut actions = store.observe_actions().await;
+///
+/// store.dispatch(Action::Increment).await;
+/// assert_eq!(actions.recv().await, Some(Action::Increment));
+/// # }
+/// ```
+#[async_trait]
+pub trait ObserveActions<Action>
+where
+    Action: Send + 'static,
+{
+    /// Observe every action before it's reduced, unlike subscribers which are notified after the state is updated
+    async fn observe_actions(&self) -> ActionStream<Action>
+    where
+        Action: Clone;
+}
+
+/// Stream of every action that reaches the reducer of a store, see `ObserveActions`.
+///
+/// The stream ends when the store is dropped.
+pub struct ActionStream<Action> {
+    rx: UnboundedReceiver<Action>,
+}
+
+impl<Action> ActionStream<Action> {
+    pub(crate) fn channel() -> (UnboundedSender<Action>, Self) {
+        let (tx, rx) = unbounded_channel();
+        (tx, ActionStream { rx })
+    }
+
+    /// Receive the next action, returns None when the store is dropped
+    pub async fn recv(&mut self) -> Option<Action> {
+        self.rx.recv().await
+    }
+}
+
+impl<Action> Stream for ActionStream<Action> {
+    type Item = Action;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        self.rx.poll_recv(cx)
+    }
+}
@@ -0,0 +1,43 @@
+use futures_core::{FusedStream, Stream};
+use std::future::poll_fn;
+use std::pin::Pin;
+use std::task::{Context, Poll};
+use tokio::sync::mpsc::UnboundedReceiver;
+
+/// Stream of the values reported by the store, like the errors of an `ErrorReceiver` or the health changes of a `HealthReceiver`.
+///
+/// The stream ends when the sending side is dropped.
+pub struct ChannelReceiver<T> {
+    rx: UnboundedReceiver<T>,
+    terminated: bool,
+}
+
+impl<T> ChannelReceiver<T> {
+    pub(crate) fn new(rx: UnboundedReceiver<T>) -> Self {
+        ChannelReceiver { rx, terminated: false }
+    }
+
+    /// Receive the next value, returns None when the sending side is dropped
+    pub async fn recv(&mut self) -> Option<T> {
+        poll_fn(|cx| Pin::new(&mut *self).poll_next(cx)).await
+    }
+}
+
+impl<T> Stream for ChannelReceiver<T> {
+    type Item = T;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let value = self.rx.poll_recv(cx);
+        if let Poll::Ready(None) = value {
+            self.terminated = true;
+        }
+
+        value
+    }
+}
+
+impl<T> FusedStream for ChannelReceiver<T> {
+    fn is_terminated(&self) -> bool {
+        self.terminated
+    }
+}
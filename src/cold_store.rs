@@ -0,0 +1,264 @@
+use crate::{Reducer, Selector, Store, StoreApi, Subscriber};
+use async_trait::async_trait;
+use std::sync::{Mutex, OnceLock};
+
+type Init<State> = Box<dyn FnOnce() -> State + Send>;
+type Condition<State> = Box<dyn Fn(&State) -> bool + Send>;
+
+// Everything needed to start the store, kept until the first access
+struct Pending<State, RootReducer> {
+    root_reducer: RootReducer,
+    init: Init<State>,
+    subscribers: Vec<Box<dyn Subscriber<State> + Send>>,
+    until_subscribers: Vec<(Box<dyn Subscriber<State> + Send>, Condition<State>)>,
+}
+
+/// A store which creates its state on first access.
+///
+/// The initial state is only created, and the store only started, when an action is dispatched or the state is selected for the first time.
+/// This is useful when creating the state is expensive, like deserializing it from disk, and the store might not be used at all.
+/// Subscribing doesn't count as an access, the subscribers are registered once the store is started.
+///
+/// Starting the store is synchronous, so cancelling the first access doesn't leave the store half started.
+/// If `init` panics the store can't be started anymore, every later access panics as well.
+///
+/// ## Example
+/// ```
+/// use redux_rs::{ColdStore, StoreApi};
+///
+/// fn reducer(state: u32, add: u32) -> u32 {
+///     state + add
+/// }
+///
+/// # #[tokio::main(flavor = "current_thread")]
+/// # async fn main() {
+/// let store = ColdStore::new(reducer, || 10);
+/// assert!(!store.is_initialized());
+///
+/// store.dispatch(5u32).await;
+/// assert_eq!(store.select(|state: &u32| *state).await, 15);
+/// # }
+/// ```
+pub struct ColdStore<State, Action, RootReducer>
+where
+    State: Send,
+    RootReducer: Send,
+{
+    store: OnceLock<Store<State, Action, RootReducer>>,
+    pending: Mutex<Option<Pending<State, RootReducer>>>,
+}
+
+impl<State, Action, RootReducer> ColdStore<State, Action, RootReducer>
+where
+    Action: Send + 'static,
+    RootReducer: Reducer<State, Action> + Send + 'static,
+    State: Send + 'static,
+{
+    /// Create a cold store, `init` creates the initial state on first access
+    pub fn new<F>(root_reducer: RootReducer, init: F) -> Self
+    where
+        F: FnOnce() -> State + Send + 'static,
+    {
+        ColdStore {
+            store: OnceLock::new(),
+            pending: Mutex::new(Some(Pending {
+                root_reducer,
+                init: Box::new(init),
+                subscribers: Vec::new(),
+                until_subscribers: Vec::new(),
+            })),
+        }
+    }
+
+    /// Returns true once the state is created
+    pub fn is_initialized(&self) -> bool {
+        self.store.get().is_some()
+    }
+
+    // Returns the store, it's started first if this is the first access.
+    // There's no await point in between taking the pending parts and setting the store, so a cancelled access can't lose them.
+    fn store(&self) -> &Store<State, Action, RootReducer> {
+        self.store.get_or_init(|| {
+            let pending = self.pending.lock().unwrap().take().expect("the store is only started once");
+
+            let store = Store::new_with_state(pending.root_reducer, (pending.init)());
+            store.subscribe_batch_detached(pending.subscribers);
+            for (subscriber, condition) in pending.until_subscribers {
+                store.subscribe_until_detached(subscriber, condition);
+            }
+
+            store
+        })
+    }
+
+    /// Dispatch an action, the store is started first if this is the first access
+    pub async fn dispatch(&self, action: Action) {
+        self.store().dispatch(action).await
+    }
+
+    /// Select a part of the state, the store is started first if this is the first access
+    pub async fn select<S, Result>(&self, selector: S) -> Result
+    where
+        S: Selector<State, Result = Result> + Send + 'static,
+        Result: Send + 'static,
+    {
+        self.store().select(selector).await
+    }
+
+    /// Subscribe to state changes, this doesn't start the store
+    pub async fn subscribe<S: Subscriber<State> + Send + 'static>(&self, subscriber: S) {
+        if let Some(pending) = self.pending.lock().unwrap().as_mut() {
+            pending.subscribers.push(Box::new(subscriber));
+            return;
+        }
+
+        self.store().subscribe(subscriber).await
+    }
+
+    /// Subscribe to state changes until the condition is met, this doesn't start the store.
+    /// The subscriber is removed from the store after the notification for the state that meets the condition.
+    pub async fn subscribe_until<S, P>(&self, subscriber: S, condition: P)
+    where
+        S: Subscriber<State> + Send + 'static,
+        P: Fn(&State) -> bool + Send + 'static,
+    {
+        if let Some(pending) = self.pending.lock().unwrap().as_mut() {
+            pending.until_subscribers.push((Box::new(subscriber), Box::new(condition)));
+            return;
+        }
+
+        self.store().subscribe_until(subscriber, condition).await
+    }
+}
+
+#[async_trait]
+impl<State, Action, RootReducer> StoreApi<State, Action> for ColdStore<State, Action, RootReducer>
+where
+    Action: Send + Sync + 'static,
+    RootReducer: Reducer<State, Action> + Send + Sync + 'static,
+    State: Send + Sync + 'static,
+{
+    async fn dispatch<A: Into<Action> + Send>(&self, action: A) {
+        ColdStore::dispatch(self, action.into()).await
+    }
+
+    async fn select<S: Selector<State, Result = Result>, Result>(&self, selector: S) -> Result
+    where
+        S: Selector<State, Result = Result> + Send + 'static,
+        Result: Send + 'static,
+    {
+        ColdStore::select(self, selector).await
+    }
+
+    async fn subscribe<S: Subscriber<State> + Send + 'static>(&self, subscriber: S) {
+        ColdStore::subscribe(self, subscriber).await
+    }
+
+    async fn subscribe_until<S, P>(&self, subscriber: S, condition: P)
+    where
+        S: Subscriber<State> + Send + Sync + 'static,
+        P: Fn(&State) -> bool + Send + Sync + 'static,
+    {
+        ColdStore::subscribe_until(self, subscriber, condition).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::Arc;
+
+    fn counter_reducer(state: i32, add: i32) -> i32 {
+        state + add
+    }
+
+    fn counting_init(calls: &Arc<AtomicUsize>) -> impl FnOnce() -> i32 + Send + 'static {
+        let calls = calls.clone();
+        move || {
+            calls.fetch_add(1, Ordering::SeqCst);
+            10
+        }
+    }
+
+    #[tokio::test]
+    async fn init_runs_once_on_first_select() {
+        let calls = Arc::new(AtomicUsize::new(0));
+        let store = ColdStore::new(counter_reducer, counting_init(&calls));
+        assert_eq!(calls.load(Ordering::SeqCst), 0);
+
+        assert_eq!(store.select(|state: &i32| *state).await, 10);
+        assert_eq!(calls.load(Ordering::SeqCst), 1);
+
+        store.dispatch(5).await;
+        assert_eq!(store.select(|state: &i32| *state).await, 15);
+        assert_eq!(calls.load(Ordering::SeqCst), 1);
+    }
+
+    #[tokio::test]
+    async fn init_is_skipped_when_unused() {
+        let calls = Arc::new(AtomicUsize::new(0));
+        let store = ColdStore::new(counter_reducer, counting_init(&calls));
+
+        store.subscribe(|_: &i32| {}).await;
+        drop(store);
+
+        assert_eq!(calls.load(Ordering::SeqCst), 0);
+    }
+
+    #[tokio::test]
+    async fn subscribers_are_registered_on_start() {
+        let store = ColdStore::new(counter_reducer, || 0);
+
+        let notified = Arc::new(Mutex::new(Vec::new()));
+        let notified_clone = notified.clone();
+        store.subscribe(move |state: &i32| notified_clone.lock().unwrap().push(*state)).await;
+        assert!(!store.is_initialized());
+
+        store.dispatch(1).await;
+        store.dispatch(2).await;
+
+        assert!(store.is_initialized());
+        assert_eq!(*notified.lock().unwrap(), vec![1, 3]);
+    }
+
+    #[tokio::test]
+    async fn cancelled_first_access_still_starts_the_store() {
+        let store = ColdStore::new(counter_reducer, || 10);
+
+        // The first access is cancelled while it waits for the store to respond
+        let select = store.select(|state: &i32| *state);
+        assert!(futures::FutureExt::now_or_never(select).is_none());
+        assert!(store.is_initialized());
+
+        store.dispatch(5).await;
+        assert_eq!(store.select(|state: &i32| *state).await, 15);
+    }
+
+    #[tokio::test]
+    async fn until_subscribers_are_removed() {
+        let store = ColdStore::new(counter_reducer, || 0);
+
+        // Both a subscriber queued before the start and one registered afterwards are dropped once their condition holds
+        let notified = Arc::new(AtomicUsize::new(0));
+        let queued = notified.clone();
+        let subscriber = move |_: &i32| {
+            queued.fetch_add(1, Ordering::SeqCst);
+        };
+        StoreApi::subscribe_until(&store, subscriber, |state: &i32| *state >= 2).await;
+        store.dispatch(1).await;
+
+        let registered = notified.clone();
+        let subscriber = move |_: &i32| {
+            registered.fetch_add(1, Ordering::SeqCst);
+        };
+        StoreApi::subscribe_until(&store, subscriber, |state: &i32| *state >= 3).await;
+        assert_eq!(Arc::strong_count(&notified), 3);
+
+        store.dispatch(1).await;
+        store.dispatch(1).await;
+        assert_eq!(store.select(|state: &i32| *state).await, 3);
+        assert_eq!(notified.load(Ordering::SeqCst), 4);
+        assert_eq!(Arc::strong_count(&notified), 1);
+    }
+}
@@ -0,0 +1,207 @@
+use crate::{Reducer, Selector, Store, Subscriber};
+use std::sync::{Arc, Mutex};
+
+type EventSubscribers<Event> = Arc<Mutex<Vec<Box<dyn Subscriber<Event> + Send>>>>;
+
+/// A store which separates commands (the intent to change) from events (what happened).
+///
+/// Dispatching a command runs the command reducer, which returns the events produced by the command.
+/// Every event is then sent to the event subscribers and applied to the state by the event reducer, in order.
+///
+/// ## Example
+/// ```
+/// use redux_rs::CommandEventStore;
+///
+/// enum Command {
+///     Deposit(u32),
+/// }
+///
+/// enum Event {
+///     Deposited(u32),
+/// }
+///
+/// fn command_reducer(state: u32, command: Command) -> (u32, Vec<Event>) {
+///     match command {
+///         Command::Deposit(amount) => (state, vec![Event::Deposited(amount)]),
+///     }
+/// }
+///
+/// fn event_reducer(state: u32, event: Event) -> u32 {
+///     match event {
+///         Event::Deposited(amount) => state + amount,
+///     }
+/// }
+///
+/// # #[tokio::main(flavor = "current_thread")]
+/// # async fn main() {
+/// let store = CommandEventStore::new_with_state(command_reducer, event_reducer, 0);
+///
+/// store.dispatch(Command::Deposit(10)).await;
+/// assert_eq!(store.select(|balance: &u32| *balance).await, 10);
+/// # }
+/// ```
+pub struct CommandEventStore<State, Command, Event>
+where
+    State: Send + 'static,
+    Command: Send + 'static,
+    Event: 'static,
+{
+    store: Store<State, Command, CommandEventReducer<State, Command, Event>>,
+    event_subscribers: EventSubscribers<Event>,
+}
+
+struct CommandEventReducer<State, Command, Event> {
+    command_reducer: fn(State, Command) -> (State, Vec<Event>),
+    event_reducer: fn(State, Event) -> State,
+    event_subscribers: EventSubscribers<Event>,
+}
+
+impl<State, Command, Event> Reducer<State, Command> for CommandEventReducer<State, Command, Event> {
+    fn reduce(&self, state: State, command: Command) -> State {
+        let (mut state, events) = (self.command_reducer)(state, command);
+
+        let event_subscribers = self.event_subscribers.lock().unwrap();
+        for event in events {
+            for subscriber in event_subscribers.iter() {
+                subscriber.notify(&event);
+            }
+
+            state = (self.event_reducer)(state, event);
+        }
+
+        state
+    }
+}
+
+impl<State, Command, Event> CommandEventStore<State, Command, Event>
+where
+    State: Send + 'static,
+    Command: Send + 'static,
+    Event: 'static,
+{
+    /// Create a new store with the given command and event reducers and the default state
+    pub fn new(command_reducer: fn(State, Command) -> (State, Vec<Event>), event_reducer: fn(State, Event) -> State) -> Self
+    where
+        State: Default,
+    {
+        Self::new_with_state(command_reducer, event_reducer, Default::default())
+    }
+
+    /// Create a new store with the given command and event reducers and the provided state
+    pub fn new_with_state(command_reducer: fn(State, Command) -> (State, Vec<Event>), event_reducer: fn(State, Event) -> State, state: State) -> Self {
+        let event_subscribers: EventSubscribers<Event> = Default::default();
+        let reducer = CommandEventReducer {
+            command_reducer,
+            event_reducer,
+            event_subscribers: event_subscribers.clone(),
+        };
+
+        CommandEventStore {
+            store: Store::new_with_state(reducer, state),
+            event_subscribers,
+        }
+    }
+
+    /// Dispatch a command to the store, the events it produces are applied before this method returns
+    pub async fn dispatch(&self, command: Command) {
+        self.store.dispatch(command).await
+    }
+
+    /// Select a part of the state
+    pub async fn select<S, Result>(&self, selector: S) -> Result
+    where
+        S: Selector<State, Result = Result> + Send + 'static,
+        Result: Send + 'static,
+    {
+        self.store.select(selector).await
+    }
+
+    /// Returns a cloned version of the state
+    pub async fn state_cloned(&self) -> State
+    where
+        State: Clone,
+    {
+        self.store.state_cloned().await
+    }
+
+    /// Subscribe to state changes, the subscriber is notified once per dispatched command
+    pub async fn subscribe<S: Subscriber<State> + Send + 'static>(&self, subscriber: S) {
+        self.store.subscribe(subscriber).await
+    }
+
+    /// Subscribe to the events, the subscriber is notified of every event before it is applied to the state
+    pub fn subscribe_events<S: Subscriber<Event> + Send + 'static>(&self, subscriber: S) {
+        self.event_subscribers.lock().unwrap().push(Box::new(subscriber));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Clone, Debug, Default, PartialEq)]
+    struct Bank {
+        alice: i64,
+        bob: i64,
+    }
+
+    #[derive(Clone, Copy, Debug, PartialEq)]
+    enum Account {
+        Alice,
+        Bob,
+    }
+
+    enum Command {
+        Transfer { from: Account, to: Account, amount: i64 },
+    }
+
+    #[derive(Clone, Debug, PartialEq)]
+    enum Event {
+        Debited(Account, i64),
+        Credited(Account, i64),
+    }
+
+    fn command_reducer(state: Bank, command: Command) -> (Bank, Vec<Event>) {
+        match command {
+            Command::Transfer { from, to, amount } => (state, vec![Event::Debited(from, amount), Event::Credited(to, amount)]),
+        }
+    }
+
+    fn balance(state: &mut Bank, account: Account) -> &mut i64 {
+        match account {
+            Account::Alice => &mut state.alice,
+            Account::Bob => &mut state.bob,
+        }
+    }
+
+    fn event_reducer(mut state: Bank, event: Event) -> Bank {
+        match event {
+            Event::Debited(account, amount) => *balance(&mut state, account) -= amount,
+            Event::Credited(account, amount) => *balance(&mut state, account) += amount,
+        }
+        state
+    }
+
+    #[tokio::test]
+    async fn bank_transfer() {
+        let store = CommandEventStore::new_with_state(command_reducer, event_reducer, Bank { alice: 100, bob: 0 });
+
+        let events = Arc::new(Mutex::new(Vec::new()));
+        let events_clone = events.clone();
+        store.subscribe_events(move |event: &Event| events_clone.lock().unwrap().push(event.clone()));
+
+        store
+            .dispatch(Command::Transfer {
+                from: Account::Alice,
+                to: Account::Bob,
+                amount: 30,
+            })
+            .await;
+
+        assert_eq!(store.state_cloned().await, Bank { alice: 70, bob: 30 });
+        assert_eq!(
+            *events.lock().unwrap(),
+            vec![Event::Debited(Account::Alice, 30), Event::Credited(Account::Bob, 30)]
+        );
+    }
+}
@@ -0,0 +1,272 @@
+use crate::{MiddleWare, StoreApi, StoreWithMiddleware, Subscriber};
+use async_trait::async_trait;
+use std::any::Any;
+use std::sync::Arc;
+
+// The store seen by the outer middleware: the inner middleware wrapped around the underlying store
+type Layer<Inner, M, State, Action> = StoreWithMiddleware<Inner, M, State, Action, Action>;
+
+/// Two middlewares chained into a single one.
+///
+/// Actions are first handled by the outer middleware, everything it dispatches is handled by the second middleware,
+/// which in turn dispatches to the underlying store.
+/// Wrapping a store with `a.chain_middleware(b)` behaves the same as `.wrap(b).await.wrap(a).await`.
+///
+/// More middlewares can be chained with the `|` operator: `a.chain_middleware(b) | c` runs `a`, then `b`, then `c`.
+/// Because of Rust's orphan rules `|` can't be implemented for every middleware,
+/// a chain of your own middlewares has to start with `chain_middleware` (or `ComposedMiddleware::new`).
+/// The middlewares of this crate (e.g. `LoggerMiddleware`, `ThunkMiddleware`) support `|` directly.
+/// Only the first middleware of the chain can convert actions, like `ThunkMiddleware`,
+/// the other middlewares have to handle the action type it dispatches.
+///
+/// NOTE: A composed middleware counts as a single layer for `StoreApi::middleware_depth`,
+/// no matter how many middlewares it contains.
+///
+/// ## Example
+/// ```
+/// use async_trait::async_trait;
+/// use std::sync::Arc;
+/// use redux_rs::{MiddleWare, MiddleWareExt, Store, StoreApi};
+///
+/// #[derive(Default)]
+/// struct Counter(i8);
+///
+/// #[derive(Debug)]
+/// enum Action {
+///     Increment,
+/// }
+///
+/// fn counter_reducer(state: Counter, action: Action) -> Counter {
+///     match action {
+///         Action::Increment => Counter(state.0 + 1),
+///     }
+/// }
+///
+/// struct PrintMiddleware(&'static str);
+/// #[async_trait]
+/// impl<Inner> MiddleWare<Counter, Action, Inner> for PrintMiddleware
+/// where
+///     Inner: StoreApi<Counter, Action> + Send + Sync,
+/// {
+///     async fn dispatch(&self, action: Action, inner: &Arc<Inner>) {
+///         println!("[{}] {:?}", self.0, action);
+///         inner.dispatch(action).await;
+///     }
+/// }
+///
+/// # #[tokio::main(flavor = "current_thread")]
+/// # async fn main() {
+/// let middleware = PrintMiddleware("first").chain_middleware(PrintMiddleware("second")) | PrintMiddleware("third");
+/// let store = Store::new(counter_reducer).wrap(middleware).await;
+///
+/// store.dispatch(Action::Increment).await;
+/// # }
+/// ```
+pub struct ComposedMiddleware<M1, M2> {
+    outer: M1,
+    pending: Option<M2>,
+    // The inner middleware wrapped around the underlying store, created on init.
+    // The type of the underlying store is only known at that moment, hence the type erasure.
+    layer: Option<Box<dyn Any + Send + Sync>>,
+}
+
+impl<M1, M2> ComposedMiddleware<M1, M2> {
+    /// Chain two middlewares, `outer` handles every action before `inner` does
+    pub fn new(outer: M1, inner: M2) -> Self {
+        ComposedMiddleware {
+            outer,
+            pending: Some(inner),
+            layer: None,
+        }
+    }
+}
+
+impl<M1, M2> ComposedMiddleware<M1, M2> {
+    fn layer<State, Action, Inner>(&self) -> &Arc<Layer<Inner, M2, State, Action>>
+    where
+        Inner: StoreApi<State, Action> + Send + Sync + 'static,
+        M2: MiddleWare<State, Action, Inner> + Send + Sync + 'static,
+        State: Send + Sync + 'static,
+        Action: Send + Sync + 'static,
+    {
+        self.layer
+            .as_ref()
+            .and_then(|layer| layer.downcast_ref::<Arc<Layer<Inner, M2, State, Action>>>())
+            .expect("composed middleware used before it was initialized")
+    }
+}
+
+// The outer middleware may convert the actions, like `ThunkMiddleware`, the inner one handles the converted actions
+#[async_trait]
+impl<M1, M2, State, OuterAction, Action, Inner> MiddleWare<State, OuterAction, Inner, Action> for ComposedMiddleware<M1, M2>
+where
+    Inner: StoreApi<State, Action> + Send + Sync + 'static,
+    M1: MiddleWare<State, OuterAction, Layer<Inner, M2, State, Action>, Action> + Send + Sync,
+    M2: MiddleWare<State, Action, Inner> + Send + Sync + 'static,
+    State: Send + Sync + 'static,
+    OuterAction: Send + Sync + 'static,
+    Action: Send + Sync + 'static,
+{
+    async fn init(&mut self, inner: &Arc<Inner>) {
+        let middleware = self.pending.take().expect("composed middleware can only be initialized once");
+        let layer = Arc::new(StoreWithMiddleware::new_shared(inner.clone(), middleware).await);
+
+        self.outer.init(&layer).await;
+        self.layer = Some(Box::new(layer));
+    }
+
+    async fn dispatch(&self, action: OuterAction, _inner: &Arc<Inner>) {
+        self.outer.dispatch(action, self.layer()).await
+    }
+
+    async fn on_subscribe(&self, subscriber: &(dyn Subscriber<State> + Sync), _inner: &Arc<Inner>) {
+        let layer = self.layer();
+
+        // Hooks run innermost first, like the hooks of separately wrapped middlewares
+        layer.on_subscribe(subscriber).await;
+        self.outer.on_subscribe(subscriber, layer).await;
+    }
+
+    async fn teardown(&self, _inner: &Arc<Inner>) {
+        let layer = self.layer();
+
+        self.outer.teardown(layer).await;
+        layer.teardown_layer().await;
+    }
+}
+
+// Chains any middleware after `$middleware` with the `|` operator, see `ComposedMiddleware`.
+// Every middleware of this crate uses it, `|` can't be implemented for all types at once because of the orphan rules.
+macro_rules! impl_middleware_bitor {
+    ($middleware:ident $(<$($param:ident),+>)?) => {
+        impl<$($($param,)+)? Rhs> std::ops::BitOr<Rhs> for $middleware$(<$($param),+>)? {
+            type Output = $crate::ComposedMiddleware<Self, Rhs>;
+
+            fn bitor(self, rhs: Rhs) -> Self::Output {
+                $crate::ComposedMiddleware::new(self, rhs)
+            }
+        }
+    };
+}
+pub(crate) use impl_middleware_bitor;
+
+impl_middleware_bitor!(ComposedMiddleware<M1, M2>);
+
+/// Entry point for chaining middlewares, see `ComposedMiddleware`
+///
+/// The trait is implemented for every type, so the method is named to stay clear of
+/// combinators like `FutureExt::then` when both traits are in scope.
+pub trait MiddleWareExt: Sized {
+    /// Chain `next` after this middleware, everything this middleware dispatches is handled by `next`
+    fn chain_middleware<M>(self, next: M) -> ComposedMiddleware<Self, M> {
+        ComposedMiddleware::new(self, next)
+    }
+}
+
+impl<M> MiddleWareExt for M {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Store;
+    use std::sync::Mutex;
+
+    #[derive(Default)]
+    struct LogStore {
+        logs: Vec<String>,
+    }
+
+    struct Log(String);
+
+    fn log_reducer(store: LogStore, action: Log) -> LogStore {
+        let mut logs = store.logs;
+        logs.push(action.0);
+
+        LogStore { logs }
+    }
+
+    struct PrefixMiddleware {
+        prefix: &'static str,
+        logs: Arc<Mutex<Vec<String>>>,
+    }
+
+    impl PrefixMiddleware {
+        fn new(prefix: &'static str, logs: &Arc<Mutex<Vec<String>>>) -> Self {
+            PrefixMiddleware { prefix, logs: logs.clone() }
+        }
+    }
+
+    #[async_trait]
+    impl<Inner> MiddleWare<LogStore, Log, Inner> for PrefixMiddleware
+    where
+        Inner: StoreApi<LogStore, Log> + Send + Sync,
+    {
+        async fn dispatch(&self, action: Log, inner: &Arc<Inner>) {
+            self.logs.lock().unwrap().push(format!("[{}] before", self.prefix));
+
+            // Tag the action so we can see which middlewares it passed
+            inner.dispatch(Log(format!("{}>{}", action.0, self.prefix))).await;
+
+            self.logs.lock().unwrap().push(format!("[{}] after", self.prefix));
+        }
+
+        async fn on_subscribe(&self, _subscriber: &(dyn Subscriber<LogStore> + Sync), _inner: &Arc<Inner>) {
+            self.logs.lock().unwrap().push(format!("[{}] subscribe", self.prefix));
+        }
+    }
+
+    #[tokio::test]
+    async fn composed_equals_wrapped() {
+        let composed_logs = Arc::new(Mutex::new(Vec::new()));
+        let composed = PrefixMiddleware::new("a", &composed_logs).chain_middleware(PrefixMiddleware::new("b", &composed_logs))
+            | PrefixMiddleware::new("c", &composed_logs);
+        let composed_store = Store::new(log_reducer).wrap(composed).await;
+
+        let wrapped_logs = Arc::new(Mutex::new(Vec::new()));
+        let wrapped_store = Store::new(log_reducer)
+            .wrap(PrefixMiddleware::new("c", &wrapped_logs))
+            .await
+            .wrap(PrefixMiddleware::new("b", &wrapped_logs))
+            .await
+            .wrap(PrefixMiddleware::new("a", &wrapped_logs))
+            .await;
+
+        composed_store.dispatch(Log("log".to_string())).await;
+        wrapped_store.dispatch(Log("log".to_string())).await;
+
+        let expected = vec![
+            "[a] before".to_string(),
+            "[b] before".to_string(),
+            "[c] before".to_string(),
+            "[c] after".to_string(),
+            "[b] after".to_string(),
+            "[a] after".to_string(),
+        ];
+        assert_eq!(*composed_logs.lock().unwrap(), expected);
+        assert_eq!(*wrapped_logs.lock().unwrap(), expected);
+
+        let selector = |state: &LogStore| state.logs.clone();
+        assert_eq!(composed_store.select(selector).await, vec!["log>a>b>c".to_string()]);
+        assert_eq!(wrapped_store.select(selector).await, vec!["log>a>b>c".to_string()]);
+    }
+
+    #[tokio::test]
+    async fn composed_counts_as_one_layer() {
+        let logs = Arc::new(Mutex::new(Vec::new()));
+        let composed = ComposedMiddleware::new(PrefixMiddleware::new("a", &logs), PrefixMiddleware::new("b", &logs)) | PrefixMiddleware::new("c", &logs);
+
+        let store = Store::new(log_reducer).wrap(composed).await;
+        assert_eq!(store.middleware_depth(), 1);
+    }
+
+    #[tokio::test]
+    async fn composed_runs_every_on_subscribe_hook() {
+        let logs = Arc::new(Mutex::new(Vec::new()));
+        let composed = PrefixMiddleware::new("a", &logs).chain_middleware(PrefixMiddleware::new("b", &logs)) | PrefixMiddleware::new("c", &logs);
+        let store = Store::new(log_reducer).wrap(composed).await;
+
+        store.subscribe(|_: &LogStore| {}).await;
+
+        assert_eq!(*logs.lock().unwrap(), vec!["[c] subscribe", "[b] subscribe", "[a] subscribe"]);
+    }
+}
@@ -0,0 +1,166 @@
+use crate::{Reducer, Store};
+use serde::de::DeserializeOwned;
+use std::error::Error;
+use std::fmt::{Display, Formatter};
+use std::io;
+use std::path::{Path, PathBuf};
+
+/// Returned when a store can't be created from a configuration file, see `Store::new_from_config`
+#[derive(Debug)]
+pub enum ConfigError {
+    /// The file couldn't be read
+    Io(io::Error),
+    /// The file isn't valid TOML or JSON, or it doesn't match the configuration type
+    Parse(Box<dyn Error + Send + Sync>),
+}
+
+impl Display for ConfigError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ConfigError::Io(error) => write!(f, "could not read the configuration file: {}", error),
+            ConfigError::Parse(error) => write!(f, "could not parse the configuration file: {}", error),
+        }
+    }
+}
+
+impl Error for ConfigError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            ConfigError::Io(error) => Some(error),
+            ConfigError::Parse(error) => Some(error.as_ref()),
+        }
+    }
+}
+
+impl From<io::Error> for ConfigError {
+    fn from(error: io::Error) -> Self {
+        ConfigError::Io(error)
+    }
+}
+
+fn parse_config<Config: DeserializeOwned>(path: &Path, contents: &str) -> Result<Config, ConfigError> {
+    let is_json = path.extension().is_some_and(|extension| extension.eq_ignore_ascii_case("json"));
+    if is_json {
+        serde_json::from_str(contents).map_err(|error| ConfigError::Parse(error.into()))
+    } else {
+        toml::from_str(contents).map_err(|error| ConfigError::Parse(error.into()))
+    }
+}
+
+impl<State, Action, RootReducer> Store<State, Action, RootReducer>
+where
+    Action: Send + 'static,
+    RootReducer: Reducer<State, Action> + Send + 'static,
+    State: Send + 'static,
+{
+    /// Create a store from a configuration file.
+    ///
+    /// The file is deserialized into `Config`, as JSON when it has a `.json` extension and as TOML otherwise.
+    /// `builder` turns the configuration into the root reducer and the initial state of the store.
+    ///
+    /// ## Example
+    /// ```
+    /// use redux_rs::{ConfigError, Store};
+    /// use serde::Deserialize;
+    ///
+    /// #[derive(Deserialize)]
+    /// struct Config {
+    ///     initial_count: u32,
+    /// }
+    ///
+    /// fn reducer(state: u32, add: u32) -> u32 {
+    ///     state + add
+    /// }
+    ///
+    /// # #[tokio::main(flavor = "current_thread")]
+    /// # async fn main() -> Result<(), ConfigError> {
+    /// let path = std::env::temp_dir().join(format!("redux_rs_config_doc_{}.toml", std::process::id()));
+    /// std::fs::write(&path, "initial_count = 10")?;
+    ///
+    /// let store = Store::new_from_config(path.clone(), |config: Config| (reducer, config.initial_count));
+    /// # std::fs::remove_file(&path)?;
+    /// assert_eq!(store?.state_cloned().await, 10);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn new_from_config<Config>(path: PathBuf, builder: fn(Config) -> (RootReducer, State)) -> Result<Self, ConfigError>
+    where
+        Config: DeserializeOwned,
+    {
+        let contents = std::fs::read_to_string(&path)?;
+        let config = parse_config(&path, &contents)?;
+
+        let (root_reducer, state) = builder(config);
+        Ok(Self::new_with_state(root_reducer, state))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde::Deserialize;
+
+    #[derive(Deserialize)]
+    struct CounterConfig {
+        initial_count: i32,
+        step: i32,
+    }
+
+    struct StepReducer {
+        step: i32,
+    }
+
+    impl Reducer<i32, ()> for StepReducer {
+        fn reduce(&self, state: i32, _action: ()) -> i32 {
+            state + self.step
+        }
+    }
+
+    fn build(config: CounterConfig) -> (StepReducer, i32) {
+        (StepReducer { step: config.step }, config.initial_count)
+    }
+
+    fn write_config(name: &str, contents: &str) -> PathBuf {
+        let path = std::env::temp_dir().join(format!("redux_rs_{}_{}", std::process::id(), name));
+        std::fs::write(&path, contents).unwrap();
+        path
+    }
+
+    #[tokio::test]
+    async fn store_from_toml_config() {
+        let path = write_config("counter.toml", "initial_count = 42\nstep = 2\n");
+
+        let store = Store::new_from_config(path.clone(), build);
+        std::fs::remove_file(path).unwrap();
+        let store = store.unwrap();
+        assert_eq!(store.state_cloned().await, 42);
+
+        store.dispatch(()).await;
+        assert_eq!(store.state_cloned().await, 44);
+    }
+
+    #[tokio::test]
+    async fn store_from_json_config() {
+        let path = write_config("counter.json", r#"{ "initial_count": 42, "step": -1 }"#);
+
+        let store = Store::new_from_config(path.clone(), build);
+        std::fs::remove_file(path).unwrap();
+        let store = store.unwrap();
+        store.dispatch(()).await;
+        assert_eq!(store.state_cloned().await, 41);
+    }
+
+    #[tokio::test]
+    async fn config_errors() {
+        let missing = std::env::temp_dir().join(format!("redux_rs_{}_missing.toml", std::process::id()));
+        let result = Store::new_from_config(missing, build);
+        assert!(matches!(result, Err(ConfigError::Io(_))));
+
+        let path = write_config("invalid.toml", "initial_count = \"many\"\n");
+        let result = Store::new_from_config(path.clone(), build);
+        std::fs::remove_file(path).unwrap();
+        let error = result.err().unwrap();
+        assert!(matches!(error, ConfigError::Parse(_)));
+        assert!(error.source().unwrap().is::<toml::de::Error>());
+    }
+}
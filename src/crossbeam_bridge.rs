@@ -0,0 +1,81 @@
+use crate::Subscriber;
+use crossbeam_channel::Sender;
+
+/// A subscriber which sends a clone of every new state on a crossbeam channel.
+///
+/// This bridges the store to synchronous consumers, like worker threads which block on a crossbeam receiver.
+/// States are silently dropped once the receiving side of the channel is disconnected.
+///
+/// ## Example
+/// ```
+/// use redux_rs::{CrossbeamBridge, Store};
+///
+/// fn reducer(state: u32, add: u32) -> u32 {
+///     state + add
+/// }
+///
+/// # #[tokio::main(flavor = "current_thread")]
+/// # async fn main() {
+/// let (sender, receiver) = crossbeam_channel::unbounded();
+///
+/// let store = Store::new(reducer);
+/// store.subscribe(CrossbeamBridge::new(sender)).await;
+///
+/// store.dispatch(5).await;
+/// assert_eq!(receiver.recv().unwrap(), 5);
+/// # }
+/// ```
+pub struct CrossbeamBridge<State> {
+    sender: Sender<State>,
+}
+
+impl<State> CrossbeamBridge<State>
+where
+    State: Clone,
+{
+    /// Create a subscriber which sends every new state on the given sender
+    pub fn new(sender: Sender<State>) -> Self {
+        CrossbeamBridge { sender }
+    }
+}
+
+impl<State> Subscriber<State> for CrossbeamBridge<State>
+where
+    State: Clone,
+{
+    fn notify(&self, state: &State) {
+        // A disconnected receiver is not an error of the store
+        let _ = self.sender.send(state.clone());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Store;
+    use std::sync::{Arc, Barrier};
+    use std::thread;
+
+    #[tokio::test]
+    async fn states_received_in_order() {
+        let (sender, receiver) = crossbeam_channel::unbounded();
+        let barrier = Arc::new(Barrier::new(2));
+
+        let store = Store::new(|state: u32, add: u32| state + add);
+        store.subscribe(CrossbeamBridge::new(sender)).await;
+
+        let consumer_barrier = barrier.clone();
+        let consumer = thread::spawn(move || {
+            // Wait until all actions are dispatched
+            consumer_barrier.wait();
+            receiver.try_iter().collect::<Vec<_>>()
+        });
+
+        for add in 1..=5 {
+            store.dispatch(add).await;
+        }
+        barrier.wait();
+
+        assert_eq!(consumer.join().unwrap(), vec![1, 3, 6, 10, 15]);
+    }
+}
@@ -0,0 +1,232 @@
+use crate::{Reducer, Selector, Store};
+use std::any::{Any, TypeId};
+use std::collections::HashMap;
+
+type Derivation<State> = Box<dyn Fn(&State) -> Box<dyn Any + Send> + Send>;
+
+/// The state kept by a `DerivedStateStore`, the base state together with the derived values
+pub struct DerivedState<State> {
+    base: State,
+    derived: HashMap<TypeId, Box<dyn Any + Send>>,
+}
+
+impl<State> DerivedState<State> {
+    /// The state produced by the root reducer
+    pub fn base(&self) -> &State {
+        &self.base
+    }
+
+    /// The derived value of type `T`, if a derivation for `T` was registered
+    pub fn derived<T: Any>(&self) -> Option<&T> {
+        self.derived.get(&TypeId::of::<T>()).and_then(|derived| derived.downcast_ref())
+    }
+}
+
+/// A store which keeps derived values next to its state.
+///
+/// The derivations are registered once, when building the store.
+/// After every dispatch the derivations are re-run, but only if the base state changed.
+/// Expensive computed values can then be read with `select_derived` without recomputing them on every select.
+///
+/// Every derivation is stored by the type it returns, so each derived type can only be registered once.
+///
+/// The base state is cloned on every dispatch, the root reducer takes the state by value and the copy is needed to tell whether it changed.
+/// For a large state this clone can cost more than the derivations it saves, keep the state cheap to clone (e.g. with `Arc`).
+///
+/// ## Example
+/// ```
+/// use redux_rs::DerivedStateStore;
+///
+/// #[derive(Clone, Debug, PartialEq)]
+/// struct Total(u32);
+///
+/// fn reducer(state: Vec<u32>, item: u32) -> Vec<u32> {
+///     let mut state = state;
+///     state.push(item);
+///     state
+/// }
+///
+/// # #[tokio::main(flavor = "current_thread")]
+/// # async fn main() {
+/// let store = DerivedStateStore::builder(reducer, Vec::new())
+///     .register_derivation(|items: &Vec<u32>| Total(items.iter().sum()))
+///     .build();
+///
+/// store.dispatch(5).await;
+/// store.dispatch(7).await;
+/// assert_eq!(store.select_derived::<Total>().await, Some(Total(12)));
+/// # }
+/// ```
+pub struct DerivedStateStore<State, Action, RootReducer>
+where
+    State: Clone + PartialEq + Send + 'static,
+    Action: Send + 'static,
+    RootReducer: Reducer<State, Action> + Send + 'static,
+{
+    store: Store<DerivedState<State>, Action, DerivedReducer<State, RootReducer>>,
+}
+
+/// Builder to register the derivations of a `DerivedStateStore`
+pub struct DerivedStateStoreBuilder<State, RootReducer> {
+    root_reducer: RootReducer,
+    state: State,
+    derivations: HashMap<TypeId, Derivation<State>>,
+}
+
+struct DerivedReducer<State, RootReducer> {
+    root_reducer: RootReducer,
+    derivations: HashMap<TypeId, Derivation<State>>,
+}
+
+impl<State, RootReducer> DerivedReducer<State, RootReducer> {
+    fn derive(&self, state: &State) -> HashMap<TypeId, Box<dyn Any + Send>> {
+        self.derivations.iter().map(|(type_id, derivation)| (*type_id, derivation(state))).collect()
+    }
+}
+
+impl<State, Action, RootReducer> Reducer<DerivedState<State>, Action> for DerivedReducer<State, RootReducer>
+where
+    State: Clone + PartialEq,
+    RootReducer: Reducer<State, Action>,
+{
+    fn reduce(&self, state: DerivedState<State>, action: Action) -> DerivedState<State> {
+        // The root reducer consumes the state, so the old state is cloned to compare against
+        let base = self.root_reducer.reduce(state.base.clone(), action);
+
+        if base == state.base {
+            return DerivedState { base, derived: state.derived };
+        }
+
+        let derived = self.derive(&base);
+        DerivedState { base, derived }
+    }
+}
+
+impl<State: 'static, RootReducer> DerivedStateStoreBuilder<State, RootReducer> {
+    /// Register a derivation, the derived value can be read with `select_derived::<Derived>()`
+    pub fn register_derivation<Derived>(mut self, derivation: fn(&State) -> Derived) -> Self
+    where
+        Derived: Any + Send,
+    {
+        self.derivations.insert(
+            TypeId::of::<Derived>(),
+            Box::new(move |state: &State| Box::new(derivation(state)) as Box<dyn Any + Send>),
+        );
+        self
+    }
+
+    /// Build the store, this computes the derived values for the initial state
+    pub fn build<Action>(self) -> DerivedStateStore<State, Action, RootReducer>
+    where
+        State: Clone + PartialEq + Send + 'static,
+        Action: Send + 'static,
+        RootReducer: Reducer<State, Action> + Send + 'static,
+    {
+        let reducer = DerivedReducer {
+            root_reducer: self.root_reducer,
+            derivations: self.derivations,
+        };
+        let derived = reducer.derive(&self.state);
+
+        DerivedStateStore {
+            store: Store::new_with_state(reducer, DerivedState { base: self.state, derived }),
+        }
+    }
+}
+
+impl<State, Action, RootReducer> DerivedStateStore<State, Action, RootReducer>
+where
+    State: Clone + PartialEq + Send + 'static,
+    Action: Send + 'static,
+    RootReducer: Reducer<State, Action> + Send + 'static,
+{
+    /// Start building a store with the given root reducer and initial state
+    pub fn builder(root_reducer: RootReducer, state: State) -> DerivedStateStoreBuilder<State, RootReducer> {
+        DerivedStateStoreBuilder {
+            root_reducer,
+            state,
+            derivations: HashMap::new(),
+        }
+    }
+
+    /// Dispatch a new action to the store
+    pub async fn dispatch(&self, action: Action) {
+        self.store.dispatch(action).await
+    }
+
+    /// Select a part of the base state
+    pub async fn select<S, Result>(&self, selector: S) -> Result
+    where
+        S: Selector<State, Result = Result> + Send + 'static,
+        Result: Send + 'static,
+    {
+        self.store.select(move |state: &DerivedState<State>| selector.select(&state.base)).await
+    }
+
+    /// Returns a clone of the derived value of type `Derived`, or None if no derivation for this type was registered
+    pub async fn select_derived<Derived>(&self) -> Option<Derived>
+    where
+        Derived: Any + Clone + Send,
+    {
+        self.store.select(|state: &DerivedState<State>| state.derived::<Derived>().cloned()).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    static DERIVATIONS: AtomicUsize = AtomicUsize::new(0);
+
+    #[derive(Clone, Debug, Default, PartialEq)]
+    struct Cart {
+        items: Vec<(String, u32)>,
+    }
+
+    enum CartAction {
+        Add(&'static str, u32),
+        Remove(&'static str),
+    }
+
+    #[derive(Clone, Debug, PartialEq)]
+    struct TotalValue(u32);
+
+    fn cart_reducer(mut state: Cart, action: CartAction) -> Cart {
+        match action {
+            CartAction::Add(name, price) => state.items.push((name.to_string(), price)),
+            CartAction::Remove(name) => state.items.retain(|(item, _)| item != name),
+        }
+        state
+    }
+
+    fn total_value(cart: &Cart) -> TotalValue {
+        DERIVATIONS.fetch_add(1, Ordering::SeqCst);
+        TotalValue(cart.items.iter().map(|(_, price)| price).sum())
+    }
+
+    #[tokio::test]
+    async fn total_cart_value() {
+        let store = DerivedStateStore::builder(cart_reducer, Cart::default())
+            .register_derivation(total_value)
+            .build();
+        assert_eq!(store.select_derived::<TotalValue>().await, Some(TotalValue(0)));
+        assert_eq!(DERIVATIONS.load(Ordering::SeqCst), 1);
+
+        store.dispatch(CartAction::Add("apple", 3)).await;
+        store.dispatch(CartAction::Add("pear", 4)).await;
+        assert_eq!(store.select_derived::<TotalValue>().await, Some(TotalValue(7)));
+        assert_eq!(DERIVATIONS.load(Ordering::SeqCst), 3);
+
+        // Removing an item which is not in the cart does not change the items
+        store.dispatch(CartAction::Remove("banana")).await;
+        assert_eq!(store.select_derived::<TotalValue>().await, Some(TotalValue(7)));
+        assert_eq!(DERIVATIONS.load(Ordering::SeqCst), 3);
+
+        store.dispatch(CartAction::Remove("apple")).await;
+        assert_eq!(store.select_derived::<TotalValue>().await, Some(TotalValue(4)));
+        assert_eq!(DERIVATIONS.load(Ordering::SeqCst), 4);
+
+        assert_eq!(store.select_derived::<u8>().await, None);
+    }
+}
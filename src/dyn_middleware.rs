@@ -0,0 +1,381 @@
+use crate::composed_middleware::impl_middleware_bitor;
+use crate::{MiddleWare, StoreApi};
+use async_trait::async_trait;
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::Arc;
+
+type BoxFuture<'a> = Pin<Box<dyn Future<Output = ()> + Send + 'a>>;
+
+/// Object safe counterpart of `MiddleWare`.
+///
+/// Regular middlewares are generic over the store they wrap, which means they can't be stored as trait objects.
+/// A `DynMiddleWare` doesn't know the store it's wrapping, it only gets a `Next` to pass the action on to.
+/// This makes it possible to build lists of middlewares at runtime.
+///
+/// ## Example
+/// ```
+/// use async_trait::async_trait;
+/// use redux_rs::{DynMiddleWare, Next};
+///
+/// struct PrintMiddleware;
+/// #[async_trait]
+/// impl<State, Action> DynMiddleWare<State, Action> for PrintMiddleware
+/// where
+///     State: 'static,
+///     Action: std::fmt::Debug + Send + 'static,
+/// {
+///     async fn dispatch(&self, action: Action, next: Next<'_, State, Action>) {
+///         println!("Action: {:?}", action);
+///         next.dispatch(action).await;
+///     }
+/// }
+/// ```
+#[async_trait]
+pub trait DynMiddleWare<State, Action>: Send + Sync
+where
+    Action: Send + 'static,
+{
+    /// This method is called every time an action is dispatched to the store.
+    ///
+    /// NOTE: You need to call `next.dispatch(action).await;` otherwise the action won't reach the underlying store.
+    async fn dispatch(&self, action: Action, next: Next<'_, State, Action>);
+
+    /// The name of the middleware, used to identify it when inspecting a stack.
+    /// Defaults to the type name of the middleware.
+    fn middleware_name(&self) -> &str {
+        std::any::type_name::<Self>()
+    }
+}
+
+/// The remainder of a middleware chain, dispatching to it passes the action to the next middleware or the underlying store.
+pub struct Next<'a, State, Action> {
+    middlewares: &'a [Arc<dyn DynMiddleWare<State, Action>>],
+    inner: &'a (dyn Fn(Action) -> BoxFuture<'a> + Send + Sync),
+}
+
+impl<'a, State, Action> Next<'a, State, Action>
+where
+    Action: Send + 'static,
+{
+    /// Create a chain which runs the middlewares in order, followed by `inner`
+    pub fn new(middlewares: &'a [Arc<dyn DynMiddleWare<State, Action>>], inner: &'a (dyn Fn(Action) -> BoxFuture<'a> + Send + Sync)) -> Self {
+        Next { middlewares, inner }
+    }
+
+    /// Pass the action on to the rest of the chain
+    pub async fn dispatch(self, action: Action) {
+        match self.middlewares.split_first() {
+            Some((middleware, middlewares)) => {
+                let next = Next {
+                    middlewares,
+                    inner: self.inner,
+                };
+                middleware.dispatch(action, next).await
+            }
+            None => (self.inner)(action).await,
+        }
+    }
+}
+
+/// Compose a list of middlewares into a single middleware.
+///
+/// Every action passes through the middlewares in list order, the last one forwards to the underlying store.
+/// This is the building block for plugin systems, where the middlewares are only known at runtime.
+///
+/// ## Example
+/// ```
+/// use async_trait::async_trait;
+/// use redux_rs::{pipe, DynMiddleWare, Next, Store, StoreApi};
+///
+/// #[derive(Default)]
+/// struct Counter(i8);
+///
+/// fn reducer(state: Counter, _action: ()) -> Counter {
+///     Counter(state.0 + 1)
+/// }
+///
+/// struct PrintMiddleware(&'static str);
+/// #[async_trait]
+/// impl DynMiddleWare<Counter, ()> for PrintMiddleware {
+///     async fn dispatch(&self, action: (), next: Next<'_, Counter, ()>) {
+///         println!("{}", self.0);
+///         next.dispatch(action).await;
+///     }
+/// }
+///
+/// # #[tokio::main(flavor = "current_thread")]
+/// # async fn main() {
+/// let pipeline = pipe(vec![Box::new(PrintMiddleware("first")), Box::new(PrintMiddleware("second"))]);
+/// let store = Store::new(reducer).wrap(pipeline).await;
+///
+/// store.dispatch(()).await;
+/// # }
+/// ```
+pub fn pipe<State, Action>(middlewares: Vec<Box<dyn DynMiddleWare<State, Action>>>) -> Pipe<State, Action> {
+    Pipe {
+        middlewares: middlewares.into_iter().map(Arc::from).collect(),
+    }
+}
+
+/// Builder for a list of middlewares, which can be inserted at any position.
+///
+/// Regular middlewares are always wrapped around the outside of a store.
+/// A stack makes it possible to insert a middleware in between, when the execution order matters.
+/// Index 0 is the outermost middleware, it's the first one to receive an action.
+/// The stack is turned into a single middleware with `build`.
+///
+/// ## Example
+/// ```
+/// # use async_trait::async_trait;
+/// # use redux_rs::{DynMiddleWare, Next};
+/// # #[derive(Default)]
+/// # struct Counter(i8);
+/// #
+/// # fn reducer(state: Counter, _action: ()) -> Counter {
+/// #     Counter(state.0 + 1)
+/// # }
+/// #
+/// # struct PrintMiddleware(&'static str);
+/// # #[async_trait]
+/// # impl DynMiddleWare<Counter, ()> for PrintMiddleware {
+/// #     async fn dispatch(&self, action: (), next: Next<'_, Counter, ()>) {
+/// #         println!("{}", self.0);
+/// #         next.dispatch(action).await;
+/// #     }
+/// # }
+/// use redux_rs::{MiddlewareStack, Store};
+///
+/// # #[tokio::main(flavor = "current_thread")]
+/// # async fn main() {
+/// let stack = MiddlewareStack::new()
+///     .push(Box::new(PrintMiddleware("logging")))
+///     .push(Box::new(PrintMiddleware("auth")))
+///     // Trace before the auth middleware, this way auth failures are traced as well
+///     .insert_at(1, Box::new(PrintMiddleware("tracing")));
+///
+/// let store = Store::new(reducer).wrap(stack.build()).await;
+/// # }
+/// ```
+pub struct MiddlewareStack<State, Action> {
+    middlewares: Vec<Box<dyn DynMiddleWare<State, Action>>>,
+}
+
+impl<State, Action> MiddlewareStack<State, Action> {
+    /// Create an empty stack
+    pub fn new() -> Self {
+        MiddlewareStack { middlewares: Vec::new() }
+    }
+
+    /// Add a middleware at the inside of the stack, closest to the store
+    pub fn push(mut self, middleware: Box<dyn DynMiddleWare<State, Action>>) -> Self {
+        self.middlewares.push(middleware);
+        self
+    }
+
+    /// Insert a middleware at the given position, index 0 being the outermost middleware.
+    ///
+    /// # Panics
+    /// Panics if `index > len`.
+    pub fn insert_at(mut self, index: usize, middleware: Box<dyn DynMiddleWare<State, Action>>) -> Self {
+        self.middlewares.insert(index, middleware);
+        self
+    }
+
+    /// The number of middlewares in the stack
+    pub fn len(&self) -> usize {
+        self.middlewares.len()
+    }
+
+    /// Returns true if the stack doesn't contain any middleware
+    pub fn is_empty(&self) -> bool {
+        self.middlewares.is_empty()
+    }
+
+    /// Turn the stack into a single middleware, which can be wrapped around a store
+    pub fn build(self) -> Pipe<State, Action> {
+        pipe(self.middlewares)
+    }
+}
+
+impl<State, Action> MiddlewareStack<State, Action>
+where
+    Action: Send + 'static,
+{
+    /// Returns the names of the middlewares, from the outermost to the innermost one
+    pub fn describe(&self) -> Vec<String> {
+        self.middlewares.iter().map(|middleware| middleware.middleware_name().to_string()).collect()
+    }
+}
+
+impl<State, Action> Default for MiddlewareStack<State, Action> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// A list of middlewares acting as a single middleware, see `pipe`
+pub struct Pipe<State, Action> {
+    middlewares: Vec<Arc<dyn DynMiddleWare<State, Action>>>,
+}
+
+impl_middleware_bitor!(Pipe<State, Action>);
+
+#[async_trait]
+impl<State, Action, Inner> MiddleWare<State, Action, Inner> for Pipe<State, Action>
+where
+    State: Send + 'static,
+    Action: Send + 'static,
+    Inner: StoreApi<State, Action> + Send + Sync,
+{
+    async fn dispatch(&self, action: Action, inner: &Arc<Inner>) {
+        let dispatch_inner = |action: Action| inner.dispatch(action);
+        Next::new(&self.middlewares, &dispatch_inner).dispatch(action).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Store;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::Mutex;
+
+    #[derive(Default)]
+    struct Counter {
+        value: i32,
+    }
+
+    struct Add(i32);
+
+    fn counter_reducer(state: Counter, action: Add) -> Counter {
+        Counter { value: state.value + action.0 }
+    }
+
+    struct Logging {
+        logs: Arc<Mutex<Vec<String>>>,
+    }
+
+    #[async_trait]
+    impl DynMiddleWare<Counter, Add> for Logging {
+        async fn dispatch(&self, action: Add, next: Next<'_, Counter, Add>) {
+            self.logs.lock().unwrap().push(format!("add {}", action.0));
+            next.dispatch(action).await;
+        }
+    }
+
+    // Drops negative additions
+    struct Validation;
+
+    #[async_trait]
+    impl DynMiddleWare<Counter, Add> for Validation {
+        async fn dispatch(&self, action: Add, next: Next<'_, Counter, Add>) {
+            if action.0 >= 0 {
+                next.dispatch(action).await;
+            }
+        }
+    }
+
+    // Lets through a limited amount of actions
+    struct RateLimiting {
+        remaining: AtomicUsize,
+    }
+
+    #[async_trait]
+    impl DynMiddleWare<Counter, Add> for RateLimiting {
+        async fn dispatch(&self, action: Add, next: Next<'_, Counter, Add>) {
+            let allowed = self
+                .remaining
+                .fetch_update(Ordering::SeqCst, Ordering::SeqCst, |remaining| remaining.checked_sub(1))
+                .is_ok();
+            if allowed {
+                next.dispatch(action).await;
+            }
+        }
+    }
+
+    #[tokio::test]
+    async fn pipe_as_single_middleware() {
+        let logs = Arc::new(Mutex::new(Vec::new()));
+        let pipeline = pipe(vec![
+            Box::new(Logging { logs: logs.clone() }),
+            Box::new(Validation),
+            Box::new(RateLimiting {
+                remaining: AtomicUsize::new(2),
+            }),
+        ]);
+
+        let store = Store::new(counter_reducer).wrap(pipeline).await;
+
+        store.dispatch(Add(1)).await;
+        store.dispatch(Add(-5)).await;
+        store.dispatch(Add(10)).await;
+        store.dispatch(Add(100)).await;
+
+        // Every action is logged, the negative one is rejected and the last one is rate limited
+        assert_eq!(*logs.lock().unwrap(), vec!["add 1", "add -5", "add 10", "add 100"]);
+        assert_eq!(store.select(|state: &Counter| state.value).await, 11);
+    }
+
+    struct Record {
+        name: &'static str,
+        calls: Arc<Mutex<Vec<&'static str>>>,
+    }
+
+    #[async_trait]
+    impl DynMiddleWare<Counter, Add> for Record {
+        async fn dispatch(&self, action: Add, next: Next<'_, Counter, Add>) {
+            self.calls.lock().unwrap().push(self.name);
+            next.dispatch(action).await;
+        }
+    }
+
+    #[tokio::test]
+    async fn stack_insert_at() {
+        let calls = Arc::new(Mutex::new(Vec::new()));
+        let record = |name| Box::new(Record { name, calls: calls.clone() });
+
+        let stack = MiddlewareStack::new()
+            .push(record("logging"))
+            .push(record("auth"))
+            .push(record("validation"))
+            .insert_at(1, record("tracing"));
+        assert_eq!(stack.len(), 4);
+
+        let store = Store::new(counter_reducer).wrap(stack.build()).await;
+        store.dispatch(Add(1)).await;
+
+        assert_eq!(*calls.lock().unwrap(), vec!["logging", "tracing", "auth", "validation"]);
+        assert_eq!(store.select(|state: &Counter| state.value).await, 1);
+    }
+
+    struct Auth;
+
+    #[async_trait]
+    impl DynMiddleWare<Counter, Add> for Auth {
+        async fn dispatch(&self, action: Add, next: Next<'_, Counter, Add>) {
+            next.dispatch(action).await;
+        }
+
+        fn middleware_name(&self) -> &str {
+            "auth"
+        }
+    }
+
+    #[test]
+    fn stack_describe() {
+        let stack = MiddlewareStack::new()
+            .push(Box::new(Validation))
+            .push(Box::new(Auth))
+            .insert_at(0, Box::new(Logging { logs: Default::default() }));
+
+        assert_eq!(
+            stack.describe(),
+            vec![
+                std::any::type_name::<Logging>().to_string(),
+                std::any::type_name::<Validation>().to_string(),
+                "auth".to_string()
+            ]
+        );
+    }
+}
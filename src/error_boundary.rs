@@ -0,0 +1,182 @@
+use crate::{Reducer, Selector, Store};
+
+/// The state of an `ErrorBoundaryStore`, either the regular state or the error which stopped the store
+#[derive(Clone, Debug, PartialEq)]
+pub enum StoreState<State, Error> {
+    Ok(State),
+    Error(Error),
+}
+
+/// The actions accepted by the store behind an `ErrorBoundaryStore`
+pub enum BoundaryAction<Action> {
+    Action(Action),
+    Reset,
+}
+
+/// A store for fallible reducers, with the signature `Fn(State, Action) -> Result<State, Error>`.
+///
+/// When the reducer returns an error the store keeps that error instead of a state.
+/// While the store holds an error all dispatched actions are dropped, until the store is reset to its initial state.
+///
+/// ## Example
+/// ```
+/// use redux_rs::ErrorBoundaryStore;
+///
+/// fn reducer(state: u8, add: u8) -> Result<u8, String> {
+///     state.checked_add(add).ok_or_else(|| "overflow".to_string())
+/// }
+///
+/// # #[tokio::main(flavor = "current_thread")]
+/// # async fn main() {
+/// let store = ErrorBoundaryStore::new(reducer, 250);
+///
+/// store.dispatch(10).await;
+/// assert_eq!(store.error_state().await, Some("overflow".to_string()));
+///
+/// store.reset().await;
+/// assert_eq!(store.select(|state: &u8| *state).await, Some(250));
+/// # }
+/// ```
+pub struct ErrorBoundaryStore<State, Action, Error, RootReducer>
+where
+    State: Clone + Send + 'static,
+    Action: Send + 'static,
+    Error: Send + 'static,
+    RootReducer: Fn(State, Action) -> Result<State, Error> + Send + 'static,
+{
+    store: Store<StoreState<State, Error>, BoundaryAction<Action>, BoundaryReducer<RootReducer, State>>,
+}
+
+struct BoundaryReducer<RootReducer, State> {
+    root_reducer: RootReducer,
+    initial_state: State,
+}
+
+impl<State, Action, Error, RootReducer> Reducer<StoreState<State, Error>, BoundaryAction<Action>> for BoundaryReducer<RootReducer, State>
+where
+    State: Clone,
+    RootReducer: Fn(State, Action) -> Result<State, Error>,
+{
+    fn reduce(&self, state: StoreState<State, Error>, action: BoundaryAction<Action>) -> StoreState<State, Error> {
+        match (state, action) {
+            (_, BoundaryAction::Reset) => StoreState::Ok(self.initial_state.clone()),
+            (StoreState::Ok(state), BoundaryAction::Action(action)) => match (self.root_reducer)(state, action) {
+                Ok(state) => StoreState::Ok(state),
+                Err(error) => StoreState::Error(error),
+            },
+            // Drop actions while the store holds an error
+            (StoreState::Error(error), BoundaryAction::Action(_)) => StoreState::Error(error),
+        }
+    }
+}
+
+impl<State, Action, Error, RootReducer> ErrorBoundaryStore<State, Action, Error, RootReducer>
+where
+    State: Clone + Send + 'static,
+    Action: Send + 'static,
+    Error: Send + 'static,
+    RootReducer: Fn(State, Action) -> Result<State, Error> + Send + 'static,
+{
+    /// Create a new store with the given fallible reducer and initial state, the store is reset to this initial state
+    pub fn new(root_reducer: RootReducer, initial_state: State) -> Self {
+        let reducer = BoundaryReducer {
+            root_reducer,
+            initial_state: initial_state.clone(),
+        };
+
+        ErrorBoundaryStore {
+            store: Store::new_with_state(reducer, StoreState::Ok(initial_state)),
+        }
+    }
+
+    /// Dispatch a new action to the store, the action is dropped if the store holds an error
+    pub async fn dispatch(&self, action: Action) {
+        self.store.dispatch(BoundaryAction::Action(action)).await
+    }
+
+    /// Reset the store to its initial state, this clears the error
+    pub async fn reset(&self) {
+        self.store.dispatch(BoundaryAction::Reset).await
+    }
+
+    /// Select a part of the state, returns None if the store holds an error
+    pub async fn select<S, Result>(&self, selector: S) -> Option<Result>
+    where
+        S: Selector<State, Result = Result> + Send + 'static,
+        Result: Send + 'static,
+    {
+        self.store
+            .select(move |state: &StoreState<State, Error>| match state {
+                StoreState::Ok(state) => Some(selector.select(state)),
+                StoreState::Error(_) => None,
+            })
+            .await
+    }
+
+    /// Returns the error held by the store, if any
+    pub async fn error_state(&self) -> Option<Error>
+    where
+        Error: Clone,
+    {
+        self.store
+            .select(|state: &StoreState<State, Error>| match state {
+                StoreState::Ok(_) => None,
+                StoreState::Error(error) => Some(error.clone()),
+            })
+            .await
+    }
+
+    /// Returns a cloned version of the state or the error
+    pub async fn state_cloned(&self) -> StoreState<State, Error>
+    where
+        Error: Clone,
+    {
+        self.store.state_cloned().await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Clone, Debug, PartialEq)]
+    enum CounterError {
+        BelowZero,
+    }
+
+    enum CounterAction {
+        Increment,
+        Decrement,
+    }
+
+    fn counter_reducer(state: u32, action: CounterAction) -> Result<u32, CounterError> {
+        match action {
+            CounterAction::Increment => Ok(state + 1),
+            CounterAction::Decrement => state.checked_sub(1).ok_or(CounterError::BelowZero),
+        }
+    }
+
+    #[tokio::test]
+    async fn error_mode_and_reset() {
+        let store = ErrorBoundaryStore::new(counter_reducer, 0);
+
+        store.dispatch(CounterAction::Increment).await;
+        assert_eq!(store.select(|state: &u32| *state).await, Some(1));
+        assert_eq!(store.error_state().await, None);
+
+        store.dispatch(CounterAction::Decrement).await;
+        store.dispatch(CounterAction::Decrement).await;
+        assert_eq!(store.error_state().await, Some(CounterError::BelowZero));
+        assert_eq!(store.select(|state: &u32| *state).await, None);
+
+        // Dispatches are dropped while the store holds an error
+        store.dispatch(CounterAction::Increment).await;
+        assert_eq!(store.state_cloned().await, StoreState::Error(CounterError::BelowZero));
+
+        store.reset().await;
+        assert_eq!(store.error_state().await, None);
+
+        store.dispatch(CounterAction::Increment).await;
+        assert_eq!(store.state_cloned().await, StoreState::Ok(1));
+    }
+}
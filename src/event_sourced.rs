@@ -0,0 +1,163 @@
+use crate::{Reducer, Selector, Store, Subscriber};
+use std::marker::PhantomData;
+
+/// The state of an `EventSourcedStore`: the reduced state together with every action that led to it
+#[derive(Clone, Debug, PartialEq)]
+pub struct EventSourced<State, Action> {
+    pub state: State,
+    pub events: Vec<Action>,
+}
+
+impl<State, Action> Default for EventSourced<State, Action>
+where
+    State: Default,
+{
+    fn default() -> Self {
+        EventSourced {
+            state: Default::default(),
+            events: Vec::new(),
+        }
+    }
+}
+
+struct EventSourcedReducer<RootReducer, State> {
+    root_reducer: RootReducer,
+    _types: PhantomData<fn(State) -> State>,
+}
+
+impl<RootReducer, State, Action> Reducer<EventSourced<State, Action>, Action> for EventSourcedReducer<RootReducer, State>
+where
+    RootReducer: Reducer<State, Action>,
+    Action: Clone,
+{
+    fn reduce(&self, state: EventSourced<State, Action>, action: Action) -> EventSourced<State, Action> {
+        let EventSourced { state, mut events } = state;
+        events.push(action.clone());
+
+        EventSourced {
+            state: self.root_reducer.reduce(state, action),
+            events,
+        }
+    }
+}
+
+/// A store which keeps every dispatched action next to the state, as an event log.
+///
+/// The reducer only works on the state, the actions are appended to the log transparently.
+/// Selectors and subscribers see the `EventSourced` wrapper, so they have access to both the state and the log.
+/// The log grows with every dispatched action, use it for stores with a limited lifetime or a low action rate.
+///
+/// ## Example
+/// ```
+/// use redux_rs::{EventSourced, EventSourcedStore};
+///
+/// fn reducer(state: u32, add: u32) -> u32 {
+///     state + add
+/// }
+///
+/// # #[tokio::main(flavor = "current_thread")]
+/// # async fn main() {
+/// let store = EventSourcedStore::new(reducer);
+/// store.dispatch(1).await;
+/// store.dispatch(2).await;
+///
+/// assert_eq!(store.select(|s: &EventSourced<u32, u32>| s.state).await, 3);
+/// assert_eq!(store.select_events().await, vec![1, 2]);
+/// # }
+/// ```
+pub struct EventSourcedStore<State, Action, RootReducer>
+where
+    State: Send + 'static,
+    Action: Send + 'static,
+    RootReducer: Send + 'static,
+{
+    store: Store<EventSourced<State, Action>, Action, EventSourcedReducer<RootReducer, State>>,
+}
+
+impl<State, Action, RootReducer> EventSourcedStore<State, Action, RootReducer>
+where
+    State: Send + 'static,
+    Action: Clone + Send + 'static,
+    RootReducer: Reducer<State, Action> + Send + 'static,
+{
+    /// Create a new store with the given root reducer and default state
+    pub fn new(root_reducer: RootReducer) -> Self
+    where
+        State: Default,
+    {
+        Self::new_with_state(root_reducer, Default::default())
+    }
+
+    /// Create a new store with the given root reducer and the provided state, the event log starts empty
+    pub fn new_with_state(root_reducer: RootReducer, state: State) -> Self {
+        let reducer = EventSourcedReducer {
+            root_reducer,
+            _types: Default::default(),
+        };
+
+        EventSourcedStore {
+            store: Store::new_with_state(reducer, EventSourced { state, events: Vec::new() }),
+        }
+    }
+
+    /// Dispatch a new action to the store, it's appended to the event log
+    pub async fn dispatch(&self, action: Action) {
+        self.store.dispatch(action).await
+    }
+
+    /// Select a part of the state or the event log
+    pub async fn select<S, Result>(&self, selector: S) -> Result
+    where
+        S: Selector<EventSourced<State, Action>, Result = Result> + Send + 'static,
+        Result: Send + 'static,
+    {
+        self.store.select(selector).await
+    }
+
+    /// Returns a copy of the event log, every action dispatched so far in dispatch order
+    pub async fn select_events(&self) -> Vec<Action> {
+        self.store.select(|state: &EventSourced<State, Action>| state.events.clone()).await
+    }
+
+    /// Subscribe to state changes
+    pub async fn subscribe<S: Subscriber<EventSourced<State, Action>> + Send + 'static>(&self, subscriber: S) {
+        self.store.subscribe(subscriber).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Default)]
+    struct Counter {
+        counter: i32,
+    }
+
+    #[derive(Clone, Debug, PartialEq)]
+    enum CounterAction {
+        Increment,
+        Decrement,
+    }
+
+    fn counter_reducer(state: Counter, action: CounterAction) -> Counter {
+        match action {
+            CounterAction::Increment => Counter { counter: state.counter + 1 },
+            CounterAction::Decrement => Counter { counter: state.counter - 1 },
+        }
+    }
+
+    #[tokio::test]
+    async fn events_are_recorded() {
+        use CounterAction::*;
+
+        let store = EventSourcedStore::new(counter_reducer);
+        let actions = vec![Increment, Increment, Decrement, Increment, Increment];
+        for action in actions.clone() {
+            store.dispatch(action).await;
+        }
+
+        assert_eq!(store.select_events().await, actions);
+        assert_eq!(store.select(|s: &EventSourced<Counter, CounterAction>| s.state.counter).await, 3);
+    }
+}
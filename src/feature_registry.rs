@@ -0,0 +1,211 @@
+use crate::{Reducer, Store};
+use std::collections::HashSet;
+use std::sync::{Arc, RwLock};
+
+type FeatureReducer<State, Action> = Box<dyn Fn(State, Action) -> State + Send + Sync>;
+
+/// Root reducer which delegates to reducers behind feature flags, the flags can be toggled at runtime.
+///
+/// Every reducer is registered under a feature, all features are disabled when registered.
+/// When an action is reduced, the reducers of the enabled features run one after the other in the order they were registered.
+/// If no feature is enabled the state is left untouched.
+///
+/// The registry is a handle, clones share the same flags.
+/// Keep a clone around to toggle the features of the store the registry was moved into, e.g. to A/B test reducer logic.
+///
+/// ## Example
+/// ```
+/// use redux_rs::{FeatureRegistry, Store};
+///
+/// fn add(state: i32, value: i32) -> i32 {
+///     state + value
+/// }
+///
+/// fn multiply(state: i32, value: i32) -> i32 {
+///     state * value
+/// }
+///
+/// # #[tokio::main(flavor = "current_thread")]
+/// # async fn main() {
+/// let mut registry = FeatureRegistry::new();
+/// registry.register_reducer("add", add);
+/// registry.register_reducer("multiply", multiply);
+/// registry.enable("add");
+///
+/// let store = Store::new_with_feature_registry(registry.clone(), 1);
+/// store.dispatch(3).await;
+/// assert_eq!(store.state_cloned().await, 4);
+///
+/// registry.disable("add");
+/// registry.enable("multiply");
+/// store.dispatch(3).await;
+/// assert_eq!(store.state_cloned().await, 12);
+/// # }
+/// ```
+pub struct FeatureRegistry<State, Action> {
+    reducers: Arc<Vec<(&'static str, FeatureReducer<State, Action>)>>,
+    enabled: Arc<RwLock<HashSet<String>>>,
+}
+
+impl<State, Action> FeatureRegistry<State, Action> {
+    /// Create a registry without reducers
+    pub fn new() -> Self {
+        FeatureRegistry {
+            reducers: Arc::new(Vec::new()),
+            enabled: Arc::new(RwLock::new(HashSet::new())),
+        }
+    }
+
+    /// Register a reducer behind the feature, the feature is disabled until `enable` is called.
+    /// Multiple reducers can be registered under the same feature.
+    ///
+    /// Panics if the registry was cloned already, register all reducers before handing the registry to a store.
+    pub fn register_reducer<Feature, R>(&mut self, feature: Feature, reducer: R) -> &mut Self
+    where
+        Feature: Into<&'static str>,
+        R: Reducer<State, Action> + Send + Sync + 'static,
+    {
+        let reducers = Arc::get_mut(&mut self.reducers).expect("reducers are registered before the registry is cloned");
+        reducers.push((feature.into(), Box::new(move |state, action| reducer.reduce(state, action))));
+        self
+    }
+
+    /// Enable the reducers of the feature, starting with the next action
+    pub fn enable(&self, feature: &str) {
+        self.enabled.write().unwrap().insert(feature.to_string());
+    }
+
+    /// Disable the reducers of the feature, starting with the next action
+    pub fn disable(&self, feature: &str) {
+        self.enabled.write().unwrap().remove(feature);
+    }
+
+    /// Returns true if the reducers of the feature are enabled
+    pub fn is_enabled(&self, feature: &str) -> bool {
+        self.enabled.read().unwrap().contains(feature)
+    }
+}
+
+impl<State, Action> Default for FeatureRegistry<State, Action> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<State, Action> Clone for FeatureRegistry<State, Action> {
+    fn clone(&self) -> Self {
+        FeatureRegistry {
+            reducers: self.reducers.clone(),
+            enabled: self.enabled.clone(),
+        }
+    }
+}
+
+impl<State, Action> Reducer<State, Action> for FeatureRegistry<State, Action>
+where
+    Action: Clone,
+{
+    fn reduce(&self, state: State, action: Action) -> State {
+        let enabled = self.enabled.read().unwrap();
+
+        self.reducers
+            .iter()
+            .filter(|(feature, _)| enabled.contains(*feature))
+            .fold(state, |state, (_, reducer)| reducer(state, action.clone()))
+    }
+}
+
+impl<State, Action> Store<State, Action, FeatureRegistry<State, Action>>
+where
+    State: Send + 'static,
+    Action: Clone + Send + 'static,
+{
+    /// Create a store with the feature registry as root reducer, see `FeatureRegistry`
+    pub fn new_with_feature_registry(registry: FeatureRegistry<State, Action>, state: State) -> Self {
+        Store::new_with_state(registry, state)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Clone, Debug, Default, PartialEq)]
+    struct Cart {
+        total: u32,
+    }
+
+    #[derive(Clone, Debug)]
+    enum CartAction {
+        AddItem(u32),
+    }
+
+    fn regular_pricing(state: Cart, action: CartAction) -> Cart {
+        match action {
+            CartAction::AddItem(price) => Cart { total: state.total + price },
+        }
+    }
+
+    fn discount_pricing(state: Cart, action: CartAction) -> Cart {
+        match action {
+            CartAction::AddItem(price) => Cart {
+                total: state.total + price / 2,
+            },
+        }
+    }
+
+    enum Pricing {
+        Regular,
+        Discount,
+    }
+
+    impl From<Pricing> for &'static str {
+        fn from(pricing: Pricing) -> Self {
+            match pricing {
+                Pricing::Regular => "regular",
+                Pricing::Discount => "discount",
+            }
+        }
+    }
+
+    #[tokio::test]
+    async fn swaps_competing_reducers() {
+        let mut registry = FeatureRegistry::new();
+        registry
+            .register_reducer(Pricing::Regular, regular_pricing)
+            .register_reducer(Pricing::Discount, discount_pricing);
+        registry.enable("regular");
+
+        let store = Store::new_with_feature_registry(registry.clone(), Cart::default());
+        store.dispatch(CartAction::AddItem(10)).await;
+        assert_eq!(store.state_cloned().await, Cart { total: 10 });
+
+        registry.disable("regular");
+        registry.enable("discount");
+        assert!(!registry.is_enabled("regular"));
+        store.dispatch(CartAction::AddItem(10)).await;
+        assert_eq!(store.state_cloned().await, Cart { total: 15 });
+    }
+
+    #[test]
+    fn runs_enabled_reducers_in_registration_order() {
+        let mut registry = FeatureRegistry::new();
+        registry
+            .register_reducer("add", |state: i32, value: i32| state + value)
+            .register_reducer("double", |state: i32, _value: i32| state * 2);
+
+        assert_eq!(registry.reduce(1, 3), 1);
+
+        registry.enable("add");
+        registry.enable("double");
+        assert_eq!(registry.reduce(1, 3), 8);
+    }
+
+    #[test]
+    #[should_panic(expected = "reducers are registered before the registry is cloned")]
+    fn register_after_clone_panics() {
+        let mut registry = FeatureRegistry::new();
+        let _store_registry = registry.clone();
+        registry.register_reducer("add", |state: i32, value: i32| state + value);
+    }
+}
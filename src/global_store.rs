@@ -0,0 +1,221 @@
+use crate::{PipelineStore, Reducer, Selector, Store, StoreApi, Subscriber};
+use async_trait::async_trait;
+use std::any::{Any, TypeId};
+use std::collections::HashMap;
+use std::sync::{Mutex, OnceLock, PoisonError};
+
+// Statics can't be generic, so there is a single registry with one store per state and action type
+type Registry = Mutex<HashMap<TypeId, &'static (dyn Any + Send + Sync)>>;
+
+static REGISTRY: OnceLock<Registry> = OnceLock::new();
+
+fn registry() -> &'static Registry {
+    REGISTRY.get_or_init(Default::default)
+}
+
+/// A single store instance for the whole application, for applications which follow the Redux convention of one store.
+///
+/// The store is initialized once with `GlobalStore::init` and can be retrieved anywhere with `GlobalStore::get`.
+/// To use middleware, wrap the store first and initialize the global store with `GlobalStore::init_with_store`.
+/// There is one global store per combination of state and action type.
+/// Like a regular store, the store runs on the Tokio runtime which is active when it's initialized.
+///
+/// ## Example
+/// ```
+/// use redux_rs::{GlobalStore, StoreApi};
+///
+/// fn reducer(state: u32, add: u32) -> u32 {
+///     state + add
+/// }
+///
+/// # #[tokio::main(flavor = "current_thread")]
+/// # async fn main() {
+/// GlobalStore::init(reducer);
+///
+/// GlobalStore::<u32, u32>::get().dispatch(5u32).await;
+/// assert_eq!(GlobalStore::<u32, u32>::get().select(|state: &u32| *state).await, 5);
+/// # }
+/// ```
+pub struct GlobalStore<State, Action> {
+    store: PipelineStore<State, Action>,
+}
+
+impl<State, Action> GlobalStore<State, Action>
+where
+    State: Send + Sync + 'static,
+    Action: Send + Sync + 'static,
+{
+    /// Initialize the global store with the given root reducer and default state.
+    ///
+    /// Panics if the global store for this state and action type was already initialized.
+    pub fn init<RootReducer>(root_reducer: RootReducer) -> &'static Self
+    where
+        RootReducer: Reducer<State, Action> + Send + Sync + 'static,
+        State: Default,
+    {
+        Self::init_with_state(root_reducer, Default::default())
+    }
+
+    /// Initialize the global store with the given root reducer and the provided state.
+    ///
+    /// Panics if the global store for this state and action type was already initialized.
+    pub fn init_with_state<RootReducer>(root_reducer: RootReducer, state: State) -> &'static Self
+    where
+        RootReducer: Reducer<State, Action> + Send + Sync + 'static,
+    {
+        Self::init_with_store(Store::new_with_state(root_reducer, state))
+    }
+
+    /// Initialize the global store with an existing store, like a store wrapped with middleware.
+    ///
+    /// Panics if the global store for this state and action type was already initialized.
+    pub fn init_with_store<S>(store: S) -> &'static Self
+    where
+        S: StoreApi<State, Action> + Send + Sync + 'static,
+    {
+        let mut stores = registry().lock().unwrap_or_else(PoisonError::into_inner);
+        if stores.contains_key(&TypeId::of::<Self>()) {
+            drop(stores);
+            panic!("the global store is already initialized");
+        }
+
+        let store: &'static Self = Box::leak(Box::new(GlobalStore {
+            store: PipelineStore::new(store),
+        }));
+        stores.insert(TypeId::of::<Self>(), store);
+
+        store
+    }
+
+    /// Returns the global store.
+    ///
+    /// Panics if the global store for this state and action type isn't initialized yet.
+    pub fn get() -> &'static Self {
+        let store = registry().lock().unwrap_or_else(PoisonError::into_inner).get(&TypeId::of::<Self>()).copied();
+
+        store
+            .and_then(|store| store.downcast_ref::<Self>())
+            .expect("the global store is not initialized")
+    }
+}
+
+#[async_trait]
+impl<State, Action> StoreApi<State, Action> for GlobalStore<State, Action>
+where
+    State: Send + 'static,
+    Action: Send + 'static,
+{
+    async fn dispatch<A: Into<Action> + Send>(&self, action: A) {
+        self.store.dispatch(action).await
+    }
+
+    async fn select<S: Selector<State, Result = Result>, Result>(&self, selector: S) -> Result
+    where
+        S: Selector<State, Result = Result> + Send + 'static,
+        Result: Send + 'static,
+    {
+        self.store.select(selector).await
+    }
+
+    async fn subscribe<S: Subscriber<State> + Send + 'static>(&self, subscriber: S) {
+        self.store.subscribe(subscriber).await
+    }
+
+    async fn subscribe_sync<S: Subscriber<State> + Send + Sync + 'static>(&self, subscriber: S) {
+        self.store.subscribe_sync(subscriber).await
+    }
+
+    async fn subscribe_until<S, P>(&self, subscriber: S, condition: P)
+    where
+        S: Subscriber<State> + Send + Sync + 'static,
+        P: Fn(&State) -> bool + Send + Sync + 'static,
+    {
+        self.store.subscribe_until(subscriber, condition).await
+    }
+
+    fn middleware_depth(&self) -> usize {
+        self.store.middleware_depth()
+    }
+
+    fn middleware_names(&self) -> Vec<String> {
+        self.store.middleware_names()
+    }
+
+    async fn teardown(&self) {
+        self.store.teardown().await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Arc;
+
+    // Every test uses its own state type, the global stores are shared by all tests in the process
+    #[derive(Default)]
+    struct InitTwiceState;
+
+    fn init_twice_reducer(state: InitTwiceState, _action: ()) -> InitTwiceState {
+        state
+    }
+
+    #[tokio::test]
+    #[should_panic(expected = "already initialized")]
+    async fn init_twice_panics() {
+        GlobalStore::init(init_twice_reducer);
+        GlobalStore::init(init_twice_reducer);
+    }
+
+    #[derive(Default)]
+    struct Counter {
+        value: i32,
+    }
+
+    fn counter_reducer(state: Counter, add: i32) -> Counter {
+        Counter { value: state.value + add }
+    }
+
+    #[tokio::test]
+    async fn get_returns_the_initialized_store() {
+        let initialized = GlobalStore::init(counter_reducer);
+
+        let store = GlobalStore::<Counter, i32>::get();
+        assert!(std::ptr::eq(initialized, store));
+        assert!(std::ptr::eq(store, GlobalStore::<Counter, i32>::get()));
+
+        store.dispatch(3).await;
+        assert_eq!(GlobalStore::<Counter, i32>::get().select(|state: &Counter| state.value).await, 3);
+    }
+
+    #[derive(Default)]
+    struct Wrapped {
+        value: i32,
+    }
+
+    type WrappedReducer = fn(Wrapped, i32) -> Wrapped;
+
+    fn wrapped_reducer(state: Wrapped, add: i32) -> Wrapped {
+        Wrapped { value: state.value + add }
+    }
+
+    #[tokio::test]
+    async fn get_returns_the_wrapped_store() {
+        // Doubles every action before it reaches the store
+        let double = crate::middleware_from_fn(|add: i32, inner: Arc<Store<Wrapped, i32, WrappedReducer>>| async move { inner.dispatch(add * 2).await });
+        let store = Store::new(wrapped_reducer as WrappedReducer).wrap(double).await;
+        GlobalStore::init_with_store(store);
+
+        let store = GlobalStore::<Wrapped, i32>::get();
+        assert_eq!(store.middleware_depth(), 1);
+
+        store.dispatch(3).await;
+        assert_eq!(store.select(|state: &Wrapped| state.value).await, 6);
+    }
+
+    #[test]
+    #[should_panic(expected = "not initialized")]
+    fn get_before_init_panics() {
+        struct Uninitialized;
+        GlobalStore::<Uninitialized, ()>::get();
+    }
+}
@@ -19,7 +19,7 @@
 //!
 //! ### State
 //!
-//! A state is the form of data that Redux manages. 
+//! A state is the form of data that Redux manages.
 //! Theoretically it could be anything, but as an example, let's consider a simple counter.
 //! The counter can only increment and decrement.
 //! The state would look like this:
@@ -160,15 +160,98 @@
 //! # }
 //! ```
 
+#[cfg(feature = "devtools_import")]
+mod action_log;
+mod action_stream;
+mod channel_receiver;
+mod cold_store;
+mod command_event;
+mod composed_middleware;
+#[cfg(feature = "config_file")]
+mod config;
+#[cfg(feature = "crossbeam_bridge")]
+mod crossbeam_bridge;
+mod derived_state;
+mod dyn_middleware;
+mod error_boundary;
+mod event_sourced;
+mod feature_registry;
+mod global_store;
+mod mapped_store;
+mod meta;
 mod middleware;
 pub mod middlewares;
+mod migration;
+mod multi_reader_store;
+mod pipeline_store;
+mod reactive_store;
 mod reducer;
+mod reducer_enhancers;
+mod replay;
+mod select_subscription;
 mod selector;
+mod sharded_store;
+mod snapshot;
+mod state_change_watcher;
+mod state_monitor;
+mod stateful_middleware;
 mod store;
+mod store_api_ext;
+mod store_error;
 mod subscriber;
+#[cfg(any(test, feature = "testing"))]
+mod testing;
+mod thread_safe_store;
+mod transition;
+mod validator;
 
-pub use middleware::{MiddleWare, StoreApi, StoreWithMiddleware};
-pub use reducer::Reducer;
-pub use selector::Selector;
+#[cfg(feature = "devtools_import")]
+pub use action_log::{ActionLog, ParseError};
+pub use action_stream::{ActionStream, ObserveActions};
+pub use channel_receiver::ChannelReceiver;
+pub use cold_store::ColdStore;
+pub use command_event::CommandEventStore;
+pub use composed_middleware::{ComposedMiddleware, MiddleWareExt};
+#[cfg(feature = "config_file")]
+pub use config::ConfigError;
+#[cfg(feature = "crossbeam_bridge")]
+pub use crossbeam_bridge::CrossbeamBridge;
+pub use derived_state::{DerivedState, DerivedStateStore, DerivedStateStoreBuilder};
+pub use dyn_middleware::{pipe, DynMiddleWare, MiddlewareStack, Next, Pipe};
+pub use error_boundary::{BoundaryAction, ErrorBoundaryStore, StoreState};
+pub use event_sourced::{EventSourced, EventSourcedStore};
+pub use feature_registry::FeatureRegistry;
+pub use global_store::GlobalStore;
+pub use mapped_store::{ActionMappedStore, MappedStore};
+pub use meta::{MetaEnvelope, StripMetaMiddleware, TaggedAction};
+pub use middleware::{middleware_from_fn, middleware_graph, MiddleWare, StoreApi, StoreMiddlewareExt, StoreWithMiddleware};
+pub use migration::{AnyMigration, MigrationError, PersistedState, StateMigration};
+pub use multi_reader_store::MultiReaderStore;
+pub use pipeline_store::PipelineStore;
+pub use reactive_store::ReactiveStore;
+pub use reducer::{cascade_reducers, reduce_immediately, Reducer};
+pub use reducer_enhancers::{
+    fallback_reducer, timing_reducer, FallbackReducer, ReducerComposer, ReducerTracer, TimingRecords, TimingReducer, TraceEvent, TracedReducer,
+    VecReducerTracer,
+};
+#[cfg(feature = "middleware_logger")]
+pub use reducer_enhancers::{log_reducer, LogReducerTracer, LoggingReducer};
+pub use select_subscription::SelectSubscription;
+pub use selector::{try_select_sync, Selector};
+pub use sharded_store::{ShardId, ShardMap, ShardedStore};
+pub use snapshot::{ConflictError, VersionedSnapshot};
+pub use state_change_watcher::StateChangeWatcher;
+pub use state_monitor::{HealthReceiver, HealthStatus, StateMonitor};
+pub use stateful_middleware::StatefulMiddleware;
 pub use store::Store;
-pub use subscriber::Subscriber;
+pub use store_api_ext::StoreApiExt;
+pub use store_error::{ErrorReceiver, StoreError};
+pub use subscriber::{BufferedSubscriber, StateDiff, Subscriber, SubscriberInfo, SubscriptionId};
+#[cfg(feature = "proptest")]
+#[doc(hidden)]
+pub use testing::run_state_machine_test;
+#[cfg(any(test, feature = "testing"))]
+pub use testing::{reduce_pure, reduce_sequence, ConcurrentHarness, MiddlewareTestBuilder, MockStore, ReducerTestHarness};
+pub use thread_safe_store::ThreadSafeStore;
+pub use transition::TransitionError;
+pub use validator::{InvariantViolation, ValidatedReducer};
@@ -0,0 +1,268 @@
+use crate::{Selector, StoreApi, Subscriber};
+use async_trait::async_trait;
+use std::marker::PhantomData;
+
+/// A store seen through a different state type, see `StoreApiExt::map_state`.
+///
+/// Selectors and subscribers receive the mapped state, the actions are dispatched to the underlying store unchanged.
+/// The state is mapped on every select and every notification, so keep the mapping cheap.
+pub struct MappedStore<Inner, State, NewState> {
+    inner: Inner,
+    to_new: fn(&State) -> NewState,
+
+    _types: PhantomData<fn(&State) -> NewState>,
+}
+
+impl<Inner, State, NewState> MappedStore<Inner, State, NewState> {
+    pub(crate) fn new(inner: Inner, to_new: fn(&State) -> NewState) -> Self {
+        MappedStore {
+            inner,
+            to_new,
+            _types: Default::default(),
+        }
+    }
+
+    /// The underlying store
+    pub fn inner(&self) -> &Inner {
+        &self.inner
+    }
+}
+
+#[async_trait]
+impl<Inner, State, NewState, Action> StoreApi<NewState, Action> for MappedStore<Inner, State, NewState>
+where
+    Inner: StoreApi<State, Action> + Send + Sync,
+    State: Send + 'static,
+    NewState: Send + 'static,
+    Action: Send + 'static,
+{
+    async fn dispatch<A: Into<Action> + Send>(&self, action: A) {
+        self.inner.dispatch(action).await
+    }
+
+    async fn select<S: Selector<NewState, Result = Result>, Result>(&self, selector: S) -> Result
+    where
+        S: Selector<NewState, Result = Result> + Send + 'static,
+        Result: Send + 'static,
+    {
+        let to_new = self.to_new;
+        self.inner.select(move |state: &State| selector.select(&to_new(state))).await
+    }
+
+    async fn subscribe<S: Subscriber<NewState> + Send + 'static>(&self, subscriber: S) {
+        let to_new = self.to_new;
+        self.inner.subscribe(move |state: &State| subscriber.notify(&to_new(state))).await
+    }
+
+    async fn subscribe_sync<S: Subscriber<NewState> + Send + Sync + 'static>(&self, subscriber: S) {
+        let to_new = self.to_new;
+        self.inner.subscribe_sync(move |state: &State| subscriber.notify(&to_new(state))).await
+    }
+
+    async fn subscribe_until<S, P>(&self, subscriber: S, condition: P)
+    where
+        S: Subscriber<NewState> + Send + Sync + 'static,
+        P: Fn(&NewState) -> bool + Send + Sync + 'static,
+    {
+        // Forwarded so the inner store removes the subscriber, instead of mapping the state for a silenced subscriber forever
+        let to_new = self.to_new;
+        self.inner
+            .subscribe_until(
+                move |state: &State| subscriber.notify(&to_new(state)),
+                move |state: &State| condition(&to_new(state)),
+            )
+            .await
+    }
+
+    fn middleware_depth(&self) -> usize {
+        self.inner.middleware_depth()
+    }
+
+    fn middleware_names(&self) -> Vec<String> {
+        self.inner.middleware_names()
+    }
+
+    async fn teardown(&self) {
+        self.inner.teardown().await
+    }
+}
+
+/// A store which accepts a different action type, see `StoreApiExt::map_action`.
+///
+/// Every dispatched action is converted with the mapping function and dispatched to the underlying store.
+/// Unlike a middleware this is a plain adapter, it doesn't add a layer to the middleware stack.
+pub struct ActionMappedStore<Inner, OuterAction, F> {
+    inner: Inner,
+    to_inner: F,
+
+    _types: PhantomData<fn(OuterAction)>,
+}
+
+impl<Inner, OuterAction, F> ActionMappedStore<Inner, OuterAction, F> {
+    pub(crate) fn new(inner: Inner, to_inner: F) -> Self {
+        ActionMappedStore {
+            inner,
+            to_inner,
+            _types: Default::default(),
+        }
+    }
+
+    /// The underlying store
+    pub fn inner(&self) -> &Inner {
+        &self.inner
+    }
+}
+
+#[async_trait]
+impl<Inner, State, Action, OuterAction, F> StoreApi<State, OuterAction> for ActionMappedStore<Inner, OuterAction, F>
+where
+    Inner: StoreApi<State, Action> + Send + Sync,
+    F: Fn(OuterAction) -> Action + Send + Sync,
+    State: Send + 'static,
+    Action: Send + 'static,
+    OuterAction: Send + 'static,
+{
+    async fn dispatch<A: Into<OuterAction> + Send>(&self, action: A) {
+        self.inner.dispatch((self.to_inner)(action.into())).await
+    }
+
+    async fn select<S: Selector<State, Result = Result>, Result>(&self, selector: S) -> Result
+    where
+        S: Selector<State, Result = Result> + Send + 'static,
+        Result: Send + 'static,
+    {
+        self.inner.select(selector).await
+    }
+
+    async fn state_cloned(&self) -> State
+    where
+        State: Clone,
+    {
+        self.inner.state_cloned().await
+    }
+
+    async fn subscribe<S: Subscriber<State> + Send + 'static>(&self, subscriber: S) {
+        self.inner.subscribe(subscriber).await
+    }
+
+    async fn subscribe_sync<S: Subscriber<State> + Send + Sync + 'static>(&self, subscriber: S) {
+        self.inner.subscribe_sync(subscriber).await
+    }
+
+    async fn subscribe_until<S, P>(&self, subscriber: S, condition: P)
+    where
+        S: Subscriber<State> + Send + Sync + 'static,
+        P: Fn(&State) -> bool + Send + Sync + 'static,
+    {
+        self.inner.subscribe_until(subscriber, condition).await
+    }
+
+    fn middleware_depth(&self) -> usize {
+        self.inner.middleware_depth()
+    }
+
+    fn middleware_names(&self) -> Vec<String> {
+        self.inner.middleware_names()
+    }
+
+    async fn teardown(&self) {
+        self.inner.teardown().await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{Store, StoreApi, StoreApiExt};
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::{Arc, Mutex};
+
+    #[derive(Default)]
+    struct Counter {
+        value: i32,
+    }
+
+    fn counter_reducer(state: Counter, add: i32) -> Counter {
+        Counter { value: state.value + add }
+    }
+
+    fn describe(counter: &Counter) -> String {
+        format!("counter: {}", counter.value)
+    }
+
+    #[tokio::test]
+    async fn select_mapped_state() {
+        let store = Store::new(counter_reducer).map_state(describe);
+
+        store.dispatch(3).await;
+        assert_eq!(store.state_cloned().await, "counter: 3");
+        assert_eq!(store.select(|state: &String| state.len()).await, 10);
+        assert_eq!(store.inner().select(|state: &Counter| state.value).await, 3);
+    }
+
+    #[tokio::test]
+    async fn subscribers_receive_mapped_state() {
+        let store = Store::new(counter_reducer).map_state(describe);
+
+        let notified = Arc::new(Mutex::new(Vec::new()));
+        let notified_clone = notified.clone();
+        store.subscribe(move |state: &String| notified_clone.lock().unwrap().push(state.clone())).await;
+
+        store.dispatch(1).await;
+        store.dispatch(-2).await;
+
+        assert_eq!(*notified.lock().unwrap(), vec!["counter: 1", "counter: -1"]);
+    }
+
+    #[tokio::test]
+    async fn finished_waits_stop_mapping() {
+        static MAPPED: AtomicUsize = AtomicUsize::new(0);
+
+        fn counted(counter: &Counter) -> i32 {
+            MAPPED.fetch_add(1, Ordering::SeqCst);
+            counter.value
+        }
+
+        let store = Store::new(counter_reducer).map_state(counted);
+        for target in 1..=3 {
+            store.dispatch_and_wait_for_effect(1, move |value: &i32| *value == target).await;
+        }
+
+        MAPPED.store(0, Ordering::SeqCst);
+        for _ in 0..10 {
+            store.dispatch(1).await;
+        }
+        assert_eq!(store.state_cloned().await, 13);
+
+        // Only the state_cloned above mapped the state, the finished waits were removed
+        assert_eq!(MAPPED.load(Ordering::SeqCst), 1);
+    }
+
+    enum RootAction {
+        Counter(CounterAction),
+        Reset,
+    }
+
+    enum CounterAction {
+        Add(i32),
+    }
+
+    fn root_reducer(state: Counter, action: RootAction) -> Counter {
+        match action {
+            RootAction::Counter(CounterAction::Add(value)) => Counter { value: state.value + value },
+            RootAction::Reset => Counter::default(),
+        }
+    }
+
+    #[tokio::test]
+    async fn dispatch_mapped_actions() {
+        let store = Store::new(root_reducer).map_action(RootAction::Counter);
+
+        store.dispatch(CounterAction::Add(4)).await;
+        store.dispatch(CounterAction::Add(-1)).await;
+        assert_eq!(store.select(|state: &Counter| state.value).await, 3);
+        assert_eq!(store.middleware_depth(), 0);
+
+        store.inner().dispatch(RootAction::Reset).await;
+        assert_eq!(store.select(|state: &Counter| state.value).await, 0);
+    }
+}
@@ -0,0 +1,170 @@
+use crate::composed_middleware::impl_middleware_bitor;
+use crate::{MiddleWare, StoreApi};
+use async_trait::async_trait;
+use std::marker::PhantomData;
+use std::sync::Arc;
+
+/// An action together with metadata, for example a correlation id, trace id or timestamp.
+///
+/// Middlewares which handle `MetaEnvelope`s can inspect the metadata, the `StripMetaMiddleware` removes it again
+/// before the action reaches the store. This way the metadata never pollutes your action type.
+/// Dispatch envelopes with `StoreApiExt::dispatch_with_meta`.
+///
+/// ## Example
+/// ```
+/// use async_trait::async_trait;
+/// use std::sync::Arc;
+/// use redux_rs::{MetaEnvelope, MiddleWare, Store, StoreApi, StoreApiExt, StripMetaMiddleware};
+///
+/// #[derive(Default)]
+/// struct Counter(i8);
+///
+/// enum Action {
+///     Increment,
+/// }
+///
+/// fn counter_reducer(state: Counter, action: Action) -> Counter {
+///     match action {
+///         Action::Increment => Counter(state.0 + 1),
+///     }
+/// }
+///
+/// // Print the correlation id of every action
+/// struct CorrelationMiddleware;
+/// #[async_trait]
+/// impl<Inner> MiddleWare<Counter, MetaEnvelope<Action, u32>, Inner> for CorrelationMiddleware
+/// where
+///     Inner: StoreApi<Counter, MetaEnvelope<Action, u32>> + Send + Sync,
+/// {
+///     async fn dispatch(&self, envelope: MetaEnvelope<Action, u32>, inner: &Arc<Inner>) {
+///         println!("Correlation id: {}", envelope.meta);
+///         inner.dispatch(envelope).await;
+///     }
+/// }
+///
+/// # #[tokio::main(flavor = "current_thread")]
+/// # async fn main() {
+/// let store = Store::new(counter_reducer)
+///     .wrap(StripMetaMiddleware::new())
+///     .await
+///     .wrap(CorrelationMiddleware)
+///     .await;
+///
+/// store.dispatch_with_meta(Action::Increment, 42).await;
+/// # }
+/// ```
+#[derive(Clone, Debug, PartialEq)]
+pub struct MetaEnvelope<Action, Meta> {
+    pub action: Action,
+    pub meta: Meta,
+}
+
+impl<Action, Meta> MetaEnvelope<Action, Meta> {
+    pub fn new(action: Action, meta: Meta) -> Self {
+        MetaEnvelope { action, meta }
+    }
+}
+
+/// An action tagged with a static label, dispatch it with `StoreApiExt::dispatch_tagged`.
+///
+/// The tag gives middlewares a meaningful name for an action without requiring `Action: Debug`,
+/// the `MetricsMiddleware` for example groups its measurements by tag.
+pub type TaggedAction<Action> = MetaEnvelope<Action, &'static str>;
+
+/// Middleware which strips the metadata of a `MetaEnvelope` and dispatches the bare action to the underlying store.
+pub struct StripMetaMiddleware<Meta> {
+    _types: PhantomData<fn(Meta)>,
+}
+
+impl<Meta> StripMetaMiddleware<Meta> {
+    pub fn new() -> Self {
+        StripMetaMiddleware { _types: PhantomData }
+    }
+}
+
+impl<Meta> Default for StripMetaMiddleware<Meta> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl_middleware_bitor!(StripMetaMiddleware<Meta>);
+
+#[async_trait]
+impl<State, Action, Meta, Inner> MiddleWare<State, MetaEnvelope<Action, Meta>, Inner, Action> for StripMetaMiddleware<Meta>
+where
+    State: Send + 'static,
+    Action: Send + 'static,
+    Meta: Send + 'static,
+    Inner: StoreApi<State, Action> + Send + Sync,
+{
+    async fn dispatch(&self, envelope: MetaEnvelope<Action, Meta>, inner: &Arc<Inner>) {
+        inner.dispatch(envelope.action).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Store, StoreApiExt};
+    use std::sync::Mutex;
+
+    #[derive(Default)]
+    struct Counter {
+        value: i32,
+    }
+
+    #[derive(Debug)]
+    enum CounterAction {
+        Increment,
+        Decrement,
+    }
+
+    fn counter_reducer(state: Counter, action: CounterAction) -> Counter {
+        match action {
+            CounterAction::Increment => Counter { value: state.value + 1 },
+            CounterAction::Decrement => Counter { value: state.value - 1 },
+        }
+    }
+
+    struct CorrelationId(&'static str);
+
+    struct CorrelationLogger {
+        logs: Arc<Mutex<Vec<String>>>,
+    }
+
+    #[async_trait]
+    impl<Inner> MiddleWare<Counter, MetaEnvelope<CounterAction, CorrelationId>, Inner> for CorrelationLogger
+    where
+        Inner: StoreApi<Counter, MetaEnvelope<CounterAction, CorrelationId>> + Send + Sync,
+    {
+        async fn dispatch(&self, envelope: MetaEnvelope<CounterAction, CorrelationId>, inner: &Arc<Inner>) {
+            self.logs.lock().unwrap().push(format!("[{}] {:?}", envelope.meta.0, envelope.action));
+            inner.dispatch(envelope).await;
+        }
+    }
+
+    #[tokio::test]
+    async fn correlation_id_in_logs() {
+        let logs = Arc::new(Mutex::new(Vec::new()));
+        let store = Store::new(counter_reducer)
+            .wrap(StripMetaMiddleware::new())
+            .await
+            .wrap(CorrelationLogger { logs: logs.clone() })
+            .await;
+
+        store.dispatch_with_meta(CounterAction::Increment, CorrelationId("req-1")).await;
+        store.dispatch_with_meta(CounterAction::Increment, CorrelationId("req-2")).await;
+        store.dispatch(MetaEnvelope::new(CounterAction::Decrement, CorrelationId("req-3"))).await;
+
+        assert_eq!(
+            *logs.lock().unwrap(),
+            vec![
+                "[req-1] Increment".to_string(),
+                "[req-2] Increment".to_string(),
+                "[req-3] Decrement".to_string()
+            ]
+        );
+        assert_eq!(store.select(|state: &Counter| state.value).await, 1);
+    }
+}
@@ -1,7 +1,10 @@
-use crate::{Selector, Subscriber};
+use crate::{ActionStream, ObserveActions, Selector, Subscriber};
 use async_trait::async_trait;
+use std::future::Future;
 use std::marker::PhantomData;
-use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex, OnceLock};
+use std::time::Duration;
 
 /// The store api offers an abstraction around all store functionality.
 ///
@@ -19,6 +22,19 @@ where
     /// this enables us to dispatch actions from multiple places at once without requiring locks.
     async fn dispatch<A: Into<Action> + Send>(&self, action: A);
 
+    /// Dispatch the actions one by one, waiting for every action to be processed before the next one is dispatched.
+    /// For a plain store this means the state is updated and the subscribers are notified before the next action is dispatched.
+    /// Middlewares decide when their dispatch completes, a thunk for example may still be running in the background.
+    async fn dispatch_all_and_wait<I>(&self, actions: I)
+    where
+        I: IntoIterator<Item = Action> + Send,
+        I::IntoIter: Send,
+    {
+        for action in actions {
+            self.dispatch(action).await;
+        }
+    }
+
     /// Select a part of the state, this is more efficient than copying the entire state all the time.
     /// In case you still need a full copy of the state, use the state_cloned method.
     async fn select<S: Selector<State, Result = Result>, Result>(&self, selector: S) -> Result
@@ -35,9 +51,205 @@ where
         self.select(|state: &State| state.clone()).await
     }
 
+    /// Select a part of the state, or return None if the store doesn't respond within the timeout.
+    /// This prevents a stuck store, for example due to a slow subscriber, from blocking the caller indefinitely.
+    async fn select_with_timeout<S, Result>(&self, selector: S, timeout: Duration) -> Option<Result>
+    where
+        S: Selector<State, Result = Result> + Send + 'static,
+        Result: Send + 'static,
+    {
+        tokio::time::timeout(timeout, self.select(selector)).await.ok()
+    }
+
+    /// Run two selectors on the same state and return both results.
+    /// Unlike two separate selects, no action can be reduced in between the two selectors.
+    async fn select_zip<S1, S2>(&self, s1: S1, s2: S2) -> (S1::Result, S2::Result)
+    where
+        S1: Selector<State> + Send + 'static,
+        S2: Selector<State> + Send + 'static,
+        S1::Result: Send + 'static,
+        S2::Result: Send + 'static,
+    {
+        self.select(move |state: &State| (s1.select(state), s2.select(state))).await
+    }
+
+    /// Returns true if the predicate holds for the current state, the same as selecting the predicate.
+    async fn state_matches<P>(&self, predicate: P) -> bool
+    where
+        P: Fn(&State) -> bool + Send + 'static,
+    {
+        self.select(predicate).await
+    }
+
+    /// Returns true if the predicate doesn't hold for the current state, the opposite of `state_matches`
+    async fn state_not_matches<P>(&self, predicate: P) -> bool
+    where
+        P: Fn(&State) -> bool + Send + 'static,
+    {
+        !self.state_matches(predicate).await
+    }
+
+    /// Select a future from the state and await it.
+    /// Only the selector runs inside the store, the future is awaited outside of it, so actions can be reduced in the meantime.
+    ///
+    /// The selector only gets a reference to the state, a future which can only be awaited once,
+    /// like a `oneshot::Receiver`, has to be taken out of the state, e.g. from an `Option` behind a `Mutex`.
+    async fn select_future<S, Fut>(&self, selector: S) -> Fut::Output
+    where
+        S: Selector<State, Result = Fut> + Send + 'static,
+        Fut: Future + Send + 'static,
+    {
+        self.select(selector).await.await
+    }
+
+    /// Run a closure with a reference to the current state and return its result.
+    /// The closure runs inside the store, so the state is never cloned.
+    /// Unlike a selector the closure only has to be callable once.
+    async fn with_state<F, R>(&self, f: F) -> R
+    where
+        F: FnOnce(&State) -> R + Send + 'static,
+        R: Send + 'static,
+    {
+        let f = Mutex::new(Some(f));
+        self.select(move |state: &State| {
+            let f = f.lock().unwrap().take().expect("with_state closure is only called once");
+            f(state)
+        })
+        .await
+    }
+
     /// Subscribe to state changes.
     /// Every time an action is dispatched the subscriber will be notified after the state is updated
     async fn subscribe<S: Subscriber<State> + Send + 'static>(&self, subscriber: S);
+
+    /// Subscribe a subscriber which is Sync as well, it's notified like a regular subscriber.
+    /// Middleware stores pass such a subscriber on to their `MiddleWare::on_subscribe` hooks as is,
+    /// a subscriber passed to `subscribe` is wrapped in a mutex once for that, by the outermost store.
+    async fn subscribe_sync<S: Subscriber<State> + Send + Sync + 'static>(&self, subscriber: S) {
+        self.subscribe(subscriber).await
+    }
+
+    /// Subscribe to state changes until the condition is met.
+    /// The subscriber is notified like a regular subscriber, including for the state that meets the condition.
+    ///
+    /// The default implementation silences the subscriber afterwards, stores which support it remove the subscriber instead.
+    async fn subscribe_until<S, P>(&self, subscriber: S, condition: P)
+    where
+        S: Subscriber<State> + Send + Sync + 'static,
+        P: Fn(&State) -> bool + Send + Sync + 'static,
+    {
+        let done = AtomicBool::new(false);
+        self.subscribe(move |state: &State| {
+            if !done.load(Ordering::SeqCst) {
+                subscriber.notify(state);
+                done.store(condition(state), Ordering::SeqCst);
+            }
+        })
+        .await
+    }
+
+    /// Returns the number of middleware layers wrapped around the underlying store.
+    /// A plain store has a depth of 0, every call to wrap adds 1.
+    fn middleware_depth(&self) -> usize {
+        0
+    }
+
+    /// Returns the names of the middleware layers wrapped around the underlying store, outermost first.
+    /// A plain store has no middleware names, see `MiddleWare::middleware_name`.
+    fn middleware_names(&self) -> Vec<String> {
+        Vec::new()
+    }
+
+    /// Run the `MiddleWare::teardown` hooks of the middleware layers, outermost first.
+    /// A plain store has nothing to tear down, see `StoreWithMiddleware::shutdown`.
+    async fn teardown(&self) {}
+}
+
+// A shared store is a store as well, this makes it possible to use e.g. an Arc<Store> wherever a StoreApi is expected
+#[async_trait]
+impl<S, State, Action> StoreApi<State, Action> for Arc<S>
+where
+    S: StoreApi<State, Action> + Send + Sync + ?Sized,
+    Action: Send + 'static,
+    State: Send + 'static,
+{
+    async fn dispatch<A: Into<Action> + Send>(&self, action: A) {
+        S::dispatch(self, action).await
+    }
+
+    async fn select<Sel: Selector<State, Result = Result>, Result>(&self, selector: Sel) -> Result
+    where
+        Sel: Selector<State, Result = Result> + Send + 'static,
+        Result: Send + 'static,
+    {
+        S::select(self, selector).await
+    }
+
+    async fn state_cloned(&self) -> State
+    where
+        State: Clone,
+    {
+        S::state_cloned(self).await
+    }
+
+    async fn subscribe<Sub: Subscriber<State> + Send + 'static>(&self, subscriber: Sub) {
+        S::subscribe(self, subscriber).await
+    }
+
+    async fn subscribe_sync<Sub: Subscriber<State> + Send + Sync + 'static>(&self, subscriber: Sub) {
+        S::subscribe_sync(self, subscriber).await
+    }
+
+    async fn subscribe_until<Sub, P>(&self, subscriber: Sub, condition: P)
+    where
+        Sub: Subscriber<State> + Send + Sync + 'static,
+        P: Fn(&State) -> bool + Send + Sync + 'static,
+    {
+        S::subscribe_until(self, subscriber, condition).await
+    }
+
+    fn middleware_depth(&self) -> usize {
+        S::middleware_depth(self)
+    }
+
+    fn middleware_names(&self) -> Vec<String> {
+        S::middleware_names(self)
+    }
+
+    async fn teardown(&self) {
+        S::teardown(self).await
+    }
+}
+
+/// Describe the middleware stack of a store as a Graphviz DOT graph.
+///
+/// Every middleware is a node labeled with its name, followed by a node for the underlying store.
+/// The edges follow the direction in which a dispatched action flows, from the outermost middleware to the store.
+/// Render the output with e.g. `dot -Tsvg`.
+pub fn middleware_graph<State, Action>(store: &impl StoreApi<State, Action>) -> String
+where
+    State: Send + 'static,
+    Action: Send + 'static,
+{
+    let names = store.middleware_names();
+
+    let mut dot = String::from("digraph middleware {\n    rankdir=LR;\n");
+    for (index, name) in names.iter().enumerate() {
+        let label = name.replace('\\', "\\\\").replace('"', "\\\"");
+        dot.push_str(&format!("    m{index} [label=\"{label}\"];\n"));
+    }
+    dot.push_str("    store [label=\"Store\", shape=box];\n");
+
+    let nodes: Vec<String> = (0..names.len())
+        .map(|index| format!("m{index}"))
+        .chain(std::iter::once("store".to_string()))
+        .collect();
+    for edge in nodes.windows(2) {
+        dot.push_str(&format!("    {} -> {};\n", edge[0], edge[1]));
+    }
+    dot.push_str("}\n");
+
+    dot
 }
 
 /// Middlewares are the way to introduce side effects to the redux store.
@@ -124,6 +336,82 @@ where
     ///
     /// NOTE: In the middleware you need to call `inner.dispatch(action).await;` otherwise no actions will be send to the underlying StoreApi (and eventually store)
     async fn dispatch(&self, action: Action, inner: &Arc<Inner>);
+
+    /// This method is called every time a subscriber is registered, right after the underlying store registered it.
+    ///
+    /// The subscriber can't be replaced, but it can be inspected or notified.
+    /// For example, you could count the subscribers or notify a new subscriber with the current state.
+    /// Because the subscriber is registered first, it doesn't miss a state change which happens while the hook runs,
+    /// it might be notified of such a change before the hook notifies it though.
+    /// The hooks of a middleware stack run innermost first.
+    #[allow(unused_variables)]
+    async fn on_subscribe(&self, subscriber: &(dyn Subscriber<State> + Sync), inner: &Arc<Inner>) {}
+
+    /// This method is called when the store is shut down with `StoreWithMiddleware::shutdown`, the counterpart of init.
+    ///
+    /// Cleanup can be done here, like flushing buffers, closing connections or cancelling background tasks.
+    /// The inner store is still running, so actions can still be dispatched to it.
+    #[allow(unused_variables)]
+    async fn teardown(&self, inner: &Arc<Inner>) {}
+
+    /// The name of the middleware, used to identify it when inspecting a store.
+    /// Defaults to the type name of the middleware.
+    fn middleware_name(&self) -> &str {
+        std::any::type_name::<Self>()
+    }
+}
+
+/// Create a middleware from a plain function or closure, without declaring a struct.
+///
+/// The function receives the action and the inner store, like `MiddleWare::dispatch` does.
+/// Since the type of the inner store is only known when the middleware is wrapped around it,
+/// the closure arguments usually need a type annotation.
+///
+/// ## Example
+/// ```
+/// use redux_rs::{middleware_from_fn, Store, StoreApi};
+/// use std::sync::Arc;
+///
+/// fn reducer(state: u8, add: u8) -> u8 {
+///     state + add
+/// }
+///
+/// # #[tokio::main(flavor = "current_thread")]
+/// # async fn main() {
+/// let double = middleware_from_fn(|action: u8, inner: Arc<Store<u8, u8, fn(u8, u8) -> u8>>| async move {
+///     inner.dispatch(action * 2).await
+/// });
+/// let store = Store::new(reducer as fn(u8, u8) -> u8).wrap(double).await;
+/// store.dispatch(3).await;
+///
+/// assert_eq!(store.select(|state: &u8| *state).await, 6);
+/// # }
+/// ```
+pub fn middleware_from_fn<State, Action, Inner, F, Fut>(f: F) -> impl MiddleWare<State, Action, Inner>
+where
+    F: Fn(Action, Arc<Inner>) -> Fut + Send + Sync,
+    Fut: Future<Output = ()> + Send,
+    Action: Send + 'static,
+    State: Send + 'static,
+    Inner: StoreApi<State, Action> + Send + Sync,
+{
+    FnMiddleware(f)
+}
+
+struct FnMiddleware<F>(F);
+
+#[async_trait]
+impl<F, Fut, State, Action, Inner> MiddleWare<State, Action, Inner> for FnMiddleware<F>
+where
+    F: Fn(Action, Arc<Inner>) -> Fut + Send + Sync,
+    Fut: Future<Output = ()> + Send,
+    Action: Send + 'static,
+    State: Send + 'static,
+    Inner: StoreApi<State, Action> + Send + Sync,
+{
+    async fn dispatch(&self, action: Action, inner: &Arc<Inner>) {
+        (self.0)(action, inner.clone()).await
+    }
 }
 
 /// Store which ties an underlying store and middleware together.
@@ -137,6 +425,9 @@ where
 {
     inner: Arc<Inner>,
     middleware: M,
+    // Set once the middleware is disabled for the first time, only possible when the actions can be converted
+    bypass: OnceLock<fn(OuterAction) -> InnerAction>,
+    enabled: AtomicBool,
 
     _types: PhantomData<(State, InnerAction, OuterAction)>,
 }
@@ -149,14 +440,18 @@ where
     InnerAction: Send + Sync + 'static,
     OuterAction: Send + Sync + 'static,
 {
-    pub(crate) async fn new(inner: Inner, mut middleware: M) -> Self {
-        let inner = Arc::new(inner);
+    pub(crate) async fn new(inner: Inner, middleware: M) -> Self {
+        Self::new_shared(Arc::new(inner), middleware).await
+    }
 
+    pub(crate) async fn new_shared(inner: Arc<Inner>, mut middleware: M) -> Self {
         middleware.init(&inner).await;
 
         StoreWithMiddleware {
             inner,
             middleware,
+            bypass: OnceLock::new(),
+            enabled: AtomicBool::new(true),
             _types: Default::default(),
         }
     }
@@ -170,6 +465,119 @@ where
     {
         StoreWithMiddleware::new(self, middleware).await
     }
+
+    // Runs the on_subscribe hook of the middleware
+    pub(crate) async fn on_subscribe(&self, subscriber: &(dyn Subscriber<State> + Sync)) {
+        self.middleware.on_subscribe(subscriber, &self.inner).await
+    }
+
+    // Only the teardown hook of this layer, the inner store is left alone
+    pub(crate) async fn teardown_layer(&self) {
+        self.middleware.teardown(&self.inner).await
+    }
+
+    /// Shut the store down, the teardown hooks of all middlewares run first, outermost first.
+    ///
+    /// The store is consumed, the underlying store stops once the last reference to it is dropped.
+    pub async fn shutdown(self) {
+        StoreApi::teardown(&self).await;
+    }
+
+    /// Bypass the middleware, actions are dispatched straight to the inner store until the middleware is enabled again.
+    ///
+    /// This is a testing convenience, e.g. to silence a logging middleware in a specific test.
+    /// Only this layer is bypassed, other middlewares of the store keep handling the actions.
+    /// The hooks other than dispatch, like `MiddleWare::on_subscribe`, still run.
+    pub fn disable_middleware(&self)
+    where
+        OuterAction: Into<InnerAction>,
+    {
+        self.bypass.get_or_init(|| OuterAction::into);
+        self.enabled.store(false, Ordering::SeqCst);
+    }
+
+    /// Handle the actions with the middleware again, see `disable_middleware`
+    pub fn enable_middleware(&self) {
+        self.enabled.store(true, Ordering::SeqCst);
+    }
+
+    /// Returns false while the middleware is disabled
+    pub fn is_middleware_enabled(&self) -> bool {
+        self.enabled.load(Ordering::SeqCst)
+    }
+
+    /// Returns the name of the outermost middleware, see `MiddleWare::middleware_name`
+    pub fn middleware_name(&self) -> &str {
+        self.middleware.middleware_name()
+    }
+
+    /// Returns the number of middleware layers, this store included.
+    pub fn middleware_depth(&self) -> usize {
+        self.inner.middleware_depth() + 1
+    }
+}
+
+/// Apply middleware to any store.
+///
+/// `store.apply(middleware).await` does the same as `store.wrap(middleware).await`,
+/// the returned store dispatches every action through the middleware before it reaches the original store.
+/// The `.await` is needed because the `MiddleWare::init` hook of the middleware runs while it's applied.
+///
+/// Every `StoreApi` implements this trait, unlike `wrap` it's also available on e.g. a `PipelineStore`.
+///
+/// ## Example
+/// ```
+/// use redux_rs::{MiddleWare, Store, StoreApi, StoreMiddlewareExt};
+/// use async_trait::async_trait;
+/// use std::sync::Arc;
+///
+/// fn reducer(state: u8, add: u8) -> u8 {
+///     state + add
+/// }
+///
+/// struct DoubleMiddleware;
+///
+/// #[async_trait]
+/// impl<Inner> MiddleWare<u8, u8, Inner> for DoubleMiddleware
+/// where
+///     Inner: StoreApi<u8, u8> + Send + Sync,
+/// {
+///     async fn dispatch(&self, action: u8, inner: &Arc<Inner>) {
+///         inner.dispatch(action * 2).await
+///     }
+/// }
+///
+/// # #[tokio::main(flavor = "current_thread")]
+/// # async fn main() {
+/// let store = Store::new(reducer).apply(DoubleMiddleware).await;
+/// store.dispatch(3).await;
+///
+/// assert_eq!(store.select(|state: &u8| *state).await, 6);
+/// # }
+/// ```
+#[async_trait]
+pub trait StoreMiddlewareExt<State, Action>: StoreApi<State, Action> + Sized
+where
+    State: Send + Sync + 'static,
+    Action: Send + Sync + 'static,
+{
+    /// Apply the middleware to the store, see the trait documentation
+    async fn apply<M, OuterAction>(self, middleware: M) -> StoreWithMiddleware<Self, M, State, Action, OuterAction>
+    where
+        Self: Send + Sync,
+        M: MiddleWare<State, OuterAction, Self, Action> + Send + Sync,
+        OuterAction: Send + Sync + 'static,
+    {
+        StoreWithMiddleware::new(self, middleware).await
+    }
+}
+
+impl<T, State, Action> StoreMiddlewareExt<State, Action> for T
+where
+    T: StoreApi<State, Action>,
+    State: Send + Sync + 'static,
+    Action: Send + Sync + 'static,
+{
 }
 
 #[async_trait]
@@ -182,6 +590,12 @@ where
     OuterAction: Send + Sync + 'static,
 {
     async fn dispatch<A: Into<OuterAction> + Send>(&self, action: A) {
+        if !self.enabled.load(Ordering::SeqCst) {
+            if let Some(bypass) = self.bypass.get() {
+                return self.inner.dispatch(bypass(action.into())).await;
+            }
+        }
+
         self.middleware.dispatch(action.into(), &self.inner).await
     }
 
@@ -194,7 +608,64 @@ where
     }
 
     async fn subscribe<S: Subscriber<State> + Send + 'static>(&self, subscriber: S) {
-        self.inner.subscribe(subscriber).await;
+        // Subscribers are only required to be Send, the mutex makes them Sync so the hooks can borrow them.
+        // The inner middleware stores get the Sync subscriber, so it's only wrapped once.
+        let subscriber = Mutex::new(subscriber);
+        self.subscribe_sync(move |state: &State| subscriber.lock().unwrap().notify(state)).await;
+    }
+
+    async fn subscribe_sync<S: Subscriber<State> + Send + Sync + 'static>(&self, subscriber: S) {
+        // The subscriber is registered before the hook runs, so it can't miss a change in between
+        let subscriber = Arc::new(subscriber);
+        let registered = subscriber.clone();
+        self.inner.subscribe_sync(move |state: &State| registered.notify(state)).await;
+
+        self.on_subscribe(&*subscriber).await;
+    }
+
+    async fn subscribe_until<S, P>(&self, subscriber: S, condition: P)
+    where
+        S: Subscriber<State> + Send + Sync + 'static,
+        P: Fn(&State) -> bool + Send + Sync + 'static,
+    {
+        let subscriber = Arc::new(subscriber);
+        let registered = subscriber.clone();
+        self.inner.subscribe_until(move |state: &State| registered.notify(state), condition).await;
+
+        self.on_subscribe(&*subscriber).await;
+    }
+
+    fn middleware_depth(&self) -> usize {
+        StoreWithMiddleware::middleware_depth(self)
+    }
+
+    fn middleware_names(&self) -> Vec<String> {
+        std::iter::once(self.middleware_name().to_string())
+            .chain(self.inner.middleware_names())
+            .collect()
+    }
+
+    async fn teardown(&self) {
+        self.teardown_layer().await;
+        self.inner.teardown().await;
+    }
+}
+
+#[async_trait]
+impl<Inner, M, State, InnerAction, OuterAction, RootAction> ObserveActions<RootAction> for StoreWithMiddleware<Inner, M, State, InnerAction, OuterAction>
+where
+    Inner: StoreApi<State, InnerAction> + ObserveActions<RootAction> + Send + Sync,
+    M: MiddleWare<State, OuterAction, Inner, InnerAction> + Send + Sync,
+    State: Send + Sync + 'static,
+    InnerAction: Send + Sync + 'static,
+    OuterAction: Send + Sync + 'static,
+    RootAction: Send + 'static,
+{
+    async fn observe_actions(&self) -> ActionStream<RootAction>
+    where
+        RootAction: Clone,
+    {
+        self.inner.observe_actions().await
     }
 }
 
@@ -202,7 +673,6 @@ where
 mod tests {
     use super::*;
     use crate::Store;
-    use std::sync::Mutex;
 
     #[derive(Default)]
     struct LogStore {
@@ -335,4 +805,278 @@ mod tests {
             );
         }
     }
+
+    #[tokio::test]
+    async fn middleware_depth() {
+        let logs = Arc::new(Mutex::new(Vec::new()));
+
+        let store = Store::new(log_reducer);
+        assert_eq!(store.middleware_depth(), 0);
+
+        let store = store.wrap(LoggerMiddleware::new("middleware_1", logs.clone())).await;
+        assert_eq!(store.middleware_depth(), 1);
+
+        let store = store
+            .wrap(LoggerMiddleware::new("middleware_2", logs.clone()))
+            .await
+            .wrap(LoggerMiddleware::new("middleware_3", logs.clone()))
+            .await;
+        assert_eq!(store.middleware_depth(), 3);
+    }
+
+    #[tokio::test]
+    async fn middleware_name() {
+        let logs = Arc::new(Mutex::new(Vec::new()));
+        let store = Store::new(log_reducer).wrap(LoggerMiddleware::new("log", logs)).await;
+
+        assert_eq!(store.middleware_name(), std::any::type_name::<LoggerMiddleware>());
+        assert!(store.middleware_name().ends_with("LoggerMiddleware"));
+    }
+
+    struct NamedMiddleware(&'static str);
+
+    #[async_trait]
+    impl<Inner> MiddleWare<LogStore, Log, Inner> for NamedMiddleware
+    where
+        Inner: StoreApi<LogStore, Log> + Send + Sync,
+    {
+        async fn dispatch(&self, action: Log, inner: &Arc<Inner>) {
+            inner.dispatch(action).await;
+        }
+
+        fn middleware_name(&self) -> &str {
+            self.0
+        }
+    }
+
+    #[tokio::test]
+    async fn middleware_graph_lists_the_stack() {
+        let store = Store::new(log_reducer)
+            .wrap(NamedMiddleware("inner"))
+            .await
+            .wrap(NamedMiddleware("middle"))
+            .await
+            .wrap(NamedMiddleware("outer"))
+            .await;
+
+        assert_eq!(store.middleware_names(), vec!["outer", "middle", "inner"]);
+
+        let dot = middleware_graph(&store);
+        assert!(dot.starts_with("digraph middleware {"));
+        assert!(dot.contains("[label=\"outer\"]"));
+        assert!(dot.contains("[label=\"middle\"]"));
+        assert!(dot.contains("[label=\"inner\"]"));
+        assert_eq!(dot.matches(" -> ").count(), 3);
+        assert!(dot.contains("m2 -> store;"));
+    }
+
+    #[tokio::test]
+    async fn apply_matches_wrap() {
+        let wrap_logs = Arc::new(Mutex::new(Vec::new()));
+        let wrapped = Store::new(log_reducer).wrap(LoggerMiddleware::new("log", wrap_logs.clone())).await;
+
+        let apply_logs = Arc::new(Mutex::new(Vec::new()));
+        let applied = Store::new(log_reducer).apply(LoggerMiddleware::new("log", apply_logs.clone())).await;
+
+        wrapped.dispatch(Log("Log 1".to_string())).await;
+        applied.dispatch(Log("Log 1".to_string())).await;
+
+        assert_eq!(*wrap_logs.lock().unwrap(), *apply_logs.lock().unwrap());
+        assert_eq!(
+            wrapped.select(|state: &LogStore| state.logs.clone()).await,
+            applied.select(|state: &LogStore| state.logs.clone()).await
+        );
+        assert_eq!(applied.middleware_depth(), 1);
+
+        let applied = applied.apply(NamedMiddleware("outer")).await;
+        assert_eq!(applied.middleware_depth(), 2);
+    }
+
+    #[derive(Clone, Default)]
+    struct Counter(i32);
+
+    fn counter_reducer(state: Counter, add: i32) -> Counter {
+        Counter(state.0 + add)
+    }
+
+    // Notifies every new subscriber with the current state, like react-redux does
+    struct InitialStateMiddleware;
+
+    #[async_trait]
+    impl<Inner> MiddleWare<Counter, i32, Inner> for InitialStateMiddleware
+    where
+        Inner: StoreApi<Counter, i32> + Send + Sync,
+    {
+        async fn dispatch(&self, action: i32, inner: &Arc<Inner>) {
+            inner.dispatch(action).await
+        }
+
+        async fn on_subscribe(&self, subscriber: &(dyn Subscriber<Counter> + Sync), inner: &Arc<Inner>) {
+            subscriber.notify(&inner.state_cloned().await);
+        }
+    }
+
+    #[tokio::test]
+    async fn on_subscribe_notifies_with_initial_state() {
+        let store = Store::new_with_state(counter_reducer, Counter(5)).wrap(InitialStateMiddleware).await;
+
+        let values = Arc::new(Mutex::new(Vec::new()));
+        let subscriber_values = values.clone();
+        store.subscribe(move |state: &Counter| subscriber_values.lock().unwrap().push(state.0)).await;
+        assert_eq!(*values.lock().unwrap(), vec![5]);
+
+        store.dispatch(2).await;
+        assert_eq!(*values.lock().unwrap(), vec![5, 7]);
+
+        let until_values = Arc::new(Mutex::new(Vec::new()));
+        let subscriber_values = until_values.clone();
+        store
+            .subscribe_until(
+                move |state: &Counter| subscriber_values.lock().unwrap().push(state.0),
+                |state: &Counter| state.0 > 8,
+            )
+            .await;
+        store.dispatch(3).await;
+        store.dispatch(3).await;
+        assert_eq!(*until_values.lock().unwrap(), vec![7, 10]);
+    }
+
+    // Dispatches an action from its hook, the new subscriber has to see it
+    struct DispatchOnSubscribeMiddleware;
+
+    #[async_trait]
+    impl<Inner> MiddleWare<Counter, i32, Inner> for DispatchOnSubscribeMiddleware
+    where
+        Inner: StoreApi<Counter, i32> + Send + Sync,
+    {
+        async fn dispatch(&self, action: i32, inner: &Arc<Inner>) {
+            inner.dispatch(action).await
+        }
+
+        async fn on_subscribe(&self, _subscriber: &(dyn Subscriber<Counter> + Sync), inner: &Arc<Inner>) {
+            inner.dispatch(1).await
+        }
+    }
+
+    #[tokio::test]
+    async fn subscriber_is_registered_before_hooks_run() {
+        let store = Store::new(counter_reducer)
+            .wrap(DispatchOnSubscribeMiddleware)
+            .await
+            .wrap(DispatchOnSubscribeMiddleware)
+            .await
+            .wrap(InitialStateMiddleware)
+            .await;
+
+        let values = Arc::new(Mutex::new(Vec::new()));
+        let subscriber_values = values.clone();
+        store.subscribe(move |state: &Counter| subscriber_values.lock().unwrap().push(state.0)).await;
+
+        // The hooks run innermost first, every dispatch of a hook reaches the subscriber
+        assert_eq!(*values.lock().unwrap(), vec![1, 2, 2]);
+    }
+
+    #[tokio::test]
+    async fn middleware_from_fn_matches_logger_middleware() {
+        let struct_logs = Arc::new(Mutex::new(Vec::new()));
+        let struct_store = Store::new(log_reducer).wrap(LoggerMiddleware::new("log", struct_logs.clone())).await;
+
+        type LogReducer = fn(LogStore, Log) -> LogStore;
+        let fn_logs = Arc::new(Mutex::new(Vec::new()));
+        let logs = fn_logs.clone();
+        let logger = middleware_from_fn(move |action: Log, inner: Arc<Store<LogStore, Log, LogReducer>>| {
+            let logs = logs.clone();
+            async move {
+                let log_message = action.0.clone();
+                logs.lock().unwrap().push(format!("[log] Before dispatching log message: {:?}", log_message));
+                inner.dispatch(action).await;
+                logs.lock().unwrap().push(format!("[log] After dispatching log message: {:?}", log_message));
+            }
+        });
+        let fn_store = Store::new(log_reducer as LogReducer).wrap(logger).await;
+
+        for message in ["Log 1", "Log 2"] {
+            struct_store.dispatch(Log(message.to_string())).await;
+            fn_store.dispatch(Log(message.to_string())).await;
+        }
+
+        assert_eq!(fn_logs.lock().unwrap().len(), 4);
+        assert_eq!(*fn_logs.lock().unwrap(), *struct_logs.lock().unwrap());
+        assert_eq!(
+            fn_store.select(|state: &LogStore| state.logs.clone()).await,
+            struct_store.select(|state: &LogStore| state.logs.clone()).await
+        );
+    }
+
+    #[tokio::test]
+    async fn disable_and_enable_middleware() {
+        let logs = Arc::new(Mutex::new(Vec::new()));
+        let store = Store::new(log_reducer).wrap(LoggerMiddleware::new("log", logs.clone())).await;
+
+        let dispatch_five = |round: &'static str| {
+            let store = &store;
+            async move {
+                for i in 0..5 {
+                    store.dispatch(Log(format!("{} {}", round, i))).await;
+                }
+            }
+        };
+
+        dispatch_five("enabled").await;
+        assert_eq!(logs.lock().unwrap().len(), 10);
+
+        store.disable_middleware();
+        assert!(!store.is_middleware_enabled());
+        dispatch_five("disabled").await;
+        assert_eq!(logs.lock().unwrap().len(), 10);
+
+        store.enable_middleware();
+        dispatch_five("re-enabled").await;
+        assert_eq!(logs.lock().unwrap().len(), 20);
+
+        assert_eq!(store.select(|state: &LogStore| state.logs.len()).await, 15);
+    }
+
+    // Collects the dispatched messages and only writes them to the sink on teardown
+    struct FlushOnTeardownMiddleware {
+        buffer: Mutex<Vec<String>>,
+        sink: Arc<Mutex<Vec<String>>>,
+    }
+
+    #[async_trait]
+    impl<Inner> MiddleWare<LogStore, Log, Inner> for FlushOnTeardownMiddleware
+    where
+        Inner: StoreApi<LogStore, Log> + Send + Sync,
+    {
+        async fn dispatch(&self, action: Log, inner: &Arc<Inner>) {
+            self.buffer.lock().unwrap().push(action.0.clone());
+            inner.dispatch(action).await;
+        }
+
+        async fn teardown(&self, _inner: &Arc<Inner>) {
+            let buffer = std::mem::take(&mut *self.buffer.lock().unwrap());
+            self.sink.lock().unwrap().extend(buffer);
+        }
+    }
+
+    #[tokio::test]
+    async fn shutdown_runs_teardown_hooks() {
+        let sink = Arc::new(Mutex::new(Vec::new()));
+        let logs = Arc::new(Mutex::new(Vec::new()));
+        let store = Store::new(log_reducer)
+            .wrap(FlushOnTeardownMiddleware {
+                buffer: Mutex::new(Vec::new()),
+                sink: sink.clone(),
+            })
+            .await
+            .wrap(LoggerMiddleware::new("log", logs.clone()))
+            .await;
+
+        store.dispatch(Log("Log 1".to_string())).await;
+        store.dispatch(Log("Log 2".to_string())).await;
+        assert!(sink.lock().unwrap().is_empty());
+
+        store.shutdown().await;
+        assert_eq!(*sink.lock().unwrap(), vec!["Log 1", "Log 2"]);
+    }
 }
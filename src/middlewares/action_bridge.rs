@@ -0,0 +1,149 @@
+use crate::composed_middleware::impl_middleware_bitor;
+use crate::{MiddleWare, StoreApi};
+use async_trait::async_trait;
+use std::sync::{Arc, Mutex};
+
+/// Publishes actions to an external event bus, for example a pub/sub channel shared by multiple instances.
+#[async_trait]
+pub trait Publisher<Action>: Send + Sync {
+    async fn publish(&self, action: &Action);
+}
+
+/// # Action bridge middleware
+/// A middleware which publishes matching actions to an external event bus.
+///
+/// Every action is dispatched to the wrapped store, actions for which `pattern` returns true are published as well.
+/// The action is published before it's dispatched to the wrapped store.
+///
+/// ## Usage:
+/// ```
+/// use std::sync::Arc;
+/// use redux_rs::{middlewares::action_bridge::{ActionBridgeMiddleware, InMemoryPublisher}, Store, StoreApi};
+///
+/// #[derive(Default)]
+/// struct Counter(i32);
+///
+/// fn counter_reducer(state: Counter, add: i32) -> Counter {
+///     Counter(state.0 + add)
+/// }
+///
+/// # #[tokio::main(flavor = "current_thread")]
+/// # async fn main() {
+/// let publisher = Arc::new(InMemoryPublisher::new());
+///
+/// // Publish large additions only
+/// let bridge = ActionBridgeMiddleware::new(|add: &i32| *add >= 10, publisher.clone());
+/// let store = Store::new(counter_reducer).wrap(bridge).await;
+///
+/// store.dispatch(1).await;
+/// store.dispatch(10).await;
+///
+/// assert_eq!(publisher.received(), vec![10]);
+/// # }
+/// ```
+pub struct ActionBridgeMiddleware<Action> {
+    pattern: fn(&Action) -> bool,
+    publisher: Arc<dyn Publisher<Action>>,
+}
+
+impl<Action> ActionBridgeMiddleware<Action> {
+    /// Publish every action for which `pattern` returns true with `publisher`
+    pub fn new(pattern: fn(&Action) -> bool, publisher: Arc<dyn Publisher<Action>>) -> Self {
+        ActionBridgeMiddleware { pattern, publisher }
+    }
+}
+
+impl_middleware_bitor!(ActionBridgeMiddleware<Action>);
+
+#[async_trait]
+impl<State, Action, Inner> MiddleWare<State, Action, Inner> for ActionBridgeMiddleware<Action>
+where
+    State: Send + 'static,
+    Action: Send + Sync + 'static,
+    Inner: StoreApi<State, Action> + Send + Sync,
+{
+    async fn dispatch(&self, action: Action, inner: &Arc<Inner>) {
+        if (self.pattern)(&action) {
+            self.publisher.publish(&action).await;
+        }
+
+        inner.dispatch(action).await
+    }
+}
+
+/// A publisher which keeps the published actions in memory, useful for tests
+pub struct InMemoryPublisher<Action> {
+    received: Mutex<Vec<Action>>,
+}
+
+impl<Action> InMemoryPublisher<Action> {
+    pub fn new() -> Self {
+        InMemoryPublisher {
+            received: Mutex::new(Vec::new()),
+        }
+    }
+
+    /// Returns a copy of all published actions, in the order they were published
+    pub fn received(&self) -> Vec<Action>
+    where
+        Action: Clone,
+    {
+        self.received.lock().unwrap().clone()
+    }
+}
+
+impl<Action> Default for InMemoryPublisher<Action> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[async_trait]
+impl<Action> Publisher<Action> for InMemoryPublisher<Action>
+where
+    Action: Clone + Send + Sync,
+{
+    async fn publish(&self, action: &Action) {
+        self.received.lock().unwrap().push(action.clone());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Store;
+
+    #[derive(Default)]
+    struct Counter {
+        value: i32,
+    }
+
+    #[derive(Clone, Debug, PartialEq)]
+    enum CounterAction {
+        Increment,
+        Decrement,
+    }
+
+    fn counter_reducer(state: Counter, action: CounterAction) -> Counter {
+        match action {
+            CounterAction::Increment => Counter { value: state.value + 1 },
+            CounterAction::Decrement => Counter { value: state.value - 1 },
+        }
+    }
+
+    #[tokio::test]
+    async fn publish_matching_actions() {
+        let publisher = Arc::new(InMemoryPublisher::new());
+        let bridge = ActionBridgeMiddleware::new(|action: &CounterAction| *action == CounterAction::Increment, publisher.clone());
+
+        let store = Store::new(counter_reducer).wrap(bridge).await;
+
+        store.dispatch(CounterAction::Increment).await;
+        store.dispatch(CounterAction::Decrement).await;
+        store.dispatch(CounterAction::Increment).await;
+
+        // Only the increments are published, but every action reached the store
+        assert_eq!(publisher.received(), vec![CounterAction::Increment, CounterAction::Increment]);
+        assert_eq!(store.select(|state: &Counter| state.value).await, 1);
+    }
+}
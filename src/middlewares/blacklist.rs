@@ -0,0 +1,138 @@
+use crate::composed_middleware::impl_middleware_bitor;
+use crate::{MiddleWare, StoreApi};
+use async_trait::async_trait;
+use std::collections::HashSet;
+use std::mem::Discriminant;
+use std::sync::Arc;
+
+/// Identifies an action variant, regardless of the data it carries
+pub type ActionDiscriminant<Action> = Discriminant<Action>;
+
+/// # Blacklist middleware
+/// A middleware which silently drops actions of blacklisted variants.
+///
+/// This is useful for disabling debug-only actions in production without `#[cfg]` guards around every dispatch.
+///
+/// ## Usage:
+/// ```
+/// use std::collections::HashSet;
+/// use std::mem::discriminant;
+/// use redux_rs::{middlewares::blacklist::BlacklistMiddleware, Store, StoreApi};
+///
+/// #[derive(Default)]
+/// struct Counter(i32);
+///
+/// enum Action {
+///     Increment,
+///     DumpState,
+/// }
+///
+/// fn reducer(state: Counter, action: Action) -> Counter {
+///     match action {
+///         Action::Increment => Counter(state.0 + 1),
+///         Action::DumpState => {
+///             println!("Counter: {}", state.0);
+///             state
+///         }
+///     }
+/// }
+///
+/// # #[tokio::main(flavor = "current_thread")]
+/// # async fn main() {
+/// let blacklisted = HashSet::from([discriminant(&Action::DumpState)]);
+/// let store = Store::new(reducer).wrap(BlacklistMiddleware::new(blacklisted)).await;
+///
+/// // Never reaches the reducer
+/// store.dispatch(Action::DumpState).await;
+/// # }
+/// ```
+pub struct BlacklistMiddleware<Action> {
+    blacklisted: HashSet<ActionDiscriminant<Action>>,
+}
+
+impl<Action> BlacklistMiddleware<Action> {
+    pub fn new(blacklisted: HashSet<ActionDiscriminant<Action>>) -> Self {
+        BlacklistMiddleware { blacklisted }
+    }
+
+    /// Returns true if actions of this variant are dropped
+    pub fn is_blacklisted(&self, action: &Action) -> bool {
+        self.blacklisted.contains(&std::mem::discriminant(action))
+    }
+}
+
+impl_middleware_bitor!(BlacklistMiddleware<Action>);
+
+#[async_trait]
+impl<State, Action, Inner> MiddleWare<State, Action, Inner> for BlacklistMiddleware<Action>
+where
+    State: Send + 'static,
+    Action: Send + 'static,
+    Inner: StoreApi<State, Action> + Send + Sync,
+{
+    async fn dispatch(&self, action: Action, inner: &Arc<Inner>) {
+        if !self.is_blacklisted(&action) {
+            inner.dispatch(action).await;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Store;
+    use std::mem::discriminant;
+
+    #[derive(Default)]
+    struct AppState {
+        value: i32,
+        dumps: usize,
+    }
+
+    enum AppAction {
+        Add(i32),
+        DumpState,
+    }
+
+    fn app_reducer(state: AppState, action: AppAction) -> AppState {
+        match action {
+            AppAction::Add(value) => AppState {
+                value: state.value + value,
+                ..state
+            },
+            AppAction::DumpState => AppState {
+                dumps: state.dumps + 1,
+                ..state
+            },
+        }
+    }
+
+    fn blacklist(production: bool) -> HashSet<ActionDiscriminant<AppAction>> {
+        if production {
+            HashSet::from([discriminant(&AppAction::DumpState)])
+        } else {
+            HashSet::new()
+        }
+    }
+
+    #[tokio::test]
+    async fn dump_state_only_outside_production() {
+        for production in [false, true] {
+            let store = Store::new(app_reducer).wrap(BlacklistMiddleware::new(blacklist(production))).await;
+
+            store.dispatch(AppAction::DumpState).await;
+            store.dispatch(AppAction::Add(5)).await;
+
+            let expected_dumps = if production { 0 } else { 1 };
+            assert_eq!(store.select(|state: &AppState| state.dumps).await, expected_dumps);
+            assert_eq!(store.select(|state: &AppState| state.value).await, 5);
+        }
+    }
+
+    #[test]
+    fn discriminant_ignores_data() {
+        let middleware = BlacklistMiddleware::new(HashSet::from([discriminant(&AppAction::Add(0))]));
+        assert!(middleware.is_blacklisted(&AppAction::Add(10)));
+        assert!(!middleware.is_blacklisted(&AppAction::DumpState));
+    }
+}
@@ -0,0 +1,300 @@
+use crate::composed_middleware::impl_middleware_bitor;
+use crate::middlewares::thunk::ActionOrThunk;
+use crate::{MiddleWare, StoreApi};
+use async_trait::async_trait;
+use std::collections::VecDeque;
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::{Arc, Mutex};
+use tokio::sync::mpsc::{unbounded_channel, UnboundedSender};
+
+/// An effect waiting to be executed, for example a thunk together with its store api
+pub type Effect = Pin<Box<dyn Future<Output = ()> + Send>>;
+
+/// Decides when and how the effects of a `ScheduledEffectMiddleware` run
+pub trait EffectScheduler: Send + Sync {
+    /// Take ownership of the effect, the scheduler decides when it runs
+    fn schedule(&self, effect: Effect);
+}
+
+/// # Scheduled effect middleware
+/// A thunk middleware which hands its thunks to an `EffectScheduler` instead of spawning them right away.
+///
+/// With the `ImmediateScheduler` this behaves like the `ThunkMiddleware`.
+/// Other schedulers can pause effects, run them in dispatch order or only run them when told to, which is useful in tests.
+///
+/// ## Usage:
+/// ```
+/// use std::sync::Arc;
+/// use redux_rs::{Store, StoreApi};
+/// use redux_rs::middlewares::effect_scheduler::{ManualScheduler, ScheduledEffectMiddleware};
+/// use redux_rs::middlewares::thunk::thunk;
+///
+/// fn reducer(state: u32, add: u32) -> u32 {
+///     state + add
+/// }
+///
+/// async fn add_ten(store_api: Arc<impl StoreApi<u32, u32>>) {
+///     store_api.dispatch(10u32).await;
+/// }
+///
+/// # #[tokio::main(flavor = "current_thread")]
+/// # async fn main() {
+/// let scheduler = Arc::new(ManualScheduler::new());
+/// let store = Store::new(reducer).wrap(ScheduledEffectMiddleware::new(scheduler.clone())).await;
+///
+/// store.dispatch(thunk(add_ten)).await;
+/// assert_eq!(store.select(|state: &u32| *state).await, 0);
+///
+/// scheduler.tick().await;
+/// assert_eq!(store.select(|state: &u32| *state).await, 10);
+/// # }
+/// ```
+pub struct ScheduledEffectMiddleware {
+    scheduler: Arc<dyn EffectScheduler>,
+}
+
+impl ScheduledEffectMiddleware {
+    /// Create the middleware, the effects of all thunks are handed to the scheduler
+    pub fn new(scheduler: Arc<dyn EffectScheduler>) -> Self {
+        ScheduledEffectMiddleware { scheduler }
+    }
+}
+
+impl Default for ScheduledEffectMiddleware {
+    fn default() -> Self {
+        Self::new(Arc::new(ImmediateScheduler))
+    }
+}
+
+impl_middleware_bitor!(ScheduledEffectMiddleware);
+
+#[async_trait]
+impl<State, Action, Inner> MiddleWare<State, ActionOrThunk<State, Action, Inner>, Inner, Action> for ScheduledEffectMiddleware
+where
+    Action: Send + 'static,
+    State: Send + 'static,
+    Inner: StoreApi<State, Action> + Send + Sync + 'static,
+{
+    async fn dispatch(&self, action: ActionOrThunk<State, Action, Inner>, inner: &Arc<Inner>) {
+        match action {
+            ActionOrThunk::Action(action) => {
+                inner.dispatch(action).await;
+            }
+            ActionOrThunk::Thunk(thunk) => {
+                let api = inner.to_owned();
+
+                self.scheduler.schedule(Box::pin(async move {
+                    thunk.execute(api).await;
+                }));
+            }
+        }
+    }
+}
+
+/// Spawns every effect as soon as it's scheduled, like the `ThunkMiddleware` does
+pub struct ImmediateScheduler;
+
+impl EffectScheduler for ImmediateScheduler {
+    fn schedule(&self, effect: Effect) {
+        tokio::spawn(effect);
+    }
+}
+
+/// Spawns effects immediately, unless the scheduler is paused.
+/// Effects scheduled while paused are spawned when the scheduler is resumed.
+#[derive(Default)]
+pub struct PausableScheduler {
+    paused: Mutex<Option<Vec<Effect>>>,
+}
+
+impl PausableScheduler {
+    /// Create a scheduler which isn't paused
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Hold back all effects until `resume` is called
+    pub fn pause(&self) {
+        let mut paused = self.paused.lock().unwrap();
+        if paused.is_none() {
+            *paused = Some(Vec::new());
+        }
+    }
+
+    /// Spawn the effects which were held back and stop pausing new effects
+    pub fn resume(&self) {
+        let pending = self.paused.lock().unwrap().take();
+        for effect in pending.into_iter().flatten() {
+            tokio::spawn(effect);
+        }
+    }
+
+    /// Returns true between `pause` and `resume`
+    pub fn is_paused(&self) -> bool {
+        self.paused.lock().unwrap().is_some()
+    }
+}
+
+impl EffectScheduler for PausableScheduler {
+    fn schedule(&self, effect: Effect) {
+        match self.paused.lock().unwrap().as_mut() {
+            Some(pending) => pending.push(effect),
+            None => {
+                tokio::spawn(effect);
+            }
+        }
+    }
+}
+
+/// Only runs effects when `tick` is called
+#[derive(Default)]
+pub struct ManualScheduler {
+    pending: Mutex<VecDeque<Effect>>,
+}
+
+impl ManualScheduler {
+    /// Create a scheduler without pending effects
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Run all pending effects one after another, returns the number of effects which ran.
+    /// Effects scheduled while ticking run on the next tick.
+    pub async fn tick(&self) -> usize {
+        let pending: Vec<Effect> = self.pending.lock().unwrap().drain(..).collect();
+        let count = pending.len();
+
+        for effect in pending {
+            effect.await;
+        }
+
+        count
+    }
+
+    /// The number of effects waiting for a tick
+    pub fn pending(&self) -> usize {
+        self.pending.lock().unwrap().len()
+    }
+}
+
+impl EffectScheduler for ManualScheduler {
+    fn schedule(&self, effect: Effect) {
+        self.pending.lock().unwrap().push_back(effect);
+    }
+}
+
+/// Runs effects one at a time, in the order they were scheduled.
+/// The scheduler spawns a task, so it has to be created within a tokio runtime.
+pub struct OrderedScheduler {
+    sender: UnboundedSender<Effect>,
+}
+
+impl OrderedScheduler {
+    /// Create the scheduler and spawn the task which runs the effects
+    pub fn new() -> Self {
+        let (sender, mut receiver) = unbounded_channel::<Effect>();
+
+        tokio::spawn(async move {
+            while let Some(effect) = receiver.recv().await {
+                effect.await;
+            }
+        });
+
+        OrderedScheduler { sender }
+    }
+}
+
+impl Default for OrderedScheduler {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl EffectScheduler for OrderedScheduler {
+    fn schedule(&self, effect: Effect) {
+        let _ = self.sender.send(effect);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::middlewares::thunk::thunk;
+    use crate::{Store, StoreApiExt};
+    use std::time::Duration;
+    use tokio::time::sleep;
+
+    fn log_reducer(mut state: Vec<u32>, value: u32) -> Vec<u32> {
+        state.push(value);
+        state
+    }
+
+    #[tokio::test]
+    async fn manual_scheduler_waits_for_tick() {
+        let scheduler = Arc::new(ManualScheduler::new());
+        let store = Store::new(log_reducer).wrap(ScheduledEffectMiddleware::new(scheduler.clone())).await;
+
+        async fn one(api: Arc<impl StoreApi<Vec<u32>, u32>>) {
+            api.dispatch(1u32).await;
+        }
+        async fn two(api: Arc<impl StoreApi<Vec<u32>, u32>>) {
+            api.dispatch(2u32).await;
+        }
+        async fn three(api: Arc<impl StoreApi<Vec<u32>, u32>>) {
+            api.dispatch(3u32).await;
+        }
+
+        store.dispatch(thunk(one)).await;
+        store.dispatch(thunk(two)).await;
+        store.dispatch(thunk(three)).await;
+
+        // Give spawned tasks a chance to run, there shouldn't be any
+        sleep(Duration::from_millis(10)).await;
+        assert_eq!(scheduler.pending(), 3);
+        assert!(store.select(|state: &Vec<u32>| state.is_empty()).await);
+
+        assert_eq!(scheduler.tick().await, 3);
+        assert_eq!(store.state_cloned().await, vec![1, 2, 3]);
+    }
+
+    #[tokio::test]
+    async fn pausable_scheduler() {
+        let scheduler = Arc::new(PausableScheduler::new());
+        let store = Store::new(log_reducer).wrap(ScheduledEffectMiddleware::new(scheduler.clone())).await;
+
+        async fn one(api: Arc<impl StoreApi<Vec<u32>, u32>>) {
+            api.dispatch(1u32).await;
+        }
+
+        scheduler.pause();
+        store.dispatch(thunk(one)).await;
+        sleep(Duration::from_millis(10)).await;
+        assert!(store.select(|state: &Vec<u32>| state.is_empty()).await);
+
+        scheduler.resume();
+        let state = store.dispatch_and_wait_for_effect(2u32, |state: &Vec<u32>| state.len() == 2).await;
+        assert!(state.contains(&1));
+    }
+
+    #[tokio::test]
+    async fn ordered_scheduler_runs_in_dispatch_order() {
+        let store = Store::new(log_reducer)
+            .wrap(ScheduledEffectMiddleware::new(Arc::new(OrderedScheduler::new())))
+            .await;
+
+        async fn slow(api: Arc<impl StoreApi<Vec<u32>, u32>>) {
+            sleep(Duration::from_millis(20)).await;
+            api.dispatch(1u32).await;
+        }
+        async fn fast(api: Arc<impl StoreApi<Vec<u32>, u32>>) {
+            api.dispatch(2u32).await;
+        }
+
+        store.dispatch(thunk(slow)).await;
+        let state = store.dispatch_and_wait_for_effect(thunk(fast), |state: &Vec<u32>| state.len() == 2).await;
+
+        // Spawned concurrently the fast effect would have finished first
+        assert_eq!(state, vec![1, 2]);
+    }
+}
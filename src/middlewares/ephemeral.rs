@@ -0,0 +1,176 @@
+use crate::composed_middleware::impl_middleware_bitor;
+use crate::{MiddleWare, StoreApi, Subscriber};
+use async_trait::async_trait;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+
+/// # Ephemeral middleware
+/// A middleware which acts as the wrapped middleware for the first `n` dispatches.
+///
+/// Afterwards the wrapped middleware is bypassed and actions go directly to the underlying store.
+/// This is useful for one-time setup logic, like tracking the first actions of a new user.
+///
+/// ## Usage:
+/// ```
+/// # use async_trait::async_trait;
+/// # use std::sync::Arc;
+/// # use redux_rs::{MiddleWare, Store, StoreApi};
+/// #
+/// # #[derive(Default)]
+/// # struct Counter(i8);
+/// #
+/// # fn counter_reducer(state: Counter, _action: ()) -> Counter {
+/// #     Counter(state.0 + 1)
+/// # }
+/// #
+/// # struct OnboardingTracker;
+/// # #[async_trait]
+/// # impl<Inner> MiddleWare<Counter, (), Inner> for OnboardingTracker
+/// # where
+/// #     Inner: StoreApi<Counter, ()> + Send + Sync,
+/// # {
+/// #     async fn dispatch(&self, action: (), inner: &Arc<Inner>) {
+/// #         inner.dispatch(action).await;
+/// #     }
+/// # }
+/// use redux_rs::middlewares::ephemeral::EphemeralMiddleware;
+///
+/// # #[tokio::main(flavor = "current_thread")]
+/// # async fn main() {
+/// // Track the first 10 actions
+/// let store = Store::new(counter_reducer).wrap(EphemeralMiddleware::new(OnboardingTracker, 10)).await;
+/// # store.dispatch(()).await;
+/// # }
+/// ```
+pub struct EphemeralMiddleware<M> {
+    middleware: M,
+    remaining: AtomicUsize,
+}
+
+impl<M> EphemeralMiddleware<M> {
+    /// Run `middleware` for the first `n` dispatches
+    pub fn new(middleware: M, n: usize) -> Self {
+        EphemeralMiddleware {
+            middleware,
+            remaining: AtomicUsize::new(n),
+        }
+    }
+
+    /// Returns true as long as the wrapped middleware still handles dispatches
+    pub fn is_active(&self) -> bool {
+        self.remaining.load(Ordering::SeqCst) > 0
+    }
+
+    fn claim_dispatch(&self) -> bool {
+        self.remaining
+            .fetch_update(Ordering::SeqCst, Ordering::SeqCst, |remaining| remaining.checked_sub(1))
+            .is_ok()
+    }
+}
+
+impl_middleware_bitor!(EphemeralMiddleware<M>);
+
+#[async_trait]
+impl<M, State, Action, Inner> MiddleWare<State, Action, Inner> for EphemeralMiddleware<M>
+where
+    M: MiddleWare<State, Action, Inner> + Send + Sync,
+    State: Send + 'static,
+    Action: Send + 'static,
+    Inner: StoreApi<State, Action> + Send + Sync,
+{
+    async fn init(&mut self, inner: &Arc<Inner>) {
+        self.middleware.init(inner).await;
+    }
+
+    async fn dispatch(&self, action: Action, inner: &Arc<Inner>) {
+        if self.claim_dispatch() {
+            self.middleware.dispatch(action, inner).await;
+        } else {
+            inner.dispatch(action).await;
+        }
+    }
+
+    async fn on_subscribe(&self, subscriber: &(dyn Subscriber<State> + Sync), inner: &Arc<Inner>) {
+        if self.is_active() {
+            self.middleware.on_subscribe(subscriber, inner).await;
+        }
+    }
+
+    // The wrapped middleware was initialized, so it is torn down even when it's no longer active
+    async fn teardown(&self, inner: &Arc<Inner>) {
+        self.middleware.teardown(inner).await;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Store;
+
+    #[derive(Default)]
+    struct Counter {
+        value: i32,
+    }
+
+    struct Increment;
+
+    fn counter_reducer(state: Counter, _action: Increment) -> Counter {
+        Counter { value: state.value + 1 }
+    }
+
+    struct LogMiddleware {
+        logged: Arc<AtomicUsize>,
+        torn_down: Arc<AtomicUsize>,
+    }
+
+    #[async_trait]
+    impl<Inner> MiddleWare<Counter, Increment, Inner> for LogMiddleware
+    where
+        Inner: StoreApi<Counter, Increment> + Send + Sync,
+    {
+        async fn dispatch(&self, action: Increment, inner: &Arc<Inner>) {
+            self.logged.fetch_add(1, Ordering::SeqCst);
+            inner.dispatch(action).await;
+        }
+
+        async fn teardown(&self, _inner: &Arc<Inner>) {
+            self.torn_down.fetch_add(1, Ordering::SeqCst);
+        }
+    }
+
+    #[tokio::test]
+    async fn bypassed_after_n_dispatches() {
+        let logged = Arc::new(AtomicUsize::new(0));
+        let logger = LogMiddleware {
+            logged: logged.clone(),
+            torn_down: Arc::new(AtomicUsize::new(0)),
+        };
+
+        let store = Store::new(counter_reducer).wrap(EphemeralMiddleware::new(logger, 5)).await;
+
+        for _ in 0..8 {
+            store.dispatch(Increment).await;
+        }
+
+        assert_eq!(logged.load(Ordering::SeqCst), 5);
+        assert_eq!(store.select(|state: &Counter| state.value).await, 8);
+    }
+
+    #[tokio::test]
+    async fn teardown_reaches_wrapped_middleware() {
+        let torn_down = Arc::new(AtomicUsize::new(0));
+        let logger = LogMiddleware {
+            logged: Arc::new(AtomicUsize::new(0)),
+            torn_down: torn_down.clone(),
+        };
+
+        let store = Store::new(counter_reducer).wrap(EphemeralMiddleware::new(logger, 1)).await;
+
+        // The wrapped middleware is bypassed by now, but it still has to clean up
+        store.dispatch(Increment).await;
+        store.dispatch(Increment).await;
+
+        store.shutdown().await;
+        assert_eq!(torn_down.load(Ordering::SeqCst), 1);
+    }
+}
@@ -0,0 +1,154 @@
+use crate::composed_middleware::impl_middleware_bitor;
+use crate::{MiddleWare, StoreApi};
+use async_trait::async_trait;
+use std::sync::Arc;
+
+/// # Filter middleware
+/// A middleware which silently drops the actions which don't pass its filter.
+///
+/// Use `FilterMiddleware::allow` to let only the matching actions through, like an allowlist,
+/// or `FilterMiddleware::deny` to drop the matching actions, like a denylist.
+/// Unlike the `BlacklistMiddleware` the filter can look at the data an action carries.
+///
+/// ## Usage:
+/// ```
+/// use redux_rs::{middlewares::filter::FilterMiddleware, Store, StoreApi};
+///
+/// enum Action {
+///     Add(i32),
+/// }
+///
+/// fn reducer(state: i32, action: Action) -> i32 {
+///     match action {
+///         Action::Add(value) => state + value,
+///     }
+/// }
+///
+/// # #[tokio::main(flavor = "current_thread")]
+/// # async fn main() {
+/// let store = Store::new(reducer).wrap(FilterMiddleware::deny(|action: &Action| matches!(action, Action::Add(0)))).await;
+///
+/// // Never reaches the reducer
+/// store.dispatch(Action::Add(0)).await;
+/// # }
+/// ```
+pub struct FilterMiddleware<Action> {
+    filter: fn(&Action) -> bool,
+    // Whether the actions matching the filter are let through or dropped
+    allow_matching: bool,
+}
+
+impl<Action> FilterMiddleware<Action> {
+    /// Only let through the actions for which `allow` returns true
+    pub fn new(allow: fn(&Action) -> bool) -> Self {
+        Self::allow(allow)
+    }
+
+    /// Only let through the actions for which `allow` returns true
+    pub fn allow(allow: fn(&Action) -> bool) -> Self {
+        FilterMiddleware {
+            filter: allow,
+            allow_matching: true,
+        }
+    }
+
+    /// Drop the actions for which `deny` returns true
+    pub fn deny(deny: fn(&Action) -> bool) -> Self {
+        FilterMiddleware {
+            filter: deny,
+            allow_matching: false,
+        }
+    }
+
+    /// Returns true if the action is let through
+    pub fn is_allowed(&self, action: &Action) -> bool {
+        (self.filter)(action) == self.allow_matching
+    }
+}
+
+impl_middleware_bitor!(FilterMiddleware<Action>);
+
+#[async_trait]
+impl<State, Action, Inner> MiddleWare<State, Action, Inner> for FilterMiddleware<Action>
+where
+    State: Send + 'static,
+    Action: Send + 'static,
+    Inner: StoreApi<State, Action> + Send + Sync,
+{
+    async fn dispatch(&self, action: Action, inner: &Arc<Inner>) {
+        if self.is_allowed(&action) {
+            inner.dispatch(action).await;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Store;
+
+    #[derive(Clone, Debug, Default, PartialEq)]
+    struct Counter {
+        value: i32,
+        reduced: u32,
+    }
+
+    #[derive(Clone, Debug, PartialEq)]
+    enum CounterAction {
+        Increment,
+        Decrement,
+    }
+
+    fn counter_reducer(state: Counter, action: CounterAction) -> Counter {
+        let value = match action {
+            CounterAction::Increment => state.value + 1,
+            CounterAction::Decrement => state.value - 1,
+        };
+
+        Counter {
+            value,
+            reduced: state.reduced + 1,
+        }
+    }
+
+    async fn dispatch_all<S: StoreApi<Counter, CounterAction>>(store: &S) {
+        store.dispatch(CounterAction::Increment).await;
+        store.dispatch(CounterAction::Decrement).await;
+        store.dispatch(CounterAction::Increment).await;
+        store.dispatch(CounterAction::Decrement).await;
+    }
+
+    #[tokio::test]
+    async fn allow_only_increment() {
+        let store = Store::new(counter_reducer)
+            .wrap(FilterMiddleware::allow(|action| matches!(action, CounterAction::Increment)))
+            .await;
+
+        dispatch_all(&store).await;
+
+        assert_eq!(store.state_cloned().await, Counter { value: 2, reduced: 2 });
+    }
+
+    #[tokio::test]
+    async fn deny_decrement() {
+        let store = Store::new(counter_reducer)
+            .wrap(FilterMiddleware::deny(|action| matches!(action, CounterAction::Decrement)))
+            .await;
+
+        dispatch_all(&store).await;
+
+        assert_eq!(store.state_cloned().await, Counter { value: 2, reduced: 2 });
+    }
+
+    #[tokio::test]
+    async fn filter_in_isolation() {
+        use crate::MiddlewareTestBuilder;
+
+        let mut test = MiddlewareTestBuilder::<_, Counter, _>::new(FilterMiddleware::allow(|action| matches!(action, CounterAction::Increment)));
+
+        test.dispatch(CounterAction::Decrement).await.assert_action_dropped();
+        test.dispatch(CounterAction::Increment)
+            .await
+            .assert_inner_dispatched(vec![CounterAction::Increment]);
+    }
+}
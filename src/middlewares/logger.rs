@@ -1,8 +1,17 @@
+#[cfg(feature = "middleware_logger")]
+use crate::composed_middleware::impl_middleware_bitor;
 use crate::{MiddleWare, StoreApi};
 use async_trait::async_trait;
+#[cfg(feature = "middleware_logger")]
 use log::{log, Level};
+#[cfg(feature = "middleware_logger_json")]
+use serde::Serialize;
 use std::fmt::Debug;
+#[cfg(feature = "middleware_tracing")]
+use std::marker::PhantomData;
 use std::sync::Arc;
+#[cfg(feature = "middleware_logger_json")]
+use std::time::{SystemTime, UNIX_EPOCH};
 
 /// A middleware which logs every single action that has been dispatched to the store
 /// We're using the `log` crate to achieve the logging, the log level can be set
@@ -31,20 +40,128 @@ use std::sync::Arc;
 /// let store = Store::new(reducer).wrap(logger_middleware).await;
 /// # }
 /// ```
-pub struct LoggerMiddleware {
+///
+/// The logger can be chained with other middlewares using `|`, see `ComposedMiddleware`:
+/// ```
+/// # #[derive(Default)]
+/// # struct EmptyStore;
+/// #
+/// # #[derive(Debug)]
+/// # struct LogableAction(&'static str);
+/// #
+/// # fn reducer(store: EmptyStore, _action: LogableAction) -> EmptyStore {
+/// #     store
+/// # }
+/// # use log::Level;
+/// # use redux_rs::{middlewares::logger::LoggerMiddleware, Store};
+/// # async fn async_test() {
+/// let middleware = LoggerMiddleware::new(Level::Info) | LoggerMiddleware::new(Level::Debug);
+/// let store = Store::new(reducer).wrap(middleware).await;
+/// # }
+/// ```
+///
+/// With the `middleware_logger_json` feature the actions can be logged as structured JSON instead,
+/// see `LoggerMiddleware::json_formatter` and `LoggerMiddleware::with_custom_formatter`.
+#[cfg(feature = "middleware_logger")]
+pub struct LoggerMiddleware<Format = DebugFormat> {
     log_level: Level,
+    // Only the JSON format has settings, the debug format is a marker
+    #[cfg_attr(not(feature = "middleware_logger_json"), allow(dead_code))]
+    format: Format,
+}
+
+/// Default format of the `LoggerMiddleware`, actions are logged as `Action: {:?}`
+#[cfg(feature = "middleware_logger")]
+pub struct DebugFormat;
+
+/// JSON format of the `LoggerMiddleware`, see `LoggerMiddleware::json_formatter`
+#[cfg(feature = "middleware_logger_json")]
+pub struct JsonFormat<Action> {
+    format_action: fn(&Action) -> serde_json::Value,
 }
 
+#[cfg(feature = "middleware_logger")]
 impl LoggerMiddleware {
     /// Crate a new logger.
     /// LogLevel is the level that the logs will be output with
     pub fn new(log_level: Level) -> Self {
-        LoggerMiddleware { log_level }
+        LoggerMiddleware {
+            log_level,
+            format: DebugFormat,
+        }
+    }
+}
+
+#[cfg(feature = "middleware_logger_json")]
+impl<Action> LoggerMiddleware<JsonFormat<Action>> {
+    /// Create a logger which logs every action as a JSON object:
+    /// `{"action": {...}, "level": "DEBUG", "timestamp": "2024-01-01T12:00:00.000Z"}`.
+    ///
+    /// The action is serialized with serde, actions which fail to serialize are logged as `null`.
+    /// Requires the `middleware_logger_json` feature.
+    pub fn json_formatter(log_level: Level) -> Self
+    where
+        Action: Serialize,
+    {
+        Self::with_custom_formatter(log_level, |action| serde_json::to_value(action).unwrap_or(serde_json::Value::Null))
+    }
+
+    /// Create a JSON logger like `json_formatter`, the value of the `action` key is created by `format_action`.
+    /// Requires the `middleware_logger_json` feature.
+    pub fn with_custom_formatter(log_level: Level, format_action: fn(&Action) -> serde_json::Value) -> Self {
+        LoggerMiddleware {
+            log_level,
+            format: JsonFormat { format_action },
+        }
+    }
+}
+
+#[cfg(feature = "middleware_logger_json")]
+impl<Action> JsonFormat<Action> {
+    fn entry(&self, log_level: Level, action: &Action, timestamp: SystemTime) -> serde_json::Value {
+        serde_json::json!({
+            "level": log_level.as_str(),
+            "action": (self.format_action)(action),
+            "timestamp": rfc3339(timestamp),
+        })
     }
 }
 
+// Format the time in UTC with millisecond precision, like `2024-01-01T12:00:00.000Z`
+#[cfg(feature = "middleware_logger_json")]
+fn rfc3339(time: SystemTime) -> String {
+    let since_epoch = time.duration_since(UNIX_EPOCH).unwrap_or_default();
+    let (days, seconds) = (since_epoch.as_secs() / 86400, since_epoch.as_secs() % 86400);
+
+    // Civil date from the days since the epoch, see http://howardhinnant.github.io/date_algorithms.html#civil_from_days
+    let z = days + 719468;
+    let era = z / 146097;
+    let day_of_era = z - era * 146097;
+    let year_of_era = (day_of_era - day_of_era / 1460 + day_of_era / 36524 - day_of_era / 146096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let mp = (5 * day_of_year + 2) / 153;
+    let day = day_of_year - (153 * mp + 2) / 5 + 1;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+    let year = year_of_era + era * 400 + u64::from(month <= 2);
+
+    format!(
+        "{:04}-{:02}-{:02}T{:02}:{:02}:{:02}.{:03}Z",
+        year,
+        month,
+        day,
+        seconds / 3600,
+        seconds / 60 % 60,
+        seconds % 60,
+        since_epoch.subsec_millis()
+    )
+}
+
+#[cfg(feature = "middleware_logger")]
+impl_middleware_bitor!(LoggerMiddleware<Format>);
+
+#[cfg(feature = "middleware_logger")]
 #[async_trait]
-impl<State, Action, Inner> MiddleWare<State, Action, Inner> for LoggerMiddleware
+impl<State, Action, Inner> MiddleWare<State, Action, Inner> for LoggerMiddleware<DebugFormat>
 where
     State: Send + 'static,
     Action: Debug + Send + 'static,
@@ -58,3 +175,230 @@ where
         inner.dispatch(action).await
     }
 }
+
+#[cfg(feature = "middleware_logger_json")]
+#[async_trait]
+impl<State, Action, Inner> MiddleWare<State, Action, Inner> for LoggerMiddleware<JsonFormat<Action>>
+where
+    State: Send + 'static,
+    Action: Send + 'static,
+    Inner: StoreApi<State, Action> + Send + Sync,
+{
+    async fn dispatch(&self, action: Action, inner: &Arc<Inner>) {
+        // Log the action
+        log!(self.log_level, "{}", self.format.entry(self.log_level, &action, SystemTime::now()));
+
+        // Continue dispatching the action
+        inner.dispatch(action).await
+    }
+}
+
+/// Create a middleware which emits a `tracing` event for every single action that has been dispatched to the store.
+/// The action is recorded in the `action` field of a `DEBUG` event.
+///
+/// This is the `tracing` native alternative to the `LoggerMiddleware`.
+///
+/// ## Usage:
+/// ```
+/// # #[derive(Default)]
+/// # struct EmptyStore;
+/// #
+/// # #[derive(Debug)]
+/// # struct LogableAction(&'static str);
+/// #
+/// # fn reducer(store: EmptyStore, _action: LogableAction) -> EmptyStore {
+/// #     store
+/// # }
+/// use redux_rs::{
+///     middlewares::logger::action_logger_middleware,
+///     Store
+/// };
+/// # async fn async_test() {
+/// let store = Store::new(reducer).wrap(action_logger_middleware()).await;
+/// # }
+/// ```
+#[cfg(feature = "middleware_tracing")]
+pub fn action_logger_middleware<State, Action, Inner>() -> impl MiddleWare<State, Action, Inner> + Send + Sync
+where
+    State: Send + 'static,
+    Action: Debug + Send + 'static,
+    Inner: StoreApi<State, Action> + Send + Sync,
+{
+    TracingLoggerMiddleware { _types: PhantomData }
+}
+
+#[cfg(feature = "middleware_tracing")]
+struct TracingLoggerMiddleware<State, Action> {
+    _types: PhantomData<fn() -> (State, Action)>,
+}
+
+#[cfg(feature = "middleware_tracing")]
+#[async_trait]
+impl<State, Action, Inner> MiddleWare<State, Action, Inner> for TracingLoggerMiddleware<State, Action>
+where
+    State: Send + 'static,
+    Action: Debug + Send + 'static,
+    Inner: StoreApi<State, Action> + Send + Sync,
+{
+    async fn dispatch(&self, action: Action, inner: &Arc<Inner>) {
+        // Trace the action
+        tracing::event!(tracing::Level::DEBUG, action = ?action);
+
+        // Continue dispatching the action
+        inner.dispatch(action).await
+    }
+}
+
+#[cfg(all(test, feature = "middleware_logger"))]
+mod logger_tests {
+    use super::*;
+    use crate::testing::capture_logger::{capture_logs, captured_logs};
+    use crate::MiddlewareTestBuilder;
+
+    #[tokio::test]
+    async fn logs_and_passes_actions() {
+        capture_logs();
+
+        let mut test = MiddlewareTestBuilder::<_, (), &str>::new(LoggerMiddleware::new(Level::Debug));
+        test.dispatch("logged first").await;
+        test.dispatch("logged second")
+            .await
+            .assert_inner_dispatched(vec!["logged first", "logged second"]);
+
+        let logs = captured_logs("redux_rs::middlewares::logger");
+        assert!(logs.contains(&"Action: \"logged first\"".to_string()));
+        assert!(logs.contains(&"Action: \"logged second\"".to_string()));
+    }
+}
+
+#[cfg(all(test, feature = "middleware_logger_json"))]
+mod json_tests {
+    use super::*;
+    use crate::testing::capture_logger::{capture_logs, captured_logs};
+    use crate::MiddlewareTestBuilder;
+    use std::time::Duration;
+
+    #[derive(Default)]
+    struct EmptyStore;
+
+    #[derive(Clone, Debug, PartialEq, Serialize)]
+    enum LogableAction {
+        Login { user: String },
+    }
+
+    #[tokio::test]
+    async fn json_logger_logs_json_entries() {
+        capture_logs();
+
+        let mut test = MiddlewareTestBuilder::<_, EmptyStore, LogableAction>::new(LoggerMiddleware::json_formatter(Level::Info));
+        test.dispatch(LogableAction::Login { user: "json".to_string() })
+            .await
+            .assert_inner_dispatched(vec![LogableAction::Login { user: "json".to_string() }]);
+
+        let logs: Vec<serde_json::Value> = captured_logs("redux_rs::middlewares::logger")
+            .iter()
+            .filter_map(|log| serde_json::from_str(log).ok())
+            .filter(|entry: &serde_json::Value| entry["action"]["Login"]["user"] == "json")
+            .collect();
+
+        assert_eq!(logs.len(), 1);
+        assert_eq!(logs[0]["level"], "INFO");
+        assert!(logs[0]["timestamp"].is_string());
+    }
+
+    #[tokio::test]
+    async fn custom_formatter_formats_action() {
+        fn user(action: &LogableAction) -> serde_json::Value {
+            match action {
+                LogableAction::Login { user } => serde_json::json!({ "user": user }),
+            }
+        }
+
+        let logger = LoggerMiddleware::with_custom_formatter(Level::Debug, user);
+        let action = LogableAction::Login { user: "custom".to_string() };
+        let timestamp = UNIX_EPOCH + Duration::from_millis(951_782_400_123);
+
+        assert_eq!(
+            logger.format.entry(logger.log_level, &action, timestamp),
+            serde_json::json!({
+                "level": "DEBUG",
+                "action": { "user": "custom" },
+                "timestamp": "2000-02-29T00:00:00.123Z",
+            })
+        );
+    }
+
+    #[test]
+    fn formats_timestamps_as_rfc3339() {
+        assert_eq!(rfc3339(UNIX_EPOCH), "1970-01-01T00:00:00.000Z");
+        assert_eq!(rfc3339(UNIX_EPOCH + Duration::from_secs(1_704_110_400)), "2024-01-01T12:00:00.000Z");
+        assert_eq!(rfc3339(UNIX_EPOCH + Duration::from_secs(4_107_542_399)), "2100-02-28T23:59:59.000Z");
+    }
+}
+
+#[cfg(all(test, feature = "middleware_tracing"))]
+mod tests {
+    use super::*;
+    use crate::Store;
+    use std::fmt;
+    use std::sync::Mutex;
+    use tracing::field::{Field, Visit};
+    use tracing::{Event, Subscriber};
+    use tracing_subscriber::layer::{Context, Layer, SubscriberExt};
+    use tracing_subscriber::registry::Registry;
+
+    #[derive(Default)]
+    struct EmptyStore;
+
+    #[derive(Debug)]
+    enum LogableAction {
+        First,
+        Second,
+    }
+
+    fn nop_reducer(store: EmptyStore, _action: LogableAction) -> EmptyStore {
+        store
+    }
+
+    // Layer which records the level and "action" field of every event
+    struct RecordingLayer {
+        events: Arc<Mutex<Vec<(tracing::Level, String)>>>,
+    }
+
+    struct ActionVisitor(Option<String>);
+    impl Visit for ActionVisitor {
+        fn record_debug(&mut self, field: &Field, value: &dyn fmt::Debug) {
+            if field.name() == "action" {
+                self.0 = Some(format!("{:?}", value));
+            }
+        }
+    }
+
+    impl<S: Subscriber> Layer<S> for RecordingLayer {
+        fn on_event(&self, event: &Event<'_>, _ctx: Context<'_, S>) {
+            let mut visitor = ActionVisitor(None);
+            event.record(&mut visitor);
+
+            if let Some(action) = visitor.0 {
+                self.events.lock().unwrap().push((*event.metadata().level(), action));
+            }
+        }
+    }
+
+    #[tokio::test]
+    async fn action_logger_emits_events() {
+        let events = Arc::new(Mutex::new(Vec::new()));
+        let subscriber = Registry::default().with(RecordingLayer { events: events.clone() });
+        let _guard = tracing::subscriber::set_default(subscriber);
+
+        let store = Store::new(nop_reducer).wrap(action_logger_middleware()).await;
+        store.dispatch(LogableAction::First).await;
+        store.dispatch(LogableAction::Second).await;
+
+        let events = events.lock().unwrap();
+        assert_eq!(
+            *events,
+            vec![(tracing::Level::DEBUG, "First".to_string()), (tracing::Level::DEBUG, "Second".to_string())]
+        );
+    }
+}
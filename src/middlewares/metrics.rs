@@ -0,0 +1,137 @@
+use crate::composed_middleware::impl_middleware_bitor;
+use crate::{MiddleWare, StoreApi, TaggedAction};
+use async_trait::async_trait;
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+/// Dispatch measurements of all actions with the same tag
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct TagMetrics {
+    /// Number of dispatched actions
+    pub count: u64,
+    /// Total time spent dispatching the actions, this includes the inner middlewares and the reducer
+    pub total: Duration,
+}
+
+/// # Metrics middleware
+/// A middleware which measures the dispatch time of tagged actions, grouped by tag.
+///
+/// Actions are dispatched with `StoreApiExt::dispatch_tagged`, the tag is used as the metric name.
+/// Every clone of the middleware refers to the same measurements, so keep a clone to read them.
+///
+/// ## Usage:
+/// ```
+/// use redux_rs::{middlewares::metrics::MetricsMiddleware, Store, StoreApiExt, StripMetaMiddleware};
+///
+/// #[derive(Default)]
+/// struct Counter(i32);
+///
+/// fn counter_reducer(state: Counter, add: i32) -> Counter {
+///     Counter(state.0 + add)
+/// }
+///
+/// # #[tokio::main(flavor = "current_thread")]
+/// # async fn main() {
+/// let metrics = MetricsMiddleware::new();
+/// let store = Store::new(counter_reducer)
+///     .wrap(StripMetaMiddleware::new())
+///     .await
+///     .wrap(metrics.clone())
+///     .await;
+///
+/// store.dispatch_tagged(1, "add").await;
+/// assert_eq!(metrics.metrics()["add"].count, 1);
+/// # }
+/// ```
+pub struct MetricsMiddleware {
+    metrics: Arc<Mutex<HashMap<&'static str, TagMetrics>>>,
+}
+
+impl MetricsMiddleware {
+    /// Create the middleware without measurements, clones share the measurements
+    pub fn new() -> Self {
+        MetricsMiddleware { metrics: Default::default() }
+    }
+
+    /// Returns a copy of the measurements, grouped by tag
+    pub fn metrics(&self) -> HashMap<&'static str, TagMetrics> {
+        self.metrics.lock().unwrap().clone()
+    }
+}
+
+impl Clone for MetricsMiddleware {
+    fn clone(&self) -> Self {
+        MetricsMiddleware { metrics: self.metrics.clone() }
+    }
+}
+
+impl Default for MetricsMiddleware {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl_middleware_bitor!(MetricsMiddleware);
+
+#[async_trait]
+impl<State, Action, Inner> MiddleWare<State, TaggedAction<Action>, Inner> for MetricsMiddleware
+where
+    State: Send + 'static,
+    Action: Send + 'static,
+    Inner: StoreApi<State, TaggedAction<Action>> + Send + Sync,
+{
+    async fn dispatch(&self, action: TaggedAction<Action>, inner: &Arc<Inner>) {
+        let tag = action.meta;
+
+        let start = Instant::now();
+        inner.dispatch(action).await;
+        let elapsed = start.elapsed();
+
+        let mut metrics = self.metrics.lock().unwrap();
+        let tag_metrics = metrics.entry(tag).or_default();
+        tag_metrics.count += 1;
+        tag_metrics.total += elapsed;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Store, StoreApiExt, StripMetaMiddleware};
+
+    #[derive(Default)]
+    struct Counter {
+        value: i32,
+    }
+
+    enum CounterAction {
+        Increment,
+        Decrement,
+    }
+
+    fn counter_reducer(state: Counter, action: CounterAction) -> Counter {
+        match action {
+            CounterAction::Increment => Counter { value: state.value + 1 },
+            CounterAction::Decrement => Counter { value: state.value - 1 },
+        }
+    }
+
+    #[tokio::test]
+    async fn timings_grouped_by_tag() {
+        let metrics = MetricsMiddleware::new();
+        let store = Store::new(counter_reducer).wrap(StripMetaMiddleware::new()).await.wrap(metrics.clone()).await;
+
+        store.dispatch_tagged(CounterAction::Increment, "increment").await;
+        store.dispatch_tagged(CounterAction::Increment, "increment").await;
+        store.dispatch_tagged(CounterAction::Decrement, "decrement").await;
+
+        let metrics = metrics.metrics();
+        assert_eq!(metrics.len(), 2);
+        assert_eq!(metrics["increment"].count, 2);
+        assert_eq!(metrics["decrement"].count, 1);
+        assert!(metrics["increment"].total > Duration::ZERO);
+
+        assert_eq!(store.select(|state: &Counter| state.value).await, 1);
+    }
+}
@@ -1,5 +1,35 @@
-#[cfg(feature = "middleware_logger")]
+#[cfg(feature = "middleware_action_bridge")]
+pub mod action_bridge;
+
+#[cfg(feature = "middleware_blacklist")]
+pub mod blacklist;
+
+#[cfg(feature = "middleware_thunk")]
+pub mod effect_scheduler;
+
+#[cfg(feature = "middleware_ephemeral")]
+pub mod ephemeral;
+
+#[cfg(feature = "middleware_filter")]
+pub mod filter;
+
+#[cfg(any(feature = "middleware_logger", feature = "middleware_tracing"))]
 pub mod logger;
 
+#[cfg(feature = "middleware_metrics")]
+pub mod metrics;
+
+#[cfg(feature = "middleware_priority")]
+pub mod priority;
+
+#[cfg(feature = "middleware_relay")]
+pub mod relay;
+
+#[cfg(feature = "middleware_saga")]
+pub mod saga;
+
+#[cfg(feature = "middleware_scheduler")]
+pub mod scheduler;
+
 #[cfg(feature = "middleware_thunk")]
 pub mod thunk;
@@ -0,0 +1,270 @@
+use crate::composed_middleware::impl_middleware_bitor;
+use crate::dyn_middleware::Next;
+use crate::{DynMiddleWare, MiddleWare, StoreApi};
+use async_trait::async_trait;
+use std::sync::{Arc, Mutex};
+
+/// Identifies a middleware in a `PriorityMiddlewareStack`
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct MiddlewareId(u64);
+
+/// # Priority middleware stack
+/// A middleware which runs a list of middlewares, ordered by priority at runtime.
+///
+/// Middlewares with a higher priority run first, middlewares with an equal priority run in the order they were added.
+/// Middlewares can be added and priorities can be changed after the stack has been wrapped around a store,
+/// every clone of the stack refers to the same list of middlewares.
+///
+/// ## Usage:
+/// ```
+/// # use async_trait::async_trait;
+/// # use redux_rs::{DynMiddleWare, Next};
+/// # #[derive(Default)]
+/// # struct EmptyStore;
+/// #
+/// # fn reducer(store: EmptyStore, _action: ()) -> EmptyStore {
+/// #     store
+/// # }
+/// #
+/// # struct NopMiddleware;
+/// # #[async_trait]
+/// # impl DynMiddleWare<EmptyStore, ()> for NopMiddleware {
+/// #     async fn dispatch(&self, action: (), next: Next<'_, EmptyStore, ()>) {
+/// #         next.dispatch(action).await;
+/// #     }
+/// # }
+/// use redux_rs::{middlewares::priority::PriorityMiddlewareStack, Store};
+/// # async fn async_test() {
+/// let stack = PriorityMiddlewareStack::new();
+/// let store = Store::new(reducer).wrap(stack.clone()).await;
+///
+/// // Plugins can add their middleware at any time
+/// let id = stack.add(Box::new(NopMiddleware), 10);
+/// stack.set_priority(id, -10);
+/// # }
+/// ```
+pub struct PriorityMiddlewareStack<State, Action> {
+    entries: Arc<Mutex<Entries<State, Action>>>,
+}
+
+struct Entries<State, Action> {
+    next_id: u64,
+    // Kept sorted from the highest to the lowest priority
+    entries: Vec<Entry<State, Action>>,
+}
+
+struct Entry<State, Action> {
+    id: MiddlewareId,
+    priority: i32,
+    middleware: Arc<dyn DynMiddleWare<State, Action>>,
+}
+
+impl<State, Action> PriorityMiddlewareStack<State, Action>
+where
+    Action: Send + 'static,
+{
+    /// Create an empty stack, actions pass through unmodified until a middleware is added
+    pub fn new() -> Self {
+        PriorityMiddlewareStack {
+            entries: Arc::new(Mutex::new(Entries {
+                next_id: 0,
+                entries: Vec::new(),
+            })),
+        }
+    }
+
+    /// Add a middleware with the given priority, the returned id can be used to change the priority later on
+    pub fn add(&self, middleware: Box<dyn DynMiddleWare<State, Action>>, priority: i32) -> MiddlewareId {
+        let mut entries = self.entries.lock().unwrap();
+
+        let id = MiddlewareId(entries.next_id);
+        entries.next_id += 1;
+
+        entries.entries.push(Entry {
+            id,
+            priority,
+            middleware: Arc::from(middleware),
+        });
+        entries.sort();
+
+        id
+    }
+
+    /// Change the priority of a middleware, returns false if there's no middleware with the given id
+    pub fn set_priority(&self, id: MiddlewareId, priority: i32) -> bool {
+        let mut entries = self.entries.lock().unwrap();
+
+        match entries.entries.iter_mut().find(|entry| entry.id == id) {
+            Some(entry) => {
+                entry.priority = priority;
+                entries.sort();
+                true
+            }
+            None => false,
+        }
+    }
+
+    fn middlewares(&self) -> Vec<Arc<dyn DynMiddleWare<State, Action>>> {
+        let entries = self.entries.lock().unwrap();
+        entries.entries.iter().map(|entry| entry.middleware.clone()).collect()
+    }
+}
+
+impl<State, Action> Entries<State, Action> {
+    fn sort(&mut self) {
+        // Ids are handed out in insertion order, they break ties between equal priorities
+        self.entries.sort_by_key(|entry| (std::cmp::Reverse(entry.priority), entry.id.0));
+    }
+}
+
+impl<State, Action> Clone for PriorityMiddlewareStack<State, Action> {
+    fn clone(&self) -> Self {
+        PriorityMiddlewareStack { entries: self.entries.clone() }
+    }
+}
+
+impl<State, Action> Default for PriorityMiddlewareStack<State, Action>
+where
+    Action: Send + 'static,
+{
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl_middleware_bitor!(PriorityMiddlewareStack<State, Action>);
+
+#[async_trait]
+impl<State, Action, Inner> MiddleWare<State, Action, Inner> for PriorityMiddlewareStack<State, Action>
+where
+    State: Send + 'static,
+    Action: Send + 'static,
+    Inner: StoreApi<State, Action> + Send + Sync,
+{
+    async fn dispatch(&self, action: Action, inner: &Arc<Inner>) {
+        // Take a snapshot, the lock must not be held while dispatching
+        let middlewares = self.middlewares();
+        let dispatch_inner = |action: Action| inner.dispatch(action);
+
+        Next::new(&middlewares, &dispatch_inner).dispatch(action).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Store;
+
+    #[derive(Default)]
+    struct Counter {
+        value: i32,
+    }
+
+    struct Increment;
+
+    fn counter_reducer(state: Counter, _action: Increment) -> Counter {
+        Counter { value: state.value + 1 }
+    }
+
+    struct RecordMiddleware {
+        name: &'static str,
+        calls: Arc<Mutex<Vec<&'static str>>>,
+    }
+
+    #[async_trait]
+    impl DynMiddleWare<Counter, Increment> for RecordMiddleware {
+        async fn dispatch(&self, action: Increment, next: Next<'_, Counter, Increment>) {
+            self.calls.lock().unwrap().push(self.name);
+            next.dispatch(action).await;
+        }
+    }
+
+    #[tokio::test]
+    async fn priority_order() {
+        let calls = Arc::new(Mutex::new(Vec::new()));
+        let record = |name| Box::new(RecordMiddleware { name, calls: calls.clone() });
+
+        let stack = PriorityMiddlewareStack::new();
+        let store = Store::new(counter_reducer).wrap(stack.clone()).await;
+
+        let medium = stack.add(record("medium"), 5);
+        stack.add(record("high"), 10);
+        stack.add(record("low"), 1);
+
+        store.dispatch(Increment).await;
+        assert_eq!(*calls.lock().unwrap(), vec!["high", "medium", "low"]);
+
+        // Move the medium middleware to the front
+        calls.lock().unwrap().clear();
+        assert!(stack.set_priority(medium, 20));
+
+        store.dispatch(Increment).await;
+        assert_eq!(*calls.lock().unwrap(), vec!["medium", "high", "low"]);
+
+        // Every action reached the store
+        assert_eq!(store.select(|state: &Counter| state.value).await, 2);
+    }
+
+    #[tokio::test]
+    async fn equal_priorities_keep_insertion_order() {
+        let calls = Arc::new(Mutex::new(Vec::new()));
+        let record = |name| Box::new(RecordMiddleware { name, calls: calls.clone() });
+
+        let stack = PriorityMiddlewareStack::new();
+        let store = Store::new(counter_reducer).wrap(stack.clone()).await;
+
+        stack.add(record("first"), 0);
+        stack.add(record("high"), 3);
+        stack.add(record("second"), 0);
+        let third = stack.add(record("third"), 7);
+
+        // Changing the priority doesn't affect the insertion order
+        assert!(stack.set_priority(third, 0));
+
+        store.dispatch(Increment).await;
+        assert_eq!(*calls.lock().unwrap(), vec!["high", "first", "second", "third"]);
+    }
+
+    #[test]
+    fn set_priority_unknown_id() {
+        let stack = PriorityMiddlewareStack::<Counter, Increment>::new();
+        let id = stack.add(
+            Box::new(RecordMiddleware {
+                name: "only",
+                calls: Default::default(),
+            }),
+            0,
+        );
+
+        assert!(stack.set_priority(id, 1));
+        assert!(!stack.set_priority(MiddlewareId(id.0 + 1), 1));
+    }
+
+    #[tokio::test]
+    async fn stacks_compose_with_bitor() {
+        let calls = Arc::new(Mutex::new(Vec::new()));
+
+        let outer = PriorityMiddlewareStack::new();
+        outer.add(
+            Box::new(RecordMiddleware {
+                name: "outer",
+                calls: calls.clone(),
+            }),
+            0,
+        );
+        let inner = PriorityMiddlewareStack::new();
+        inner.add(
+            Box::new(RecordMiddleware {
+                name: "inner",
+                calls: calls.clone(),
+            }),
+            0,
+        );
+
+        let store = Store::new(counter_reducer).wrap(outer | inner).await;
+        store.dispatch(Increment).await;
+
+        assert_eq!(*calls.lock().unwrap(), vec!["outer", "inner"]);
+        assert_eq!(store.select(|state: &Counter| state.value).await, 1);
+    }
+}
@@ -0,0 +1,146 @@
+use crate::composed_middleware::impl_middleware_bitor;
+use crate::{MiddleWare, StoreApi};
+use async_trait::async_trait;
+use std::marker::PhantomData;
+use std::sync::Arc;
+
+/// # Relay middleware
+/// A middleware which forwards actions to another store.
+///
+/// Every action is first dispatched to the wrapped store.
+/// Afterwards it's mapped to an action of the target store, if the mapping returns `Some` the mapped action is dispatched to the target.
+/// This creates a directed message bus between stores.
+///
+/// The mapping function receives a reference, this way the action can still be dispatched to the wrapped store without requiring `Clone`.
+///
+/// ## Usage:
+/// ```
+/// use std::sync::Arc;
+/// use redux_rs::{middlewares::relay::RelayMiddleware, Store, StoreApi};
+///
+/// #[derive(Default)]
+/// struct Counter(i32);
+///
+/// fn counter_reducer(state: Counter, add: i32) -> Counter {
+///     Counter(state.0 + add)
+/// }
+///
+/// # #[tokio::main(flavor = "current_thread")]
+/// # async fn main() {
+/// let totals = Arc::new(Store::new(counter_reducer));
+///
+/// // Relay positive values to the totals store
+/// let relay = RelayMiddleware::new(totals.clone(), |add: &i32| if *add > 0 { Some(*add) } else { None });
+/// let store = Store::new(counter_reducer).wrap(relay).await;
+///
+/// store.dispatch(5).await;
+/// store.dispatch(-2).await;
+///
+/// assert_eq!(store.select(|state: &Counter| state.0).await, 3);
+/// assert_eq!(totals.select(|state: &Counter| state.0).await, 5);
+/// # }
+/// ```
+pub struct RelayMiddleware<Target, TargetState, Action, TargetAction> {
+    target: Arc<Target>,
+    map: fn(&Action) -> Option<TargetAction>,
+
+    _types: PhantomData<fn() -> TargetState>,
+}
+
+impl<Target, TargetState, Action, TargetAction> RelayMiddleware<Target, TargetState, Action, TargetAction>
+where
+    Target: StoreApi<TargetState, TargetAction> + Send + Sync,
+    TargetState: Send + 'static,
+    TargetAction: Send + 'static,
+{
+    /// Relay every action for which `map` returns `Some` to `target`
+    pub fn new(target: Arc<Target>, map: fn(&Action) -> Option<TargetAction>) -> Self {
+        RelayMiddleware {
+            target,
+            map,
+            _types: Default::default(),
+        }
+    }
+}
+
+impl_middleware_bitor!(RelayMiddleware<Target, TargetState, Action, TargetAction>);
+
+#[async_trait]
+impl<State, Action, Inner, Target, TargetState, TargetAction> MiddleWare<State, Action, Inner> for RelayMiddleware<Target, TargetState, Action, TargetAction>
+where
+    State: Send + 'static,
+    Action: Send + 'static,
+    Inner: StoreApi<State, Action> + Send + Sync,
+    Target: StoreApi<TargetState, TargetAction> + Send + Sync,
+    TargetState: Send + 'static,
+    TargetAction: Send + 'static,
+{
+    async fn dispatch(&self, action: Action, inner: &Arc<Inner>) {
+        let relayed = (self.map)(&action);
+
+        // Dispatch to our own store first
+        inner.dispatch(action).await;
+
+        if let Some(relayed) = relayed {
+            self.target.dispatch(relayed).await;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Store;
+
+    #[derive(Default)]
+    struct Counter {
+        value: i32,
+    }
+
+    enum CounterAction {
+        Increment,
+        Decrement,
+    }
+
+    fn counter_reducer(state: Counter, action: CounterAction) -> Counter {
+        match action {
+            CounterAction::Increment => Counter { value: state.value + 1 },
+            CounterAction::Decrement => Counter { value: state.value - 1 },
+        }
+    }
+
+    #[derive(Default)]
+    struct Audit {
+        entries: Vec<String>,
+    }
+
+    struct AuditAction(String);
+
+    fn audit_reducer(mut state: Audit, action: AuditAction) -> Audit {
+        state.entries.push(action.0);
+        state
+    }
+
+    fn audit_decrements(action: &CounterAction) -> Option<AuditAction> {
+        match action {
+            CounterAction::Increment => None,
+            CounterAction::Decrement => Some(AuditAction("decremented".to_string())),
+        }
+    }
+
+    #[tokio::test]
+    async fn relay_to_other_store() {
+        let store_b = Arc::new(Store::new(audit_reducer));
+        let store_a = Store::new(counter_reducer).wrap(RelayMiddleware::new(store_b.clone(), audit_decrements)).await;
+
+        store_a.dispatch(CounterAction::Increment).await;
+        store_a.dispatch(CounterAction::Decrement).await;
+        store_a.dispatch(CounterAction::Decrement).await;
+
+        assert_eq!(store_a.select(|state: &Counter| state.value).await, -1);
+        assert_eq!(
+            store_b.select(|state: &Audit| state.entries.clone()).await,
+            vec!["decremented".to_string(), "decremented".to_string()]
+        );
+    }
+}
@@ -0,0 +1,335 @@
+use crate::composed_middleware::impl_middleware_bitor;
+use crate::{MiddleWare, PipelineStore, Selector, StoreApi};
+use async_trait::async_trait;
+use std::sync::{Arc, Mutex};
+use tokio::sync::mpsc::{unbounded_channel, UnboundedReceiver, UnboundedSender};
+use tokio::task::JoinHandle;
+
+/// A long-lived workflow which reacts to the actions dispatched to the store, see `SagaMiddleware`.
+#[async_trait]
+pub trait Saga<State, Action>: Send + Sync {
+    /// Run the saga, the saga is done when this method returns
+    async fn run(&self, ctx: SagaContext<State, Action>);
+}
+
+/// The connection between a running saga and the store
+pub struct SagaContext<State, Action> {
+    inbox: UnboundedReceiver<Action>,
+    inboxes: Arc<Inboxes<Action>>,
+    store: Arc<PipelineStore<State, Action>>,
+}
+
+impl<State, Action> SagaContext<State, Action>
+where
+    State: Send + 'static,
+    Action: Clone + Send + 'static,
+{
+    /// Wait for the next dispatched action which matches the pattern, other actions are skipped.
+    /// Returns None when the middleware is dropped.
+    pub async fn take_action<P>(&mut self, pattern: P) -> Option<Action>
+    where
+        P: Fn(&Action) -> bool,
+    {
+        while let Some(action) = self.inbox.recv().await {
+            if pattern(&action) {
+                return Some(action);
+            }
+        }
+
+        None
+    }
+
+    /// Dispatch an action to the store, the running sagas see it like any other dispatched action
+    pub async fn put_action(&self, action: Action) {
+        self.store.dispatch(action.clone()).await;
+        self.inboxes.deliver(action);
+    }
+
+    /// Select a part of the state
+    pub async fn select<S, Result>(&self, selector: S) -> Result
+    where
+        S: Selector<State, Result = Result> + Send + 'static,
+        Result: Send + 'static,
+    {
+        self.store.select(selector).await
+    }
+}
+
+// Every running saga has an inbox which receives a copy of the dispatched actions
+struct Inboxes<Action> {
+    senders: Mutex<Vec<UnboundedSender<Action>>>,
+}
+
+impl<Action> Inboxes<Action>
+where
+    Action: Clone,
+{
+    fn open(&self) -> UnboundedReceiver<Action> {
+        let (tx, rx) = unbounded_channel();
+        self.senders.lock().unwrap().push(tx);
+        rx
+    }
+
+    // Inboxes of finished or cancelled sagas are closed, they are removed here
+    fn deliver(&self, action: Action) {
+        self.senders.lock().unwrap().retain(|sender| sender.send(action.clone()).is_ok());
+    }
+}
+
+struct RegisteredSaga<State, Action> {
+    saga: Arc<dyn Saga<State, Action>>,
+    restart_on: Option<fn(&Action) -> bool>,
+    running: Mutex<Option<JoinHandle<()>>>,
+}
+
+/// # Saga middleware
+/// Runs sagas, long-lived async workflows which wait for actions and dispatch actions of their own.
+///
+/// Unlike thunks, which run once per dispatch, sagas keep running across dispatches.
+/// They are started when the middleware is wrapped around a store and are cancelled when the middleware is dropped.
+/// A saga registered with `restart_saga_on` is cancelled and started again every time a matching action is dispatched.
+///
+/// Actions reach the sagas after they are dispatched to the inner store, actions put by a saga skip the middlewares outside this one.
+///
+/// ## Usage:
+/// ```
+/// use async_trait::async_trait;
+/// use redux_rs::{Store, StoreApi};
+/// use redux_rs::middlewares::saga::{Saga, SagaContext, SagaMiddleware};
+///
+/// #[derive(Clone, Debug, PartialEq)]
+/// enum Action {
+///     Ping,
+///     Pong,
+/// }
+///
+/// fn reducer(state: Vec<Action>, action: Action) -> Vec<Action> {
+///     let mut state = state;
+///     state.push(action);
+///     state
+/// }
+///
+/// struct PingPong;
+/// #[async_trait]
+/// impl Saga<Vec<Action>, Action> for PingPong {
+///     async fn run(&self, mut ctx: SagaContext<Vec<Action>, Action>) {
+///         while ctx.take_action(|action| *action == Action::Ping).await.is_some() {
+///             ctx.put_action(Action::Pong).await;
+///         }
+///     }
+/// }
+///
+/// # #[tokio::main(flavor = "current_thread")]
+/// # async fn main() {
+/// let store = Store::new(reducer).wrap(SagaMiddleware::new().run_saga(PingPong)).await;
+/// store.dispatch(Action::Ping).await;
+/// # }
+/// ```
+pub struct SagaMiddleware<State, Action> {
+    sagas: Vec<RegisteredSaga<State, Action>>,
+    inboxes: Arc<Inboxes<Action>>,
+    store: Option<Arc<PipelineStore<State, Action>>>,
+}
+
+impl<State, Action> SagaMiddleware<State, Action>
+where
+    State: Send + 'static,
+    Action: Clone + Send + 'static,
+{
+    /// Create the middleware without sagas, add them with `run_saga`
+    pub fn new() -> Self {
+        SagaMiddleware {
+            sagas: Vec::new(),
+            inboxes: Arc::new(Inboxes {
+                senders: Mutex::new(Vec::new()),
+            }),
+            store: None,
+        }
+    }
+
+    /// Run the saga once the middleware is wrapped around a store
+    pub fn run_saga<S>(self, saga: S) -> Self
+    where
+        S: Saga<State, Action> + 'static,
+    {
+        self.register(saga, None)
+    }
+
+    /// Run the saga once the middleware is wrapped around a store.
+    /// The running saga is cancelled and started again every time an action matching `restart_on` is dispatched.
+    pub fn restart_saga_on<S>(self, saga: S, restart_on: fn(&Action) -> bool) -> Self
+    where
+        S: Saga<State, Action> + 'static,
+    {
+        self.register(saga, Some(restart_on))
+    }
+
+    fn register<S>(mut self, saga: S, restart_on: Option<fn(&Action) -> bool>) -> Self
+    where
+        S: Saga<State, Action> + 'static,
+    {
+        self.sagas.push(RegisteredSaga {
+            saga: Arc::new(saga),
+            restart_on,
+            running: Mutex::new(None),
+        });
+        self
+    }
+
+    // Cancels the running instance of the saga, if any, and starts a new one
+    fn start(&self, registered: &RegisteredSaga<State, Action>) {
+        let store = match &self.store {
+            Some(store) => store.clone(),
+            None => return,
+        };
+
+        let ctx = SagaContext {
+            inbox: self.inboxes.open(),
+            inboxes: self.inboxes.clone(),
+            store,
+        };
+        let saga = registered.saga.clone();
+        let handle = tokio::spawn(async move { saga.run(ctx).await });
+
+        if let Some(previous) = registered.running.lock().unwrap().replace(handle) {
+            previous.abort();
+        }
+    }
+}
+
+impl<State, Action> Default for SagaMiddleware<State, Action>
+where
+    State: Send + 'static,
+    Action: Clone + Send + 'static,
+{
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<State, Action> Drop for SagaMiddleware<State, Action> {
+    fn drop(&mut self) {
+        for registered in &self.sagas {
+            if let Some(running) = registered.running.lock().unwrap().take() {
+                running.abort();
+            }
+        }
+    }
+}
+
+impl_middleware_bitor!(SagaMiddleware<State, Action>);
+
+#[async_trait]
+impl<State, Action, Inner> MiddleWare<State, Action, Inner> for SagaMiddleware<State, Action>
+where
+    State: Send + 'static,
+    Action: Clone + Send + Sync + 'static,
+    Inner: StoreApi<State, Action> + Send + Sync + 'static,
+{
+    async fn init(&mut self, inner: &Arc<Inner>) {
+        self.store = Some(Arc::new(PipelineStore::from_shared(inner.clone())));
+
+        for registered in &self.sagas {
+            self.start(registered);
+        }
+    }
+
+    async fn dispatch(&self, action: Action, inner: &Arc<Inner>) {
+        inner.dispatch(action.clone()).await;
+
+        // The restarted sagas only see the actions after the one which restarted them
+        self.inboxes.deliver(action.clone());
+        for registered in &self.sagas {
+            if registered.restart_on.is_some_and(|restart_on| restart_on(&action)) {
+                self.start(registered);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Store;
+    use std::time::Duration;
+    use tokio::time::{sleep, timeout};
+
+    #[derive(Clone, Debug, Default, PartialEq)]
+    struct Session {
+        activity: u32,
+        expired: bool,
+        logins: u32,
+    }
+
+    #[derive(Clone, Debug, PartialEq)]
+    enum SessionAction {
+        Login,
+        Activity,
+        SessionExpired,
+    }
+
+    fn session_reducer(state: Session, action: SessionAction) -> Session {
+        match action {
+            SessionAction::Login => Session {
+                logins: state.logins + 1,
+                expired: false,
+                ..state
+            },
+            SessionAction::Activity => Session {
+                activity: state.activity + 1,
+                ..state
+            },
+            SessionAction::SessionExpired => Session { expired: true, ..state },
+        }
+    }
+
+    // Expires the session when there's no activity for 5 seconds
+    struct SessionTimeout;
+
+    #[async_trait]
+    impl Saga<Session, SessionAction> for SessionTimeout {
+        async fn run(&self, mut ctx: SagaContext<Session, SessionAction>) {
+            loop {
+                let activity = ctx.take_action(|action| *action == SessionAction::Activity);
+                if let Ok(None) | Err(_) = timeout(Duration::from_secs(5), activity).await {
+                    ctx.put_action(SessionAction::SessionExpired).await;
+                    return;
+                }
+            }
+        }
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn session_expires_without_activity() {
+        let store = Store::new(session_reducer).wrap(SagaMiddleware::new().run_saga(SessionTimeout)).await;
+
+        sleep(Duration::from_secs(3)).await;
+        store.dispatch(SessionAction::Activity).await;
+
+        // 7 seconds after the start, but only 4 seconds after the last activity
+        sleep(Duration::from_secs(4)).await;
+        assert!(!store.select(|state: &Session| state.expired).await);
+
+        sleep(Duration::from_secs(2)).await;
+        assert!(store.select(|state: &Session| state.expired).await);
+        assert_eq!(store.select(|state: &Session| state.activity).await, 1);
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn saga_restarts_on_action() {
+        let middleware = SagaMiddleware::new().restart_saga_on(SessionTimeout, |action| *action == SessionAction::Login);
+        let store = Store::new(session_reducer).wrap(middleware).await;
+
+        sleep(Duration::from_secs(6)).await;
+        assert!(store.select(|state: &Session| state.expired).await);
+
+        // The login starts a new session timeout
+        store.dispatch(SessionAction::Login).await;
+        sleep(Duration::from_secs(4)).await;
+        assert!(!store.select(|state: &Session| state.expired).await);
+
+        sleep(Duration::from_secs(2)).await;
+        assert!(store.select(|state: &Session| state.expired).await);
+        assert_eq!(store.select(|state: &Session| state.logins).await, 1);
+    }
+}
@@ -0,0 +1,238 @@
+use crate::composed_middleware::impl_middleware_bitor;
+use crate::{MiddleWare, PipelineStore, StoreApi};
+use async_trait::async_trait;
+use std::sync::{Arc, Weak};
+use std::time::{Duration, SystemTime};
+use tokio::sync::watch;
+use tokio::task::JoinHandle;
+use tokio::time::{interval_at, sleep, Instant};
+
+// The channel only holds a weak reference, the middleware inside the store owns the store.
+// Scheduled tasks stop once the store is dropped, instead of keeping it alive.
+type SharedStore<State, Action> = Option<Weak<PipelineStore<State, Action>>>;
+
+/// # Scheduler middleware
+/// A middleware which dispatches actions at a later time, once or at an interval.
+///
+/// The middleware is cheap to clone, keep a clone around to schedule actions after it's wrapped around a store.
+/// Actions which are due before the middleware is wrapped around a store are dispatched as soon as it is.
+/// Scheduled actions are dispatched to the inner store, so they skip the middlewares outside this one.
+///
+/// ## Usage:
+/// ```
+/// use std::time::Duration;
+/// use redux_rs::{middlewares::scheduler::SchedulerMiddleware, Store, StoreApi};
+///
+/// fn reducer(state: u32, add: u32) -> u32 {
+///     state + add
+/// }
+///
+/// # #[tokio::main(flavor = "current_thread")]
+/// # async fn main() {
+/// let scheduler = SchedulerMiddleware::new();
+/// let store = Store::new(reducer).wrap(scheduler.clone()).await;
+///
+/// scheduler.schedule_in(5, Duration::from_millis(10));
+/// tokio::time::sleep(Duration::from_millis(50)).await;
+///
+/// assert_eq!(store.state_cloned().await, 5);
+/// # }
+/// ```
+pub struct SchedulerMiddleware<State, Action> {
+    store: Arc<watch::Sender<SharedStore<State, Action>>>,
+    // Only set for the middleware wrapped around the store, not for the clones
+    wrapped: Option<Arc<PipelineStore<State, Action>>>,
+}
+
+/// Handle to an action scheduled by the `SchedulerMiddleware`.
+/// Dropping the handle doesn't cancel the action.
+pub struct ScheduleHandle {
+    task: JoinHandle<()>,
+}
+
+impl ScheduleHandle {
+    /// Cancel the scheduled action, recurring actions are no longer dispatched either
+    pub fn cancel(&self) {
+        self.task.abort();
+    }
+}
+
+impl<State, Action> SchedulerMiddleware<State, Action>
+where
+    State: Send + 'static,
+    Action: Send + 'static,
+{
+    pub fn new() -> Self {
+        let (store, _) = watch::channel(None);
+        SchedulerMiddleware {
+            store: Arc::new(store),
+            wrapped: None,
+        }
+    }
+
+    /// Dispatch the action at the given time, or as soon as possible if the time has passed
+    pub fn schedule_at(&self, action: Action, time: SystemTime) -> ScheduleHandle {
+        let delay = time.duration_since(SystemTime::now()).unwrap_or(Duration::ZERO);
+        self.schedule_in(action, delay)
+    }
+
+    /// Dispatch the action after the delay
+    pub fn schedule_in(&self, action: Action, delay: Duration) -> ScheduleHandle {
+        let deadline = Instant::now() + delay;
+        let mut store = self.store.subscribe();
+
+        let task = tokio::spawn(async move {
+            sleep(deadline - Instant::now()).await;
+            if let Some(store) = wrapped_store(&mut store).await {
+                store.dispatch(action).await;
+            }
+        });
+
+        ScheduleHandle { task }
+    }
+
+    /// Dispatch an action created by `action` at every interval, the first one is dispatched after one interval
+    pub fn schedule_every<F>(&self, action: F, interval: Duration) -> ScheduleHandle
+    where
+        F: Fn() -> Action + Send + Sync + 'static,
+    {
+        let mut ticks = interval_at(Instant::now() + interval, interval);
+        let mut store = self.store.subscribe();
+
+        let task = tokio::spawn(async move {
+            loop {
+                ticks.tick().await;
+                match wrapped_store(&mut store).await {
+                    Some(store) => store.dispatch(action()).await,
+                    None => return,
+                }
+            }
+        });
+
+        ScheduleHandle { task }
+    }
+}
+
+// Waits until the middleware is wrapped around a store,
+// returns None if the middleware is dropped before or if the store is dropped already
+async fn wrapped_store<State, Action>(store: &mut watch::Receiver<SharedStore<State, Action>>) -> Option<Arc<PipelineStore<State, Action>>> {
+    let store = store.wait_for(Option::is_some).await.ok()?.clone()?;
+    store.upgrade()
+}
+
+impl<State, Action> Clone for SchedulerMiddleware<State, Action> {
+    fn clone(&self) -> Self {
+        SchedulerMiddleware {
+            store: self.store.clone(),
+            wrapped: None,
+        }
+    }
+}
+
+impl<State, Action> Default for SchedulerMiddleware<State, Action>
+where
+    State: Send + 'static,
+    Action: Send + 'static,
+{
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl_middleware_bitor!(SchedulerMiddleware<State, Action>);
+
+#[async_trait]
+impl<State, Action, Inner> MiddleWare<State, Action, Inner> for SchedulerMiddleware<State, Action>
+where
+    State: Send + 'static,
+    Action: Send + 'static,
+    Inner: StoreApi<State, Action> + Send + Sync + 'static,
+{
+    async fn init(&mut self, inner: &Arc<Inner>) {
+        let store = Arc::new(PipelineStore::from_shared(inner.clone()));
+        self.store.send_replace(Some(Arc::downgrade(&store)));
+        self.wrapped = Some(store);
+    }
+
+    async fn dispatch(&self, action: Action, inner: &Arc<Inner>) {
+        inner.dispatch(action).await;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Store;
+
+    fn reducer(state: Vec<&'static str>, action: &'static str) -> Vec<&'static str> {
+        let mut state = state;
+        state.push(action);
+        state
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn scheduled_action_is_dispatched_after_delay() {
+        let scheduler = SchedulerMiddleware::new();
+        let store = Store::new(reducer).wrap(scheduler.clone()).await;
+
+        scheduler.schedule_in("delayed", Duration::from_millis(50));
+
+        sleep(Duration::from_millis(40)).await;
+        assert!(store.state_cloned().await.is_empty());
+
+        sleep(Duration::from_millis(20)).await;
+        assert_eq!(store.state_cloned().await, vec!["delayed"]);
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn cancelled_action_is_not_dispatched() {
+        let scheduler = SchedulerMiddleware::new();
+        let store = Store::new(reducer).wrap(scheduler.clone()).await;
+
+        let handle = scheduler.schedule_at("cancelled", SystemTime::now() + Duration::from_millis(50));
+        handle.cancel();
+
+        sleep(Duration::from_millis(100)).await;
+        assert!(store.state_cloned().await.is_empty());
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn recurring_action() {
+        let scheduler = SchedulerMiddleware::new();
+        let store = Store::new(reducer).wrap(scheduler.clone()).await;
+
+        let handle = scheduler.schedule_every(|| "tick", Duration::from_millis(10));
+
+        sleep(Duration::from_millis(35)).await;
+        handle.cancel();
+        sleep(Duration::from_millis(50)).await;
+
+        assert_eq!(store.state_cloned().await, vec!["tick", "tick", "tick"]);
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn scheduled_before_wrapping() {
+        let scheduler = SchedulerMiddleware::new();
+        scheduler.schedule_in("early", Duration::from_millis(10));
+
+        sleep(Duration::from_millis(20)).await;
+        let store = Store::new(reducer).wrap(scheduler).await;
+
+        sleep(Duration::from_millis(1)).await;
+        assert_eq!(store.state_cloned().await, vec!["early"]);
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn recurring_action_stops_when_store_is_dropped() {
+        let scheduler = SchedulerMiddleware::new();
+        let store = Store::new(reducer).wrap(scheduler.clone()).await;
+
+        let handle = scheduler.schedule_every(|| "tick", Duration::from_millis(10));
+        sleep(Duration::from_millis(15)).await;
+        assert_eq!(store.state_cloned().await, vec!["tick"]);
+
+        drop(store);
+        sleep(Duration::from_millis(20)).await;
+        assert!(handle.task.is_finished());
+    }
+}
@@ -1,3 +1,4 @@
+use crate::composed_middleware::impl_middleware_bitor;
 use crate::{MiddleWare, StoreApi};
 use async_trait::async_trait;
 use std::future::Future;
@@ -168,6 +169,8 @@ use std::sync::Arc;
 /// ```
 pub struct ThunkMiddleware;
 
+impl_middleware_bitor!(ThunkMiddleware);
+
 #[async_trait]
 impl<State, Action, Inner> MiddleWare<State, ActionOrThunk<State, Action, Inner>, Inner, Action> for ThunkMiddleware
 where
@@ -249,11 +252,12 @@ where
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::Store;
+    use crate::{MiddlewareTestBuilder, ObserveActions, Store, StoreApiExt};
+    use std::sync::atomic::{AtomicUsize, Ordering};
     use std::time::Duration;
     use tokio::time::sleep;
 
-    #[derive(Default, Debug, PartialEq)]
+    #[derive(Clone, Default, Debug, PartialEq)]
     struct UserState {
         users: Vec<User>,
     }
@@ -264,6 +268,7 @@ mod tests {
         name: String,
     }
 
+    #[derive(Clone, Debug, PartialEq)]
     enum UserAction {
         UsersLoaded { users: Vec<User> },
     }
@@ -302,20 +307,9 @@ mod tests {
         }
     }
 
-    #[tokio::test]
-    async fn load_users_thunk() {
-        let store = Store::new(user_reducer).wrap(ThunkMiddleware).await;
-        store.dispatch(thunk(LoadUsersThunk)).await;
-
-        let users = store.select(|state: &UserState| state.users.clone()).await;
-        assert_eq!(users, vec![]);
-
-        sleep(Duration::from_millis(200)).await;
-
-        let users = store.select(|state: &UserState| state.users.clone()).await;
-        assert_eq!(
-            users,
-            vec![
+    fn loaded_users() -> Vec<UserAction> {
+        vec![UserAction::UsersLoaded {
+            users: vec![
                 User {
                     id: 0,
                     name: "John Doe".to_string(),
@@ -324,14 +318,22 @@ mod tests {
                     id: 1,
                     name: "Jane Doe".to_string(),
                 },
-            ]
-        );
+            ],
+        }]
     }
 
-    #[tokio::test]
-    async fn load_users_fn_thunk() {
-        let store = Store::new(user_reducer).wrap(ThunkMiddleware).await;
+    #[tokio::test(start_paused = true)]
+    async fn load_users_thunk() {
+        let mut test = MiddlewareTestBuilder::<_, UserState, UserAction>::new(ThunkMiddleware);
 
+        // The thunk runs in the background, the action is only dispatched after the emulated api call
+        test.dispatch(thunk(LoadUsersThunk)).await.assert_action_dropped();
+
+        test.wait_for_inner_dispatched(1).await.assert_inner_dispatched(loaded_users());
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn load_users_fn_thunk() {
         async fn load_users(store_api: Arc<impl StoreApi<UserState, UserAction>>) {
             // Emulate api call by delaying for 100 ms
             sleep(Duration::from_millis(100)).await;
@@ -353,26 +355,66 @@ mod tests {
                 .await;
         }
 
-        store.dispatch(thunk(load_users)).await;
+        let mut test = MiddlewareTestBuilder::<_, UserState, UserAction>::new(ThunkMiddleware);
+        test.dispatch(thunk(load_users)).await.assert_action_dropped();
 
-        let users = store.select(|state: &UserState| state.users.clone()).await;
-        assert_eq!(users, vec![]);
+        test.wait_for_inner_dispatched(1).await.assert_inner_dispatched(loaded_users());
+    }
 
-        sleep(Duration::from_millis(200)).await;
+    #[tokio::test]
+    async fn wait_for_thunk_effect_in_store() {
+        let store = Store::new(user_reducer).wrap(ThunkMiddleware).await;
+        let state = store
+            .dispatch_and_wait_for_effect(thunk(LoadUsersThunk), |state: &UserState| !state.users.is_empty())
+            .await;
 
-        let users = store.select(|state: &UserState| state.users.clone()).await;
-        assert_eq!(
-            users,
-            vec![
-                User {
-                    id: 0,
-                    name: "John Doe".to_string(),
-                },
-                User {
-                    id: 1,
-                    name: "Jane Doe".to_string(),
-                },
-            ]
-        );
+        assert_eq!(vec![UserAction::UsersLoaded { users: state.users }], loaded_users());
+    }
+
+    #[tokio::test]
+    async fn passes_plain_actions() {
+        let mut test = MiddlewareTestBuilder::<_, UserState, UserAction>::new(ThunkMiddleware);
+        test.dispatch(ActionOrThunk::Action(UserAction::UsersLoaded { users: vec![] }))
+            .await
+            .assert_inner_dispatched(vec![UserAction::UsersLoaded { users: vec![] }]);
+    }
+
+    #[tokio::test]
+    async fn observe_thunk_actions() {
+        let store = Store::new(user_reducer).wrap(ThunkMiddleware).await;
+
+        // The wrapped store forwards to the underlying store, so actions dispatched by thunks are observed too
+        let mut actions = store.observe_actions().await;
+        store.dispatch(thunk(LoadUsersThunk)).await;
+
+        let action = actions.recv().await;
+        assert!(matches!(action, Some(UserAction::UsersLoaded { users }) if users.len() == 2));
+    }
+
+    struct CountingMiddleware(Arc<AtomicUsize>);
+
+    #[async_trait]
+    impl<Inner> MiddleWare<UserState, UserAction, Inner> for CountingMiddleware
+    where
+        Inner: StoreApi<UserState, UserAction> + Send + Sync,
+    {
+        async fn dispatch(&self, action: UserAction, inner: &Arc<Inner>) {
+            self.0.fetch_add(1, Ordering::SeqCst);
+            inner.dispatch(action).await
+        }
+    }
+
+    #[tokio::test]
+    async fn chain_middleware_after_thunks() {
+        let counted = Arc::new(AtomicUsize::new(0));
+        let store = Store::new(user_reducer).wrap(ThunkMiddleware | CountingMiddleware(counted.clone())).await;
+
+        // The actions dispatched by the thunk pass the chained middleware
+        store
+            .dispatch_and_wait_for_effect(thunk(LoadUsersThunk), |state: &UserState| !state.users.is_empty())
+            .await;
+        store.dispatch(ActionOrThunk::Action(UserAction::UsersLoaded { users: vec![] })).await;
+
+        assert_eq!(counted.load(Ordering::SeqCst), 2);
     }
 }
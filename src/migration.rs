@@ -0,0 +1,190 @@
+use crate::{Reducer, Store};
+use std::any::Any;
+use std::error::Error;
+use std::fmt::{Display, Formatter};
+
+type AnyState = Box<dyn Any + Send>;
+
+/// A persisted state together with the schema version it was written with
+pub struct PersistedState {
+    pub version: u32,
+    pub state: AnyState,
+}
+
+impl PersistedState {
+    pub fn new<State: Any + Send>(version: u32, state: State) -> Self {
+        PersistedState {
+            version,
+            state: Box::new(state),
+        }
+    }
+}
+
+/// Upgrades a state to the given version, from the version right before it
+pub struct StateMigration<OldState, NewState> {
+    pub version: u32,
+    pub migrate: fn(OldState) -> NewState,
+}
+
+/// A type-erased `StateMigration`, so migrations between different state types can be kept in one list
+pub struct AnyMigration {
+    version: u32,
+    migrate: Box<dyn Fn(AnyState) -> Result<AnyState, MigrationError> + Send>,
+}
+
+impl<OldState, NewState> From<StateMigration<OldState, NewState>> for AnyMigration
+where
+    OldState: Any + Send,
+    NewState: Any + Send,
+{
+    fn from(migration: StateMigration<OldState, NewState>) -> Self {
+        let StateMigration { version, migrate } = migration;
+
+        AnyMigration {
+            version,
+            migrate: Box::new(move |state: AnyState| {
+                let state = state.downcast::<OldState>().map_err(|_| MigrationError::TypeMismatch { version })?;
+                Ok(Box::new(migrate(*state)) as AnyState)
+            }),
+        }
+    }
+}
+
+/// Returned when a persisted state can't be migrated to the current state
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum MigrationError {
+    /// The migration to this version didn't receive the state type it expects
+    TypeMismatch { version: u32 },
+    /// There's no migration to this version
+    MissingMigration { version: u32 },
+}
+
+impl Display for MigrationError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            MigrationError::TypeMismatch { version } => write!(f, "unexpected state type for the migration to version {}", version),
+            MigrationError::MissingMigration { version } => write!(f, "no migration to version {}", version),
+        }
+    }
+}
+
+impl Error for MigrationError {}
+
+impl<State, Action, RootReducer> Store<State, Action, RootReducer>
+where
+    Action: Send + 'static,
+    RootReducer: Reducer<State, Action> + Send + 'static,
+    State: Any + Send + 'static,
+{
+    /// Create a new store from a persisted state, upgrading it with the migrations first.
+    ///
+    /// Starting from the version of the persisted state, the migrations are applied in version order
+    /// until the latest migration has been applied. A persisted state without newer migrations is used as is.
+    ///
+    /// ## Example
+    /// ```
+    /// use redux_rs::{PersistedState, StateMigration, Store};
+    ///
+    /// struct CounterV1(u8);
+    /// struct CounterV2(u32);
+    ///
+    /// fn reducer(state: CounterV2, add: u32) -> CounterV2 {
+    ///     CounterV2(state.0 + add)
+    /// }
+    ///
+    /// # #[tokio::main(flavor = "current_thread")]
+    /// # async fn main() {
+    /// let migration = StateMigration {
+    ///     version: 2,
+    ///     migrate: |state: CounterV1| CounterV2(state.0 as u32),
+    /// };
+    ///
+    /// let store = Store::new_with_migrations(reducer, PersistedState::new(1, CounterV1(5)), vec![migration.into()]).unwrap();
+    /// assert_eq!(store.select(|state: &CounterV2| state.0).await, 5);
+    /// # }
+    /// ```
+    pub fn new_with_migrations(root_reducer: RootReducer, persisted: PersistedState, mut migrations: Vec<AnyMigration>) -> Result<Self, MigrationError> {
+        migrations.sort_by_key(|migration| migration.version);
+
+        let PersistedState { mut version, mut state } = persisted;
+        let persisted_version = version;
+        for migration in migrations.iter().filter(|migration| migration.version > persisted_version) {
+            if migration.version != version + 1 {
+                return Err(MigrationError::MissingMigration { version: version + 1 });
+            }
+
+            state = (migration.migrate)(state)?;
+            version = migration.version;
+        }
+
+        let state = state.downcast::<State>().map_err(|_| MigrationError::TypeMismatch { version })?;
+        Ok(Store::new_with_state(root_reducer, *state))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct SettingsV1 {
+        volume: u8,
+    }
+
+    struct SettingsV2 {
+        volume: u8,
+        muted: bool,
+    }
+
+    #[derive(Clone, Debug, PartialEq)]
+    struct SettingsV3 {
+        volume: f32,
+        muted: bool,
+    }
+
+    fn settings_reducer(state: SettingsV3, muted: bool) -> SettingsV3 {
+        SettingsV3 { muted, ..state }
+    }
+
+    fn migrations() -> Vec<AnyMigration> {
+        vec![
+            StateMigration {
+                version: 3,
+                migrate: |state: SettingsV2| SettingsV3 {
+                    volume: state.volume as f32 / 100.0,
+                    muted: state.muted,
+                },
+            }
+            .into(),
+            StateMigration {
+                version: 2,
+                migrate: |state: SettingsV1| SettingsV2 {
+                    volume: state.volume,
+                    muted: false,
+                },
+            }
+            .into(),
+        ]
+    }
+
+    #[tokio::test]
+    async fn migrate_v1_to_v3() {
+        let store = Store::new_with_migrations(settings_reducer, PersistedState::new(1, SettingsV1 { volume: 50 }), migrations()).unwrap();
+        assert_eq!(store.state_cloned().await, SettingsV3 { volume: 0.5, muted: false });
+
+        store.dispatch(true).await;
+        assert_eq!(store.state_cloned().await, SettingsV3 { volume: 0.5, muted: true });
+    }
+
+    #[tokio::test]
+    async fn current_version_is_not_migrated() {
+        let state = SettingsV3 { volume: 1.0, muted: true };
+        let store = Store::new_with_migrations(settings_reducer, PersistedState::new(3, state.clone()), migrations()).unwrap();
+        assert_eq!(store.state_cloned().await, state);
+    }
+
+    #[tokio::test]
+    async fn wrong_state_type() {
+        let result = Store::new_with_migrations(settings_reducer, PersistedState::new(1, SettingsV2 { volume: 1, muted: true }), migrations());
+        assert_eq!(result.err(), Some(MigrationError::TypeMismatch { version: 2 }));
+    }
+}
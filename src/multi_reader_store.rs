@@ -0,0 +1,230 @@
+use crate::{Reducer, Selector, StoreApi, Subscriber};
+use async_trait::async_trait;
+use std::sync::{Arc, Mutex, RwLock};
+use tokio::sync::mpsc::{unbounded_channel, UnboundedReceiver, UnboundedSender};
+use tokio::sync::oneshot;
+
+type ReadJob<State> = Box<dyn FnOnce(&State) + Send>;
+type WriteJob<Action> = (Action, oneshot::Sender<()>);
+type Condition<State> = Box<dyn Fn(&State) -> bool + Send>;
+// A subscriber with a condition is removed once the condition is met, see `StoreApi::subscribe_until`
+type Subscribers<State> = Arc<Mutex<Vec<(Box<dyn Subscriber<State> + Send>, Option<Condition<State>>)>>>;
+
+/// A store with a single writer task for dispatches and a pool of reader tasks for selects.
+///
+/// The state lives behind a read-write lock, so selects run concurrently with each other.
+/// Dispatches take the write lock and are reduced one by one in dispatch order, like in a regular `Store`.
+/// This pays off for read-heavy workloads with expensive selectors, use a regular `Store` otherwise.
+///
+/// Readers run on the tokio runtime, so the number of selects which actually run in parallel
+/// is limited by the worker threads of the runtime as well.
+///
+/// ## Example
+/// ```
+/// use redux_rs::{MultiReaderStore, StoreApi};
+///
+/// fn reducer(state: u32, add: u32) -> u32 {
+///     state + add
+/// }
+///
+/// # #[tokio::main(flavor = "current_thread")]
+/// # async fn main() {
+/// let store = MultiReaderStore::new_multi_reader(reducer, 0, 4);
+/// store.dispatch(5u32).await;
+///
+/// assert_eq!(store.select(|state: &u32| *state).await, 5);
+/// # }
+/// ```
+pub struct MultiReaderStore<State, Action> {
+    writer: UnboundedSender<WriteJob<Action>>,
+    readers: UnboundedSender<ReadJob<State>>,
+    subscribers: Subscribers<State>,
+}
+
+impl<State, Action> MultiReaderStore<State, Action>
+where
+    State: Send + Sync + 'static,
+    Action: Send + 'static,
+{
+    /// Create a store with the given root reducer, state and number of reader tasks.
+    /// Panics if `reader_threads` is 0.
+    pub fn new_multi_reader<RootReducer>(root_reducer: RootReducer, state: State, reader_threads: usize) -> Self
+    where
+        RootReducer: Reducer<State, Action> + Send + 'static,
+    {
+        assert!(reader_threads > 0, "a MultiReaderStore needs at least one reader");
+
+        // The state is only taken out while the reducer runs, the write lock is held during that time
+        let state = Arc::new(RwLock::new(Some(state)));
+        let subscribers: Subscribers<State> = Default::default();
+
+        let (writer, write_jobs) = unbounded_channel();
+        tokio::spawn(write(root_reducer, state.clone(), subscribers.clone(), write_jobs));
+
+        let (readers, read_jobs) = unbounded_channel();
+        let read_jobs = Arc::new(tokio::sync::Mutex::new(read_jobs));
+        for _ in 0..reader_threads {
+            tokio::spawn(read(state.clone(), read_jobs.clone()));
+        }
+
+        MultiReaderStore { writer, readers, subscribers }
+    }
+}
+
+async fn write<State, Action, RootReducer>(
+    root_reducer: RootReducer,
+    state: Arc<RwLock<Option<State>>>,
+    subscribers: Subscribers<State>,
+    mut jobs: UnboundedReceiver<WriteJob<Action>>,
+) where
+    RootReducer: Reducer<State, Action>,
+{
+    while let Some((action, done)) = jobs.recv().await {
+        {
+            let mut state = state.write().unwrap();
+            let new_state = root_reducer.reduce(state.take().unwrap(), action);
+            *state = Some(new_state);
+        }
+
+        // Subscribers are notified under a read lock, so selects can run in the meantime
+        let state = state.read().unwrap();
+        let state = state.as_ref().unwrap();
+        subscribers.lock().unwrap().retain(|(subscriber, condition)| {
+            subscriber.notify(state);
+            !condition.as_ref().is_some_and(|condition| condition(state))
+        });
+
+        let _ = done.send(());
+    }
+}
+
+async fn read<State>(state: Arc<RwLock<Option<State>>>, jobs: Arc<tokio::sync::Mutex<UnboundedReceiver<ReadJob<State>>>>) {
+    loop {
+        // Only the receiving is exclusive, the job runs after the lock on the queue is released
+        let job = match jobs.lock().await.recv().await {
+            Some(job) => job,
+            None => return,
+        };
+
+        job(state.read().unwrap().as_ref().unwrap());
+    }
+}
+
+#[async_trait]
+impl<State, Action> StoreApi<State, Action> for MultiReaderStore<State, Action>
+where
+    State: Send + Sync + 'static,
+    Action: Send + 'static,
+{
+    async fn dispatch<A: Into<Action> + Send>(&self, action: A) {
+        let (done, reduced) = oneshot::channel();
+        let _ = self.writer.send((action.into(), done));
+        let _ = reduced.await;
+    }
+
+    async fn select<S: Selector<State, Result = Result>, Result>(&self, selector: S) -> Result
+    where
+        S: Selector<State, Result = Result> + Send + 'static,
+        Result: Send + 'static,
+    {
+        let (tx, rx) = oneshot::channel();
+        let job: ReadJob<State> = Box::new(move |state: &State| {
+            let _ = tx.send(selector.select(state));
+        });
+
+        let _ = self.readers.send(job);
+        rx.await.expect("the readers live as long as the store")
+    }
+
+    async fn subscribe<S: Subscriber<State> + Send + 'static>(&self, subscriber: S) {
+        self.subscribers.lock().unwrap().push((Box::new(subscriber), None));
+    }
+
+    async fn subscribe_until<S, P>(&self, subscriber: S, condition: P)
+    where
+        S: Subscriber<State> + Send + Sync + 'static,
+        P: Fn(&State) -> bool + Send + Sync + 'static,
+    {
+        self.subscribers.lock().unwrap().push((Box::new(subscriber), Some(Box::new(condition))));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::time::{Duration, Instant};
+
+    fn counter_reducer(state: u32, add: u32) -> u32 {
+        state + add
+    }
+
+    // Selector which waits until `parties` selectors run at the same time, returns false if that doesn't happen.
+    // The deadline only keeps a broken store from hanging the test.
+    fn rendezvous(active: Arc<AtomicUsize>, parties: usize) -> impl Fn(&u32) -> bool {
+        move |_: &u32| {
+            active.fetch_add(1, Ordering::SeqCst);
+            let deadline = Instant::now() + Duration::from_secs(10);
+            while active.load(Ordering::SeqCst) < parties {
+                if Instant::now() > deadline {
+                    return false;
+                }
+                std::thread::yield_now();
+            }
+            true
+        }
+    }
+
+    #[tokio::test(flavor = "multi_thread", worker_threads = 5)]
+    async fn selects_run_concurrently() {
+        let store = MultiReaderStore::new_multi_reader(counter_reducer, 0, 4);
+        let active = Arc::new(AtomicUsize::new(0));
+
+        let (first, second, third, fourth, _) = tokio::join!(
+            store.select(rendezvous(active.clone(), 4)),
+            store.select(rendezvous(active.clone(), 4)),
+            store.select(rendezvous(active.clone(), 4)),
+            store.select(rendezvous(active.clone(), 4)),
+            store.dispatch(1u32),
+        );
+
+        assert!(first && second && third && fourth);
+        assert_eq!(store.select(|state: &u32| *state).await, 1);
+    }
+
+    #[tokio::test]
+    async fn dispatches_are_ordered() {
+        let store = MultiReaderStore::new_multi_reader(|state: Vec<u32>, value: u32| [state, vec![value]].concat(), Vec::new(), 2);
+
+        let notified = Arc::new(Mutex::new(Vec::new()));
+        let notified_clone = notified.clone();
+        store.subscribe(move |state: &Vec<u32>| notified_clone.lock().unwrap().push(state.len())).await;
+
+        for value in 0..5u32 {
+            store.dispatch(value).await;
+        }
+
+        assert_eq!(store.state_cloned().await, vec![0, 1, 2, 3, 4]);
+        assert_eq!(*notified.lock().unwrap(), vec![1, 2, 3, 4, 5]);
+    }
+
+    #[tokio::test]
+    async fn until_subscribers_are_removed() {
+        let store = MultiReaderStore::new_multi_reader(counter_reducer, 0, 1);
+
+        let notified = Arc::new(AtomicUsize::new(0));
+        let subscriber_notified = notified.clone();
+        let subscriber = move |_: &u32| {
+            subscriber_notified.fetch_add(1, Ordering::SeqCst);
+        };
+        store.subscribe_until(subscriber, |state: &u32| *state >= 2).await;
+
+        for _ in 0..5 {
+            store.dispatch(1u32).await;
+        }
+
+        assert_eq!(notified.load(Ordering::SeqCst), 2);
+        assert_eq!(Arc::strong_count(&notified), 1);
+        assert!(store.subscribers.lock().unwrap().is_empty());
+    }
+}
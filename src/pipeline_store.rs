@@ -0,0 +1,263 @@
+use crate::{Selector, StoreApi, Subscriber};
+use async_trait::async_trait;
+use std::any::Any;
+use std::sync::Arc;
+
+type AnyResult = Box<dyn Any + Send>;
+type AnySelector<State> = Box<dyn Fn(&State) -> AnyResult + Send>;
+type BoxedSubscriber<State> = Box<dyn Subscriber<State> + Send + Sync>;
+type BoxedCondition<State> = Box<dyn Fn(&State) -> bool + Send + Sync>;
+
+// Object safe counterpart of StoreApi, the generic arguments are boxed
+#[async_trait]
+trait ErasedStore<State, Action>: Send + Sync {
+    async fn dispatch_erased(&self, action: Action);
+
+    async fn select_erased(&self, selector: AnySelector<State>) -> AnyResult;
+
+    async fn subscribe_erased(&self, subscriber: BoxedSubscriber<State>);
+
+    async fn subscribe_until_erased(&self, subscriber: BoxedSubscriber<State>, condition: BoxedCondition<State>);
+
+    fn middleware_depth_erased(&self) -> usize;
+
+    fn middleware_names_erased(&self) -> Vec<String>;
+
+    async fn teardown_erased(&self);
+}
+
+// Implemented for Arc so a store which is already shared, like the inner store of a middleware, can be erased as well
+#[async_trait]
+impl<Store, State, Action> ErasedStore<State, Action> for Arc<Store>
+where
+    Store: StoreApi<State, Action> + Send + Sync,
+    State: Send + 'static,
+    Action: Send + 'static,
+{
+    async fn dispatch_erased(&self, action: Action) {
+        self.dispatch(action).await
+    }
+
+    async fn select_erased(&self, selector: AnySelector<State>) -> AnyResult {
+        self.select(selector).await
+    }
+
+    async fn subscribe_erased(&self, subscriber: BoxedSubscriber<State>) {
+        self.subscribe_sync(move |state: &State| subscriber.notify(state)).await
+    }
+
+    async fn subscribe_until_erased(&self, subscriber: BoxedSubscriber<State>, condition: BoxedCondition<State>) {
+        self.subscribe_until(move |state: &State| subscriber.notify(state), condition).await
+    }
+
+    fn middleware_depth_erased(&self) -> usize {
+        self.middleware_depth()
+    }
+
+    fn middleware_names_erased(&self) -> Vec<String> {
+        self.middleware_names()
+    }
+
+    async fn teardown_erased(&self) {
+        self.teardown().await
+    }
+}
+
+/// Store with its middleware types erased, which makes it easy to name and to store in a struct.
+/// Usually created by the `middleware_pipeline!` macro.
+///
+/// The erasure costs an allocation per select and per subscribe, dispatching is not affected.
+pub struct PipelineStore<State, Action> {
+    inner: Box<dyn ErasedStore<State, Action>>,
+}
+
+impl<State, Action> PipelineStore<State, Action>
+where
+    State: Send + 'static,
+    Action: Send + 'static,
+{
+    /// Erase the type of the store
+    pub fn new<S>(store: S) -> Self
+    where
+        S: StoreApi<State, Action> + Send + Sync + 'static,
+    {
+        Self::from_shared(Arc::new(store))
+    }
+
+    pub(crate) fn from_shared<S>(store: Arc<S>) -> Self
+    where
+        S: StoreApi<State, Action> + Send + Sync + 'static,
+    {
+        PipelineStore { inner: Box::new(store) }
+    }
+}
+
+#[async_trait]
+impl<State, Action> StoreApi<State, Action> for PipelineStore<State, Action>
+where
+    State: Send + 'static,
+    Action: Send + 'static,
+{
+    async fn dispatch<A: Into<Action> + Send>(&self, action: A) {
+        self.inner.dispatch_erased(action.into()).await
+    }
+
+    async fn select<S: Selector<State, Result = Result>, Result>(&self, selector: S) -> Result
+    where
+        S: Selector<State, Result = Result> + Send + 'static,
+        Result: Send + 'static,
+    {
+        let result = self
+            .inner
+            .select_erased(Box::new(move |state: &State| Box::new(selector.select(state)) as AnyResult))
+            .await;
+        *result.downcast().expect("the selector result has the type of the selector")
+    }
+
+    async fn subscribe<S: Subscriber<State> + Send + 'static>(&self, subscriber: S) {
+        // Subscribers are only required to be Send, the mutex makes them Sync
+        let subscriber = std::sync::Mutex::new(subscriber);
+        self.inner
+            .subscribe_erased(Box::new(move |state: &State| subscriber.lock().unwrap().notify(state)))
+            .await
+    }
+
+    async fn subscribe_sync<S: Subscriber<State> + Send + Sync + 'static>(&self, subscriber: S) {
+        self.inner.subscribe_erased(Box::new(subscriber)).await
+    }
+
+    async fn subscribe_until<S, P>(&self, subscriber: S, condition: P)
+    where
+        S: Subscriber<State> + Send + Sync + 'static,
+        P: Fn(&State) -> bool + Send + Sync + 'static,
+    {
+        self.inner.subscribe_until_erased(Box::new(subscriber), Box::new(condition)).await
+    }
+
+    fn middleware_depth(&self) -> usize {
+        self.inner.middleware_depth_erased()
+    }
+
+    fn middleware_names(&self) -> Vec<String> {
+        self.inner.middleware_names_erased()
+    }
+
+    async fn teardown(&self) {
+        self.inner.teardown_erased().await
+    }
+}
+
+/// Wrap a store with a list of middlewares and erase the resulting type into a `PipelineStore`.
+///
+/// `middleware_pipeline!(store, [a, b, c])` expands to `PipelineStore::new(store.wrap(a).await.wrap(b).await.wrap(c).await)`,
+/// so it has to be used in an async context. The last middleware is the outermost one.
+///
+/// ## Example
+/// ```
+/// use redux_rs::{middleware_pipeline, PipelineStore, Store, StoreApi};
+///
+/// fn reducer(state: u8, add: u8) -> u8 {
+///     state + add
+/// }
+///
+/// # #[tokio::main(flavor = "current_thread")]
+/// # async fn main() {
+/// let store: PipelineStore<u8, u8> = middleware_pipeline!(Store::new(reducer), []);
+/// store.dispatch(2).await;
+///
+/// assert_eq!(store.state_cloned().await, 2);
+/// # }
+/// ```
+#[macro_export]
+macro_rules! middleware_pipeline {
+    ($store:expr, [$($middleware:expr),* $(,)?]) => {
+        $crate::PipelineStore::new($store $(.wrap($middleware).await)*)
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{MiddleWare, Store};
+    use std::sync::Mutex;
+
+    #[derive(Clone, Debug, Default, PartialEq)]
+    struct Counter(i32);
+
+    struct Add(i32);
+
+    fn counter_reducer(state: Counter, action: Add) -> Counter {
+        Counter(state.0 + action.0)
+    }
+
+    struct Record {
+        name: &'static str,
+        logs: Arc<Mutex<Vec<String>>>,
+    }
+
+    #[async_trait]
+    impl<Inner> MiddleWare<Counter, Add, Inner> for Record
+    where
+        Inner: StoreApi<Counter, Add> + Send + Sync,
+    {
+        async fn dispatch(&self, action: Add, inner: &Arc<Inner>) {
+            self.logs.lock().unwrap().push(format!("{} before {}", self.name, action.0));
+            let amount = action.0;
+            inner.dispatch(action).await;
+            self.logs.lock().unwrap().push(format!("{} after {}", self.name, amount));
+        }
+    }
+
+    fn record(name: &'static str, logs: &Arc<Mutex<Vec<String>>>) -> Record {
+        Record { name, logs: logs.clone() }
+    }
+
+    #[tokio::test]
+    async fn pipeline_matches_manual_chain() {
+        let pipeline_logs = Arc::new(Mutex::new(Vec::new()));
+        let pipeline = middleware_pipeline!(
+            Store::new(counter_reducer),
+            [record("a", &pipeline_logs), record("b", &pipeline_logs), record("c", &pipeline_logs)]
+        );
+
+        let manual_logs = Arc::new(Mutex::new(Vec::new()));
+        let manual = Store::new(counter_reducer)
+            .wrap(record("a", &manual_logs))
+            .await
+            .wrap(record("b", &manual_logs))
+            .await
+            .wrap(record("c", &manual_logs))
+            .await;
+
+        for amount in [1, 2, 3] {
+            pipeline.dispatch(Add(amount)).await;
+            manual.dispatch(Add(amount)).await;
+        }
+
+        assert_eq!(*pipeline_logs.lock().unwrap(), *manual_logs.lock().unwrap());
+        assert_eq!(pipeline.state_cloned().await, manual.state_cloned().await);
+        assert_eq!(pipeline.state_cloned().await, Counter(6));
+        assert_eq!(pipeline.middleware_depth(), 3);
+    }
+
+    #[tokio::test]
+    async fn pipeline_subscribers() {
+        let logs = Arc::new(Mutex::new(Vec::new()));
+        let pipeline = middleware_pipeline!(Store::new(counter_reducer), [record("a", &logs)]);
+
+        let notified = Arc::new(Mutex::new(Vec::new()));
+        let notified_clone = notified.clone();
+        pipeline
+            .subscribe_until(
+                move |state: &Counter| notified_clone.lock().unwrap().push(state.0),
+                |state: &Counter| state.0 >= 2,
+            )
+            .await;
+
+        pipeline.dispatch(Add(1)).await;
+        pipeline.dispatch(Add(1)).await;
+        pipeline.dispatch(Add(1)).await;
+
+        assert_eq!(*notified.lock().unwrap(), vec![1, 2]);
+    }
+}
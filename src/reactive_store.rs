@@ -0,0 +1,304 @@
+use crate::{Reducer, Selector, StoreApi, Subscriber};
+use async_trait::async_trait;
+use std::marker::PhantomData;
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::sync::{Arc, Mutex, RwLock};
+use std::thread::{self, ThreadId};
+use tokio::sync::watch;
+
+/// A store which reduces actions in place, without a worker task.
+///
+/// Dispatching takes a write lock on the state, runs the reducer and publishes the new state on a `tokio::sync::watch` channel.
+/// The latest state is always available through `state_watch`, without calling select.
+/// Because no task is spawned this store is suitable for single-threaded environments, like UI event loops.
+///
+/// Notice that the reducer and subscribers run on the task which dispatches the action.
+/// The state isn't locked while the subscribers run, so a subscriber can select the state and dispatch actions.
+/// An action dispatched by a subscriber doesn't notify the subscribers right away,
+/// once all subscribers are notified of the current action they are notified again with the latest state.
+/// Subscribing from within a subscriber deadlocks.
+/// When actions are dispatched from several threads at once, subscribers can skip a state, but never see an older state after a newer one.
+///
+/// ## Example
+/// ```
+/// use redux_rs::ReactiveStore;
+///
+/// fn reducer(state: u32, add: u32) -> u32 {
+///     state + add
+/// }
+///
+/// let store = ReactiveStore::new(reducer);
+/// let watch = store.state_watch();
+///
+/// store.dispatch_sync(5);
+/// assert_eq!(*watch.borrow(), 5);
+/// ```
+pub struct ReactiveStore<State, Action, RootReducer> {
+    root_reducer: RootReducer,
+    // The state is only taken out while the reducer runs, the write lock is held during that time.
+    // The version counts the reduced actions, so notifications of concurrent dispatches can be ordered.
+    state: Arc<RwLock<(Option<State>, u64)>>,
+    // Only sent to while the write lock is held, so the watch never ends up with an older state
+    sender: watch::Sender<State>,
+    subscribers: Mutex<Vec<Box<dyn Subscriber<State> + Send>>>,
+    // The version of the last state the subscribers were notified of, only changed while the subscribers are locked
+    notified_version: AtomicU64,
+    // The thread which notifies the subscribers right now, to recognize a dispatch from within a subscriber
+    notifying: Mutex<Option<ThreadId>>,
+    // Set by a dispatch from within a subscriber, the subscribers are notified again afterwards
+    renotify: AtomicBool,
+
+    _types: PhantomData<fn(Action)>,
+}
+
+impl<State, Action, RootReducer> ReactiveStore<State, Action, RootReducer>
+where
+    State: Clone,
+    RootReducer: Reducer<State, Action>,
+{
+    /// Create a new store with the given root reducer and default state
+    pub fn new(root_reducer: RootReducer) -> Self
+    where
+        State: Default,
+    {
+        Self::new_with_state(root_reducer, Default::default())
+    }
+
+    /// Create a new store with the given root reducer and the provided state
+    pub fn new_with_state(root_reducer: RootReducer, state: State) -> Self {
+        let (sender, _) = watch::channel(state.clone());
+
+        ReactiveStore {
+            root_reducer,
+            state: Arc::new(RwLock::new((Some(state), 0))),
+            sender,
+            subscribers: Default::default(),
+            notified_version: Default::default(),
+            notifying: Default::default(),
+            renotify: Default::default(),
+
+            _types: Default::default(),
+        }
+    }
+
+    /// Dispatch a new action to the store, the new state is available as soon as this method returns
+    pub fn dispatch_sync(&self, action: Action) {
+        let (new_state, version) = {
+            let mut state = self.state.write().unwrap();
+            let new_state = self.root_reducer.reduce(state.0.take().unwrap(), action);
+            state.0 = Some(new_state.clone());
+            state.1 += 1;
+
+            // Published before the lock is released, so concurrent dispatches reach the watch in order
+            self.sender.send_replace(new_state.clone());
+            (new_state, state.1)
+        };
+
+        self.notify_subscribers(new_state, version);
+    }
+
+    // Called without holding the state lock, so subscribers can select and dispatch
+    fn notify_subscribers(&self, state: State, version: u64) {
+        let current = thread::current().id();
+        if *self.notifying.lock().unwrap() == Some(current) {
+            // Dispatched from within a subscriber, the subscribers are still locked by the outer dispatch
+            self.renotify.store(true, Ordering::SeqCst);
+            return;
+        }
+
+        let subscribers = self.subscribers.lock().unwrap();
+        if self.notified_version.load(Ordering::SeqCst) >= version {
+            // A concurrent dispatch already notified the subscribers of a newer state
+            return;
+        }
+        let _notifying = NotifyingGuard::new(&self.notifying, current);
+
+        let (mut state, mut version) = (state, version);
+        loop {
+            self.notified_version.store(version, Ordering::SeqCst);
+            for subscriber in subscribers.iter() {
+                subscriber.notify(&state);
+            }
+
+            if !self.renotify.swap(false, Ordering::SeqCst) {
+                break;
+            }
+            (state, version) = {
+                let state = self.state.read().unwrap();
+                (state.0.clone().unwrap(), state.1)
+            };
+        }
+    }
+
+    /// Select a part of the state
+    pub fn select_sync<S: Selector<State>>(&self, selector: S) -> S::Result {
+        let state = self.state.read().unwrap();
+        selector.select(state.0.as_ref().unwrap())
+    }
+
+    /// Returns a receiver which always holds the latest state
+    pub fn state_watch(&self) -> watch::Receiver<State> {
+        self.sender.subscribe()
+    }
+}
+
+// Clears the notifying thread when the notification is done, also when a subscriber panics
+struct NotifyingGuard<'a> {
+    notifying: &'a Mutex<Option<ThreadId>>,
+}
+
+impl<'a> NotifyingGuard<'a> {
+    fn new(notifying: &'a Mutex<Option<ThreadId>>, thread: ThreadId) -> Self {
+        *notifying.lock().unwrap() = Some(thread);
+        NotifyingGuard { notifying }
+    }
+}
+
+impl Drop for NotifyingGuard<'_> {
+    fn drop(&mut self) {
+        if let Ok(mut notifying) = self.notifying.lock() {
+            *notifying = None;
+        }
+    }
+}
+
+#[async_trait]
+impl<State, Action, RootReducer> StoreApi<State, Action> for ReactiveStore<State, Action, RootReducer>
+where
+    Action: Send + 'static,
+    RootReducer: Reducer<State, Action> + Send + Sync + 'static,
+    State: Clone + Send + Sync + 'static,
+{
+    async fn dispatch<A: Into<Action> + Send>(&self, action: A) {
+        self.dispatch_sync(action.into())
+    }
+
+    async fn select<S: Selector<State, Result = Result>, Result>(&self, selector: S) -> Result
+    where
+        S: Selector<State, Result = Result> + Send + 'static,
+        Result: Send + 'static,
+    {
+        self.select_sync(selector)
+    }
+
+    async fn subscribe<S: Subscriber<State> + Send + 'static>(&self, subscriber: S) {
+        self.subscribers.lock().unwrap().push(Box::new(subscriber));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Clone, Debug, Default, PartialEq)]
+    struct Counter {
+        value: i32,
+    }
+
+    enum CounterAction {
+        Increment,
+        Decrement,
+    }
+
+    fn counter_reducer(state: Counter, action: CounterAction) -> Counter {
+        match action {
+            CounterAction::Increment => Counter { value: state.value + 1 },
+            CounterAction::Decrement => Counter { value: state.value - 1 },
+        }
+    }
+
+    #[tokio::test]
+    async fn state_watch() {
+        let store = ReactiveStore::new(counter_reducer);
+        let mut watch = store.state_watch();
+
+        store.dispatch(CounterAction::Increment).await;
+        assert!(watch.has_changed().unwrap());
+        assert_eq!(watch.borrow_and_update().value, 1);
+
+        store.dispatch(CounterAction::Decrement).await;
+        store.dispatch(CounterAction::Decrement).await;
+        assert_eq!(watch.borrow().value, -1);
+        assert_eq!(store.select(|state: &Counter| state.value).await, -1);
+    }
+
+    #[test]
+    fn subscribers_can_select_and_dispatch() {
+        let store = Arc::new(ReactiveStore::new(counter_reducer));
+
+        let values = Arc::new(Mutex::new(Vec::new()));
+        let (subscriber_store, subscriber_values) = (Arc::downgrade(&store), values.clone());
+        store.subscribers.lock().unwrap().push(Box::new(move |state: &Counter| {
+            let store = subscriber_store.upgrade().unwrap();
+            subscriber_values
+                .lock()
+                .unwrap()
+                .push((state.value, store.select_sync(|state: &Counter| state.value)));
+
+            // Bounce back to zero once
+            if state.value == 2 {
+                store.dispatch_sync(CounterAction::Decrement);
+                store.dispatch_sync(CounterAction::Decrement);
+            }
+        }));
+
+        store.dispatch_sync(CounterAction::Increment);
+        store.dispatch_sync(CounterAction::Increment);
+
+        assert_eq!(store.select_sync(|state: &Counter| state.value), 0);
+        assert_eq!(*values.lock().unwrap(), vec![(1, 1), (2, 2), (0, 0)]);
+    }
+
+    #[tokio::test]
+    async fn matches_actor_store() {
+        let actions = || (0..1000).map(|i| if i % 3 == 0 { CounterAction::Decrement } else { CounterAction::Increment });
+
+        // The reactive store reduces in place, every dispatch is visible before it returns
+        let reactive = ReactiveStore::new(counter_reducer);
+        for action in actions() {
+            reactive.dispatch_sync(action);
+        }
+
+        let store = crate::Store::new(counter_reducer);
+        store.dispatch_all_and_wait(actions()).await;
+
+        assert_eq!(reactive.select_sync(|state: &Counter| state.clone()), store.state_cloned().await);
+        assert_eq!(reactive.state_watch().borrow().value, 332);
+    }
+
+    #[test]
+    fn watch_holds_latest_state_after_concurrent_dispatches() {
+        let store = Arc::new(ReactiveStore::new(counter_reducer));
+        let notified = Arc::new(Mutex::new(Vec::new()));
+        let subscriber_notified = notified.clone();
+        store
+            .subscribers
+            .lock()
+            .unwrap()
+            .push(Box::new(move |state: &Counter| subscriber_notified.lock().unwrap().push(state.value)));
+
+        let barrier = Arc::new(std::sync::Barrier::new(4));
+        let threads: Vec<_> = (0..4)
+            .map(|_| {
+                let (store, barrier) = (store.clone(), barrier.clone());
+                thread::spawn(move || {
+                    barrier.wait();
+                    for _ in 0..100 {
+                        store.dispatch_sync(CounterAction::Increment);
+                    }
+                })
+            })
+            .collect();
+        for thread in threads {
+            thread.join().unwrap();
+        }
+
+        assert_eq!(store.select_sync(|state: &Counter| state.value), 400);
+        assert_eq!(store.state_watch().borrow().value, 400);
+
+        // States may be skipped, but the subscribers never see an older state after a newer one
+        let notified = notified.lock().unwrap();
+        assert!(notified.windows(2).all(|pair| pair[0] < pair[1]));
+        assert_eq!(notified.last(), Some(&400));
+    }
+}
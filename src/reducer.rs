@@ -51,3 +51,141 @@ where
         self(state, action)
     }
 }
+
+/// Combine two reducers, the second reducer only runs if the first one changed the state.
+///
+/// This avoids wasteful computation in the second reducer for actions which don't affect the first part of the state,
+/// similar to short-circuit evaluation.
+/// The state is cloned before the first reducer runs in order to detect the change, so keep this in mind for large states.
+///
+/// ## Example
+/// ```
+/// use redux_rs::{cascade_reducers, Reducer};
+///
+/// fn clamp(state: i32, _action: ()) -> i32 {
+///     state.min(10)
+/// }
+///
+/// fn log_clamped(state: i32, _action: ()) -> i32 {
+///     println!("The value was clamped");
+///     state
+/// }
+///
+/// let reducer = cascade_reducers(clamp, log_clamped);
+/// assert_eq!(reducer.reduce(15, ()), 10);
+/// ```
+pub fn cascade_reducers<State, Action, R1, R2>(first: R1, second: R2) -> impl Reducer<State, Action>
+where
+    State: Clone + PartialEq,
+    Action: Clone,
+    R1: Reducer<State, Action>,
+    R2: Reducer<State, Action>,
+{
+    move |state: State, action: Action| {
+        let previous = state.clone();
+        let state = first.reduce(state, action.clone());
+
+        if state != previous {
+            second.reduce(state, action)
+        } else {
+            state
+        }
+    }
+}
+
+/// Apply the actions one by one to the initial state and return the final state.
+///
+/// Everything happens synchronously on the calling thread, no store or runtime is involved.
+/// This is useful for short-lived computations, like a command line tool which runs a fixed sequence of actions.
+///
+/// ## Example
+/// ```
+/// use redux_rs::reduce_immediately;
+///
+/// fn reducer(state: u8, add: u8) -> u8 {
+///     state + add
+/// }
+///
+/// assert_eq!(reduce_immediately(reducer, 0, vec![1, 2, 3]), 6);
+/// ```
+pub fn reduce_immediately<State, Action, R>(reducer: R, initial: State, actions: Vec<Action>) -> State
+where
+    R: Reducer<State, Action>,
+{
+    actions.into_iter().fold(initial, |state, action| reducer.reduce(state, action))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Store;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    static SECOND_RUNS: AtomicUsize = AtomicUsize::new(0);
+
+    #[derive(Clone, Debug, Default, PartialEq)]
+    struct Counter {
+        value: u8,
+        changes: u8,
+    }
+
+    #[derive(Clone)]
+    enum CounterAction {
+        Increment,
+        Reset,
+    }
+
+    fn value_reducer(state: Counter, action: CounterAction) -> Counter {
+        match action {
+            CounterAction::Increment => Counter {
+                value: state.value + 1,
+                ..state
+            },
+            CounterAction::Reset => Counter { value: 0, ..state },
+        }
+    }
+
+    fn changes_reducer(state: Counter, _action: CounterAction) -> Counter {
+        SECOND_RUNS.fetch_add(1, Ordering::SeqCst);
+        Counter {
+            changes: state.changes + 1,
+            ..state
+        }
+    }
+
+    #[tokio::test]
+    async fn second_only_runs_on_change() {
+        let store = Store::new(cascade_reducers(value_reducer, changes_reducer));
+
+        store.dispatch(CounterAction::Reset).await;
+        assert_eq!(SECOND_RUNS.load(Ordering::SeqCst), 0);
+
+        store.dispatch(CounterAction::Increment).await;
+        store.dispatch(CounterAction::Increment).await;
+        assert_eq!(SECOND_RUNS.load(Ordering::SeqCst), 2);
+
+        store.dispatch(CounterAction::Reset).await;
+        store.dispatch(CounterAction::Reset).await;
+        assert_eq!(SECOND_RUNS.load(Ordering::SeqCst), 3);
+
+        assert_eq!(store.state_cloned().await, Counter { value: 0, changes: 3 });
+    }
+
+    #[tokio::test]
+    async fn reduce_immediately_matches_store() {
+        let actions = vec![
+            CounterAction::Increment,
+            CounterAction::Increment,
+            CounterAction::Reset,
+            CounterAction::Increment,
+            CounterAction::Increment,
+        ];
+
+        let state = reduce_immediately(value_reducer, Counter::default(), actions.clone());
+        assert_eq!(state, Counter { value: 2, changes: 0 });
+
+        let store = Store::new(value_reducer);
+        store.dispatch_all_and_wait(actions).await;
+        assert_eq!(store.state_cloned().await, state);
+    }
+}
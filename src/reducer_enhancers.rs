@@ -0,0 +1,589 @@
+use crate::Reducer;
+#[cfg(feature = "middleware_logger")]
+use std::fmt::Debug;
+use std::panic::{self, AssertUnwindSafe, UnwindSafe};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+/// Reducer decorator which logs the state before, the action and the state after every reduction.
+/// The logs are written with the `log` crate at debug level.
+///
+/// Unlike the `LoggerMiddleware` this runs inside the store, right around the reducer.
+///
+/// ## Example
+/// ```
+/// use redux_rs::{log_reducer, Store};
+///
+/// fn reducer(state: u8, add: u8) -> u8 {
+///     state + add
+/// }
+///
+/// # async fn async_test() {
+/// let store = Store::new(log_reducer(reducer));
+/// # }
+/// ```
+#[cfg(feature = "middleware_logger")]
+pub struct LoggingReducer<R> {
+    inner: R,
+}
+
+/// Wrap the reducer in a `LoggingReducer`
+#[cfg(feature = "middleware_logger")]
+pub fn log_reducer<R>(inner: R) -> LoggingReducer<R> {
+    LoggingReducer { inner }
+}
+
+#[cfg(feature = "middleware_logger")]
+impl<R, State, Action> Reducer<State, Action> for LoggingReducer<R>
+where
+    R: Reducer<State, Action>,
+    State: Debug + Clone,
+    Action: Debug,
+{
+    fn reduce(&self, state: State, action: Action) -> State {
+        let before = state.clone();
+        let action_description = format!("{:?}", action);
+
+        let after = self.inner.reduce(state, action);
+        log::debug!("before: {:?}, action: {}, after: {:?}", before, action_description, after);
+
+        after
+    }
+}
+
+/// The durations of the reductions done by a `TimingReducer`, in the order they happened.
+/// Every clone refers to the same records.
+#[derive(Clone, Default)]
+pub struct TimingRecords {
+    records: Arc<Mutex<Vec<Duration>>>,
+}
+
+impl TimingRecords {
+    /// Returns a copy of the recorded durations
+    pub fn durations(&self) -> Vec<Duration> {
+        self.records.lock().unwrap().clone()
+    }
+
+    fn record(&self, duration: Duration) {
+        self.records.lock().unwrap().push(duration);
+    }
+}
+
+/// Reducer decorator which records how long every reduction takes.
+///
+/// ## Example
+/// ```
+/// use redux_rs::{timing_reducer, Store};
+///
+/// fn reducer(state: u8, add: u8) -> u8 {
+///     state + add
+/// }
+///
+/// # #[tokio::main(flavor = "current_thread")]
+/// # async fn main() {
+/// let reducer = timing_reducer(reducer);
+/// let timings = reducer.records();
+///
+/// let store = Store::new(reducer);
+/// store.dispatch(1).await;
+///
+/// assert_eq!(timings.durations().len(), 1);
+/// # }
+/// ```
+pub struct TimingReducer<R> {
+    inner: R,
+    records: TimingRecords,
+}
+
+/// Wrap the reducer in a `TimingReducer`
+pub fn timing_reducer<R>(inner: R) -> TimingReducer<R> {
+    TimingReducer {
+        inner,
+        records: TimingRecords::default(),
+    }
+}
+
+impl<R> TimingReducer<R> {
+    /// Returns a handle to the recorded durations, keep it around before moving the reducer into a store
+    pub fn records(&self) -> TimingRecords {
+        self.records.clone()
+    }
+}
+
+impl<R, State, Action> Reducer<State, Action> for TimingReducer<R>
+where
+    R: Reducer<State, Action>,
+{
+    fn reduce(&self, state: State, action: Action) -> State {
+        let start = Instant::now();
+        let state = self.inner.reduce(state, action);
+        self.records.record(start.elapsed());
+
+        state
+    }
+}
+
+/// Reducer decorator which replaces the state with a fallback state when the reducer panics.
+///
+/// This keeps the store running when a reducer has a bug, at the cost of losing the state at the moment of the panic.
+/// With the `middleware_logger` feature the panic is logged with the `log` crate at error level.
+/// The panic hook still runs, so by default the panic is printed to stderr as well.
+///
+/// ## Example
+/// ```
+/// use redux_rs::{fallback_reducer, Reducer};
+///
+/// fn reducer(state: u8, add: u8) -> u8 {
+///     state.checked_add(add).expect("overflow")
+/// }
+///
+/// let reducer = fallback_reducer(reducer, 0);
+/// assert_eq!(reducer.reduce(250, 10), 0);
+/// assert_eq!(reducer.reduce(0, 10), 10);
+/// ```
+pub struct FallbackReducer<R, State> {
+    inner: R,
+    fallback: State,
+}
+
+/// Wrap the reducer in a `FallbackReducer`
+pub fn fallback_reducer<State, Action, R>(inner: R, fallback: State) -> FallbackReducer<R, State>
+where
+    R: Reducer<State, Action>,
+    State: Clone,
+{
+    FallbackReducer { inner, fallback }
+}
+
+impl<R, State, Action> Reducer<State, Action> for FallbackReducer<R, State>
+where
+    R: Reducer<State, Action>,
+    State: Clone + UnwindSafe,
+{
+    fn reduce(&self, state: State, action: Action) -> State {
+        // The state is consumed by the panicking reducer, the reducer and the action are not used after a panic
+        let inner = AssertUnwindSafe(&self.inner);
+        let action = AssertUnwindSafe(action);
+
+        match panic::catch_unwind(move || inner.reduce(state, action.0)) {
+            Ok(state) => state,
+            Err(_payload) => {
+                #[cfg(feature = "middleware_logger")]
+                log::error!(
+                    "reducer panicked, falling back to the fallback state: {}",
+                    crate::store_error::panic_message(_payload.as_ref())
+                );
+
+                self.fallback.clone()
+            }
+        }
+    }
+}
+
+/// Receives trace events for every reduction of a `TracedReducer`.
+pub trait ReducerTracer<State, Action>: Send + Sync {
+    /// Called with the state and the action right before the reducer runs
+    fn before_reduce(&self, state: &State, action: &Action);
+
+    /// Called after the reducer ran with the state before and after the reduction and how long the reducer took
+    fn after_reduce(&self, old_state: &State, new_state: &State, action: &Action, duration: Duration);
+}
+
+/// Reducer decorator which reports every reduction to a `ReducerTracer`.
+///
+/// The state and the action are cloned on every reduction, so the tracer can see the state before and after.
+///
+/// ## Example
+/// ```
+/// use redux_rs::{Reducer, TraceEvent, TracedReducer, VecReducerTracer};
+/// use std::sync::Arc;
+///
+/// fn reducer(state: u8, add: u8) -> u8 {
+///     state + add
+/// }
+///
+/// let tracer = Arc::new(VecReducerTracer::default());
+/// let reducer = TracedReducer::new(reducer, tracer.clone());
+/// reducer.reduce(1, 2);
+///
+/// assert_eq!(tracer.events().len(), 2);
+/// assert!(matches!(tracer.events()[1], TraceEvent::After { old_state: 1, new_state: 3, action: 2, .. }));
+/// ```
+pub struct TracedReducer<R, State, Action> {
+    inner: R,
+    tracer: Arc<dyn ReducerTracer<State, Action>>,
+}
+
+impl<R, State, Action> TracedReducer<R, State, Action>
+where
+    R: Reducer<State, Action>,
+{
+    /// Wrap the reducer, every reduction is reported to the tracer
+    pub fn new(inner: R, tracer: Arc<dyn ReducerTracer<State, Action>>) -> Self {
+        TracedReducer { inner, tracer }
+    }
+}
+
+impl<R, State, Action> Reducer<State, Action> for TracedReducer<R, State, Action>
+where
+    R: Reducer<State, Action>,
+    State: Clone,
+    Action: Clone,
+{
+    fn reduce(&self, state: State, action: Action) -> State {
+        self.tracer.before_reduce(&state, &action);
+
+        let old_state = state.clone();
+        let traced_action = action.clone();
+
+        let start = Instant::now();
+        let new_state = self.inner.reduce(state, action);
+        self.tracer.after_reduce(&old_state, &new_state, &traced_action, start.elapsed());
+
+        new_state
+    }
+}
+
+/// A trace event recorded by a `VecReducerTracer`
+#[derive(Clone, Debug, PartialEq)]
+pub enum TraceEvent<State, Action> {
+    Before {
+        state: State,
+        action: Action,
+    },
+    After {
+        old_state: State,
+        new_state: State,
+        action: Action,
+        duration: Duration,
+    },
+}
+
+/// Tracer which records every event in memory, mostly useful in tests.
+pub struct VecReducerTracer<State, Action> {
+    events: Mutex<Vec<TraceEvent<State, Action>>>,
+}
+
+impl<State, Action> Default for VecReducerTracer<State, Action> {
+    fn default() -> Self {
+        VecReducerTracer {
+            events: Mutex::new(Vec::new()),
+        }
+    }
+}
+
+impl<State, Action> VecReducerTracer<State, Action>
+where
+    State: Clone,
+    Action: Clone,
+{
+    /// Returns a copy of the recorded events, in the order they happened
+    pub fn events(&self) -> Vec<TraceEvent<State, Action>> {
+        self.events.lock().unwrap().clone()
+    }
+}
+
+impl<State, Action> ReducerTracer<State, Action> for VecReducerTracer<State, Action>
+where
+    State: Clone + Send,
+    Action: Clone + Send,
+{
+    fn before_reduce(&self, state: &State, action: &Action) {
+        self.events.lock().unwrap().push(TraceEvent::Before {
+            state: state.clone(),
+            action: action.clone(),
+        });
+    }
+
+    fn after_reduce(&self, old_state: &State, new_state: &State, action: &Action, duration: Duration) {
+        self.events.lock().unwrap().push(TraceEvent::After {
+            old_state: old_state.clone(),
+            new_state: new_state.clone(),
+            action: action.clone(),
+            duration,
+        });
+    }
+}
+
+/// Tracer which writes every event with the `log` crate at trace level.
+#[cfg(feature = "middleware_logger")]
+#[derive(Clone, Copy, Debug, Default)]
+pub struct LogReducerTracer;
+
+#[cfg(feature = "middleware_logger")]
+impl<State, Action> ReducerTracer<State, Action> for LogReducerTracer
+where
+    State: Debug,
+    Action: Debug,
+{
+    fn before_reduce(&self, state: &State, action: &Action) {
+        log::trace!("reducing action: {:?}, state: {:?}", action, state);
+    }
+
+    fn after_reduce(&self, old_state: &State, new_state: &State, action: &Action, duration: Duration) {
+        log::trace!(
+            "reduced action: {:?} in {:?}, before: {:?}, after: {:?}",
+            action,
+            duration,
+            old_state,
+            new_state
+        );
+    }
+}
+
+type Step<State, Action> = Box<dyn Fn(State, &Action) -> State + Send + Sync>;
+
+/// Reducer which runs pre-processing steps before and post-processing steps after the main reducer.
+///
+/// Steps receive the state and a reference to the action. Multiple steps run in the order they were added.
+/// The action is cloned for the post-processing steps, since the main reducer takes ownership of it.
+///
+/// ## Example
+/// ```
+/// use redux_rs::{Reducer, ReducerComposer};
+///
+/// fn reducer(state: i32, add: i32) -> i32 {
+///     state + add
+/// }
+///
+/// let reducer = ReducerComposer::new(reducer).after(|state: i32, _add: &i32| state.clamp(0, 100));
+/// assert_eq!(reducer.reduce(90, 20), 100);
+/// ```
+pub struct ReducerComposer<State, Action, R> {
+    main: R,
+    before: Vec<Step<State, Action>>,
+    after: Vec<Step<State, Action>>,
+}
+
+impl<State, Action, R> ReducerComposer<State, Action, R>
+where
+    R: Reducer<State, Action>,
+{
+    /// Compose around the main reducer, without steps before or after it
+    pub fn new(main: R) -> Self {
+        ReducerComposer {
+            main,
+            before: Vec::new(),
+            after: Vec::new(),
+        }
+    }
+
+    /// Add a step which runs before the main reducer
+    pub fn before<F>(mut self, pre: F) -> Self
+    where
+        F: Fn(State, &Action) -> State + Send + Sync + 'static,
+    {
+        self.before.push(Box::new(pre));
+        self
+    }
+
+    /// Add a step which runs after the main reducer
+    pub fn after<F>(mut self, post: F) -> Self
+    where
+        F: Fn(State, &Action) -> State + Send + Sync + 'static,
+    {
+        self.after.push(Box::new(post));
+        self
+    }
+}
+
+impl<State, Action, R> Reducer<State, Action> for ReducerComposer<State, Action, R>
+where
+    R: Reducer<State, Action>,
+    Action: Clone,
+{
+    fn reduce(&self, state: State, action: Action) -> State {
+        let state = self.before.iter().fold(state, |state, pre| pre(state, &action));
+
+        // The main reducer takes ownership of the action, it's only cloned when there are post-processing steps
+        if self.after.is_empty() {
+            return self.main.reduce(state, action);
+        }
+
+        let state = self.main.reduce(state, action.clone());
+        self.after.iter().fold(state, |state, post| post(state, &action))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Store;
+
+    #[derive(Clone, Debug, Default, PartialEq)]
+    struct Counter {
+        value: i32,
+    }
+
+    #[derive(Clone, Debug, PartialEq)]
+    enum CounterAction {
+        Increment,
+        Decrement,
+    }
+
+    fn counter_reducer(state: Counter, action: CounterAction) -> Counter {
+        match action {
+            CounterAction::Increment => Counter { value: state.value + 1 },
+            CounterAction::Decrement => Counter { value: state.value - 1 },
+        }
+    }
+
+    #[tokio::test]
+    async fn timing_records_every_dispatch() {
+        let reducer = timing_reducer(counter_reducer);
+        let records = reducer.records();
+
+        let store = Store::new(reducer);
+        store.dispatch(CounterAction::Increment).await;
+        store.dispatch(CounterAction::Decrement).await;
+        store.dispatch(CounterAction::Increment).await;
+
+        assert_eq!(records.durations().len(), 3);
+        assert_eq!(store.select(|state: &Counter| state.value).await, 1);
+    }
+
+    #[cfg(feature = "middleware_logger")]
+    #[tokio::test]
+    async fn logging_and_timing_decorators() {
+        use crate::testing::capture_logger::{capture_logs, captured_logs};
+
+        capture_logs();
+
+        let reducer = timing_reducer(log_reducer(counter_reducer));
+        let records = reducer.records();
+
+        let store = Store::new(reducer);
+        store.dispatch(CounterAction::Increment).await;
+        store.dispatch(CounterAction::Decrement).await;
+
+        assert_eq!(records.durations().len(), 2);
+        // The other decorators in this module log as well, only keep the logs of the log reducer
+        let logs: Vec<String> = captured_logs("redux_rs::reducer_enhancers")
+            .into_iter()
+            .filter(|log| log.starts_with("before: "))
+            .collect();
+        assert_eq!(
+            logs,
+            vec![
+                "before: Counter { value: 0 }, action: Increment, after: Counter { value: 1 }".to_string(),
+                "before: Counter { value: 1 }, action: Decrement, after: Counter { value: 0 }".to_string(),
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn composer_keeps_counter_in_range() {
+        fn add_reducer(state: i32, add: i32) -> i32 {
+            state.saturating_add(add)
+        }
+
+        fn clamp_input(state: i32, _add: &i32) -> i32 {
+            state.clamp(0, 100)
+        }
+
+        fn clamp_output(state: i32, _add: &i32) -> i32 {
+            state.clamp(0, 100)
+        }
+
+        let order = Arc::new(Mutex::new(Vec::new()));
+        let (before_order, after_order) = (order.clone(), order.clone());
+        let reducer = ReducerComposer::new(add_reducer)
+            .before(clamp_input)
+            .before(move |state, _: &i32| {
+                before_order.lock().unwrap().push("before");
+                state
+            })
+            .after(clamp_output)
+            .after(move |state, _: &i32| {
+                after_order.lock().unwrap().push("after");
+                state
+            });
+
+        let store = Store::new_with_state(reducer, 500);
+        for add in [-1000, 30, 90, 1000, -20, i32::MAX] {
+            store.dispatch(add).await;
+            let value = store.select(|state: &i32| *state).await;
+            assert!((0..=100).contains(&value), "{} is out of range", value);
+        }
+
+        assert_eq!(store.select(|state: &i32| *state).await, 100);
+        assert_eq!(order.lock().unwrap()[..2], ["before", "after"]);
+    }
+
+    fn fragile_reducer(state: Counter, action: CounterAction) -> Counter {
+        if matches!(action, CounterAction::Decrement) && state.value == 0 {
+            panic!("counter can't go below zero");
+        }
+        counter_reducer(state, action)
+    }
+
+    #[tokio::test]
+    async fn fallback_replaces_panicking_reduction() {
+        let store = Store::new_with_state(fallback_reducer(fragile_reducer, Counter { value: 10 }), Counter { value: 1 });
+
+        store.dispatch(CounterAction::Decrement).await;
+        assert_eq!(store.state_cloned().await, Counter { value: 0 });
+
+        store.dispatch(CounterAction::Decrement).await;
+        assert_eq!(store.state_cloned().await, Counter { value: 10 });
+
+        store.dispatch(CounterAction::Increment).await;
+        store.dispatch(CounterAction::Decrement).await;
+        store.dispatch(CounterAction::Decrement).await;
+        assert_eq!(store.state_cloned().await, Counter { value: 9 });
+    }
+
+    #[tokio::test]
+    async fn traced_reducer_reports_state_pairs() {
+        let tracer = Arc::new(VecReducerTracer::default());
+        let store = Store::new(TracedReducer::new(counter_reducer, tracer.clone()));
+
+        store.dispatch(CounterAction::Increment).await;
+        store.dispatch(CounterAction::Decrement).await;
+
+        let events = tracer.events();
+        assert_eq!(events.len(), 4);
+        assert_eq!(
+            events[0],
+            TraceEvent::Before {
+                state: Counter { value: 0 },
+                action: CounterAction::Increment
+            }
+        );
+
+        let pairs: Vec<_> = events
+            .iter()
+            .filter_map(|event| match event {
+                TraceEvent::After {
+                    old_state, new_state, action, ..
+                } => Some((old_state.value, new_state.value, action.clone())),
+                TraceEvent::Before { .. } => None,
+            })
+            .collect();
+        assert_eq!(pairs, vec![(0, 1, CounterAction::Increment), (1, 0, CounterAction::Decrement)]);
+    }
+
+    #[test]
+    fn traced_reducer_records_duration() {
+        fn slow_reducer(state: i32, _action: ()) -> i32 {
+            std::thread::sleep(Duration::from_millis(5));
+            state + 1
+        }
+
+        let tracer = Arc::new(VecReducerTracer::default());
+        let reducer = TracedReducer::new(slow_reducer, tracer.clone());
+        assert_eq!(reducer.reduce(0, ()), 1);
+
+        match &tracer.events()[1] {
+            TraceEvent::After { duration, .. } => assert!(*duration >= Duration::from_millis(5)),
+            event => panic!("unexpected event {:?}", event),
+        }
+    }
+
+    #[cfg(feature = "middleware_logger")]
+    #[test]
+    fn log_tracer_is_a_reducer_tracer() {
+        let reducer = TracedReducer::new(counter_reducer, Arc::new(LogReducerTracer));
+        assert_eq!(reducer.reduce(Counter::default(), CounterAction::Increment), Counter { value: 1 });
+    }
+}
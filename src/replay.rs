@@ -0,0 +1,132 @@
+use crate::{Reducer, Store};
+use std::io;
+use std::path::PathBuf;
+
+impl<State, Action, RootReducer> Store<State, Action, RootReducer>
+where
+    Action: Send + 'static,
+    RootReducer: Reducer<State, Action> + Send + 'static,
+    State: Send + 'static,
+{
+    /// Create a store with the default state and replay the actions of a log file on it.
+    /// Returns the store, for further interaction, together with the state after the last action.
+    ///
+    /// Every non-empty line of the file is one action, `deserialize_action` parses a line into an action.
+    /// This is the tool to reproduce a production issue locally from the actions logged in production.
+    ///
+    /// ## Example
+    /// ```
+    /// use redux_rs::Store;
+    ///
+    /// fn reducer(state: u32, add: u32) -> u32 {
+    ///     state + add
+    /// }
+    ///
+    /// # #[tokio::main(flavor = "current_thread")]
+    /// # async fn main() -> std::io::Result<()> {
+    /// let path = std::env::temp_dir().join(format!("redux_rs_replay_doc_{}.log", std::process::id()));
+    /// std::fs::write(&path, "1\n2\n3\n")?;
+    ///
+    /// let (store, state) = Store::replay_from_log_file(reducer, path.clone(), |line| line.parse().unwrap()).await?;
+    /// assert_eq!(state, 6);
+    /// # std::fs::remove_file(path)?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn replay_from_log_file<F>(root_reducer: RootReducer, path: PathBuf, deserialize_action: F) -> io::Result<(Self, State)>
+    where
+        F: Fn(&str) -> Action,
+        State: Clone + Default,
+    {
+        let log = tokio::fs::read_to_string(path).await?;
+
+        let store = Store::new(root_reducer);
+        let actions = log.lines().filter(|line| !line.trim().is_empty()).map(deserialize_action);
+        store.dispatch_all_and_wait(actions).await;
+
+        let state = store.select(|state: &State| state.clone()).await;
+        Ok((store, state))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Clone, Debug, Default, PartialEq)]
+    struct Cart {
+        items: Vec<String>,
+        total: u32,
+    }
+
+    enum CartAction {
+        Add { item: String, price: u32 },
+        Clear,
+    }
+
+    fn cart_reducer(state: Cart, action: CartAction) -> Cart {
+        match action {
+            CartAction::Add { item, price } => {
+                let mut items = state.items;
+                items.push(item);
+                Cart {
+                    items,
+                    total: state.total + price,
+                }
+            }
+            CartAction::Clear => Cart::default(),
+        }
+    }
+
+    // Minimal parser for the log lines of this test, a real application would use serde_json
+    fn parse_action(line: &str) -> CartAction {
+        let field = |name: &str| {
+            let start = line.find(&format!("\"{}\":", name)).unwrap() + name.len() + 3;
+            let rest = &line[start..];
+            let end = rest.find([',', '}']).unwrap();
+            rest[..end].trim().trim_matches('"').to_string()
+        };
+
+        match field("type").as_str() {
+            "Add" => CartAction::Add {
+                item: field("item"),
+                price: field("price").parse().unwrap(),
+            },
+            "Clear" => CartAction::Clear,
+            other => panic!("unknown action {}", other),
+        }
+    }
+
+    #[tokio::test]
+    async fn replay_json_log() {
+        let path = std::env::temp_dir().join(format!("redux_rs_replay_{}.log", std::process::id()));
+        let log = [
+            r#"{"type": "Add", "item": "apple", "price": 3}"#,
+            r#"{"type": "Clear"}"#,
+            r#"{"type": "Add", "item": "pear", "price": 4}"#,
+            "",
+            r#"{"type": "Add", "item": "plum", "price": 2}"#,
+        ];
+        std::fs::write(&path, log.join("\n")).unwrap();
+
+        let (store, state) = Store::replay_from_log_file(cart_reducer, path.clone(), parse_action).await.unwrap();
+        std::fs::remove_file(path).unwrap();
+
+        let expected = Cart {
+            items: vec!["pear".to_string(), "plum".to_string()],
+            total: 6,
+        };
+        assert_eq!(state, expected);
+
+        store.dispatch(CartAction::Clear).await;
+        assert_eq!(store.state_cloned().await, Cart::default());
+    }
+
+    #[tokio::test]
+    async fn missing_log_file() {
+        let path = std::env::temp_dir().join(format!("redux_rs_replay_missing_{}.log", std::process::id()));
+        let result = Store::replay_from_log_file(cart_reducer, path, parse_action).await;
+
+        assert_eq!(result.err().map(|err| err.kind()), Some(io::ErrorKind::NotFound));
+    }
+}
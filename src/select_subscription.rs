@@ -0,0 +1,101 @@
+use crate::Selector;
+use futures_core::Stream;
+use std::future::{poll_fn, Future};
+use std::marker::PhantomData;
+use std::pin::Pin;
+use std::sync::Mutex;
+use std::task::{Context, Poll};
+use tokio::sync::mpsc::{unbounded_channel, UnboundedReceiver, UnboundedSender};
+
+/// Selected value of the state together with its changes, see `Store::select_subscribe`.
+///
+/// Awaiting the subscription resolves to the current value.
+/// As a stream it yields the current value, unless it was already awaited, followed by every change.
+/// A value is only yielded when it differs from the previous one.
+/// The stream ends when the store is dropped, dropping the subscription unsubscribes from the store.
+pub struct SelectSubscription<State, S>
+where
+    S: Selector<State>,
+{
+    rx: UnboundedReceiver<S::Result>,
+    last: Option<S::Result>,
+    _types: PhantomData<fn(&State)>,
+}
+
+impl<State, S> SelectSubscription<State, S>
+where
+    S: Selector<State>,
+    S::Result: Clone + PartialEq + Send + 'static,
+{
+    // Returns the subscriber which feeds the subscription, it keeps track of the last value to skip duplicates
+    pub(crate) fn channel(selector: S) -> (impl Fn(&State) + Send, UnboundedSender<S::Result>, Self)
+    where
+        S: Send,
+    {
+        let (tx, rx) = unbounded_channel();
+        let subscriber_tx = tx.clone();
+        let last_sent = Mutex::new(None);
+
+        let subscriber = move |state: &State| {
+            let value = selector.select(state);
+
+            let mut last_sent = last_sent.lock().unwrap();
+            if last_sent.as_ref() != Some(&value) {
+                let _ = subscriber_tx.send(value.clone());
+                *last_sent = Some(value);
+            }
+        };
+
+        let subscription = SelectSubscription {
+            rx,
+            last: None,
+            _types: Default::default(),
+        };
+
+        (subscriber, tx, subscription)
+    }
+
+    /// Receive the next distinct value, returns None when the store is dropped
+    pub async fn next(&mut self) -> Option<S::Result> {
+        poll_fn(|cx| Pin::new(&mut *self).poll_next(cx)).await
+    }
+}
+
+// The subscription is never pin projected
+impl<State, S> Unpin for SelectSubscription<State, S> where S: Selector<State> {}
+
+impl<State, S> Future for SelectSubscription<State, S>
+where
+    S: Selector<State>,
+    S::Result: Clone,
+{
+    type Output = S::Result;
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        if let Some(last) = &self.last {
+            return Poll::Ready(last.clone());
+        }
+
+        // The store sends the current value as soon as the subscription is registered
+        self.as_mut()
+            .poll_next(cx)
+            .map(|value| value.expect("the store sends the current value before it shuts down"))
+    }
+}
+
+impl<State, S> Stream for SelectSubscription<State, S>
+where
+    S: Selector<State>,
+    S::Result: Clone,
+{
+    type Item = S::Result;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let value = self.rx.poll_recv(cx);
+        if let Poll::Ready(Some(value)) = &value {
+            self.last = Some(value.clone());
+        }
+
+        value
+    }
+}
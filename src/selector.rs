@@ -62,6 +62,30 @@ pub trait Selector<State> {
     type Result;
 
     fn select(&self, state: &State) -> Self::Result;
+
+    /// Alias for `select`, reads more naturally when the state is already at hand.
+    fn select_on(&self, state: &State) -> Self::Result {
+        self.select(state)
+    }
+}
+
+/// Apply a selector to a state you already hold a reference to, for example inside a subscriber.
+/// This does not go through the store and never blocks.
+///
+/// ## Example
+/// ```
+/// use redux_rs::try_select_sync;
+///
+/// struct Counter(i8);
+///
+/// let counter = Counter(42);
+/// assert_eq!(try_select_sync(&counter, |counter: &Counter| counter.0), 42);
+/// ```
+pub fn try_select_sync<State, S>(state: &State, selector: S) -> S::Result
+where
+    S: Selector<State>,
+{
+    selector.select_on(state)
 }
 
 impl<F, State, Result> Selector<State> for F
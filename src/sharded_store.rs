@@ -0,0 +1,196 @@
+use crate::{Reducer, Store};
+use async_trait::async_trait;
+
+/// Index of a shard in a `ShardedStore`, the first shard has id 0
+pub type ShardId = usize;
+
+/// A fixed set of stores which share an action type, see `ShardedStore`.
+///
+/// Implemented for tuples of 2 up to 4 stores.
+#[async_trait]
+pub trait ShardMap<Action>: Send + Sync {
+    /// The number of shards
+    fn shard_count(&self) -> usize;
+
+    /// Dispatch the action to the shard with the given id.
+    /// Panics if there's no shard with that id.
+    async fn dispatch_to(&self, shard: ShardId, action: Action);
+}
+
+macro_rules! impl_shard_map {
+    ($len:expr; $($index:tt => $state:ident, $reducer:ident),+) => {
+        #[async_trait]
+        impl<Action, $($state, $reducer),+> ShardMap<Action> for ($(Store<$state, Action, $reducer>,)+)
+        where
+            Action: Send + 'static,
+            $(
+                $state: Send + 'static,
+                $reducer: Reducer<$state, Action> + Send + Sync + 'static,
+            )+
+        {
+            fn shard_count(&self) -> usize {
+                $len
+            }
+
+            async fn dispatch_to(&self, shard: ShardId, action: Action) {
+                match shard {
+                    $($index => self.$index.dispatch(action).await,)+
+                    _ => panic!("there's no shard with id {}, the store has {} shards", shard, $len),
+                }
+            }
+        }
+    };
+}
+
+impl_shard_map!(2; 0 => S0, R0, 1 => S1, R1);
+impl_shard_map!(3; 0 => S0, R0, 1 => S1, R1, 2 => S2, R2);
+impl_shard_map!(4; 0 => S0, R0, 1 => S1, R1, 2 => S2, R2, 3 => S3, R3);
+
+/// Store which splits its state into independent shards, every shard is a `Store` with its own reducer and worker.
+///
+/// Actions are routed to a single shard, so actions for different shards are reduced concurrently.
+/// Use this for large states with independent parts, the shards can't see each other's state.
+///
+/// ## Example
+/// ```
+/// use redux_rs::{ShardedStore, Store};
+///
+/// enum Action {
+///     AddUser(String),
+///     IncrementVisits,
+/// }
+///
+/// fn users_reducer(state: Vec<String>, action: Action) -> Vec<String> {
+///     let mut state = state;
+///     if let Action::AddUser(user) = action {
+///         state.push(user);
+///     }
+///     state
+/// }
+///
+/// fn visits_reducer(state: u64, action: Action) -> u64 {
+///     match action {
+///         Action::IncrementVisits => state + 1,
+///         _ => state,
+///     }
+/// }
+///
+/// # #[tokio::main(flavor = "current_thread")]
+/// # async fn main() {
+/// let store = ShardedStore::new((Store::new(users_reducer), Store::new(visits_reducer)), |action: &Action| match action {
+///     Action::AddUser(_) => 0,
+///     Action::IncrementVisits => 1,
+/// });
+///
+/// store.dispatch(Action::IncrementVisits).await;
+/// assert_eq!(store.shards().1.select(|visits: &u64| *visits).await, 1);
+/// # }
+/// ```
+pub struct ShardedStore<Shards, Action> {
+    shards: Shards,
+    route: fn(&Action) -> ShardId,
+}
+
+impl<Shards, Action> ShardedStore<Shards, Action>
+where
+    Shards: ShardMap<Action>,
+{
+    /// Create a sharded store, `route` returns the id of the shard which handles an action
+    pub fn new(shards: Shards, route: fn(&Action) -> ShardId) -> Self {
+        ShardedStore { shards, route }
+    }
+
+    /// Dispatch the action to the shard it's routed to
+    pub async fn dispatch(&self, action: Action) {
+        let shard = (self.route)(&action);
+        self.shards.dispatch_to(shard, action).await
+    }
+
+    /// The shards, select from a shard through its tuple field
+    pub fn shards(&self) -> &Shards {
+        &self.shards
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::Arc;
+    use std::time::{Duration, Instant};
+
+    #[derive(Clone, Copy)]
+    enum Action {
+        Left,
+        Right,
+    }
+
+    fn left_reducer(state: u32, action: Action) -> u32 {
+        match action {
+            Action::Left => state + 1,
+            Action::Right => state,
+        }
+    }
+
+    fn right_reducer(state: u32, action: Action) -> u32 {
+        match action {
+            Action::Left => state,
+            Action::Right => state + 1,
+        }
+    }
+
+    fn route(action: &Action) -> ShardId {
+        match action {
+            Action::Left => 0,
+            Action::Right => 1,
+        }
+    }
+
+    // Waits until both shards reduce at the same time, returns false if that doesn't happen.
+    // The deadline only keeps shards which reduce one after the other from hanging the test.
+    fn rendezvous(active: &AtomicUsize) -> bool {
+        active.fetch_add(1, Ordering::SeqCst);
+        let deadline = Instant::now() + Duration::from_secs(10);
+        while active.load(Ordering::SeqCst) < 2 {
+            if Instant::now() > deadline {
+                return false;
+            }
+            std::thread::yield_now();
+        }
+        true
+    }
+
+    // Only counts the action when the other shard was reducing at the same time
+    fn concurrent(active: &Arc<AtomicUsize>, reducer: fn(u32, Action) -> u32) -> impl Fn(u32, Action) -> u32 + Send + Sync + 'static {
+        let active = active.clone();
+        move |state: u32, action: Action| {
+            if rendezvous(&active) {
+                reducer(state, action)
+            } else {
+                state
+            }
+        }
+    }
+
+    #[tokio::test(flavor = "multi_thread", worker_threads = 3)]
+    async fn shards_reduce_concurrently() {
+        let active = Arc::new(AtomicUsize::new(0));
+        let sharded = ShardedStore::new(
+            (Store::new(concurrent(&active, left_reducer)), Store::new(concurrent(&active, right_reducer))),
+            route,
+        );
+
+        tokio::join!(sharded.dispatch(Action::Left), sharded.dispatch(Action::Right));
+
+        assert_eq!(sharded.shards().0.select(|state: &u32| *state).await, 1);
+        assert_eq!(sharded.shards().1.select(|state: &u32| *state).await, 1);
+        assert_eq!(sharded.shards().shard_count(), 2);
+    }
+
+    #[tokio::test]
+    #[should_panic(expected = "there's no shard with id 2")]
+    async fn unknown_shard() {
+        let sharded = ShardedStore::new((Store::new(left_reducer), Store::new(right_reducer)), |_: &Action| 2);
+        sharded.dispatch(Action::Left).await;
+    }
+}
@@ -0,0 +1,30 @@
+use std::error::Error;
+use std::fmt::{Display, Formatter};
+
+/// A copy of the state together with the version of the store at the moment it was taken.
+///
+/// The version is incremented for every reduced action, even one which leaves the state as it was, see `Store::state_version`.
+#[derive(Clone, Debug, PartialEq)]
+pub struct VersionedSnapshot<State> {
+    pub version: u64,
+    pub state: State,
+}
+
+/// Returned when a snapshot can't be restored
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum ConflictError {
+    /// An action was reduced or the state was replaced since the snapshot was taken
+    VersionMismatch { current: u64, snapshot: u64 },
+}
+
+impl Display for ConflictError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ConflictError::VersionMismatch { current, snapshot } => {
+                write!(f, "snapshot version {} does not match the current version {}", snapshot, current)
+            }
+        }
+    }
+}
+
+impl Error for ConflictError {}
@@ -0,0 +1,34 @@
+use std::future::Future;
+use std::pin::Pin;
+use std::task::{Context, Poll};
+use tokio::sync::oneshot;
+
+/// Future which resolves with the state after the next change, see `Store::watch_for_change`.
+/// It resolves with None if the store is dropped before the state changes.
+///
+/// The watcher doesn't depend on the Tokio executor, it can be awaited or polled from any runtime
+/// like `futures::executor::block_on` or a `LocalPool`. The store itself still runs on Tokio.
+pub struct StateChangeWatcher<State> {
+    rx: oneshot::Receiver<State>,
+}
+
+impl<State> StateChangeWatcher<State> {
+    pub(crate) fn new(rx: oneshot::Receiver<State>) -> Self {
+        StateChangeWatcher { rx }
+    }
+
+    /// Poll for the changed state, the waker of the context is woken when the state changes.
+    /// Returns None if the store was dropped before the state changed.
+    /// Once the state was returned the watcher is done, polling it again panics.
+    pub fn poll_for_change(&mut self, cx: &mut Context<'_>) -> Poll<Option<State>> {
+        Pin::new(&mut self.rx).poll(cx).map(Result::ok)
+    }
+}
+
+impl<State> Future for StateChangeWatcher<State> {
+    type Output = Option<State>;
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        self.poll_for_change(cx)
+    }
+}
@@ -0,0 +1,150 @@
+use crate::{ChannelReceiver, Subscriber};
+use std::sync::Mutex;
+use tokio::sync::mpsc::{unbounded_channel, UnboundedSender};
+
+/// Health of a state, as reported by the check of a `StateMonitor`
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum HealthStatus {
+    Healthy,
+    Degraded(String),
+    Unhealthy(String),
+}
+
+/// Subscriber which checks the health of every new state and reports when the health changes.
+///
+/// The state is assumed to be healthy when the monitor is created,
+/// only transitions are sent on the `HealthReceiver`, a status is never sent twice in a row.
+///
+/// ## Example
+/// ```
+/// use redux_rs::{HealthStatus, StateMonitor, Store};
+///
+/// fn reducer(balance: i64, amount: i64) -> i64 {
+///     balance + amount
+/// }
+///
+/// fn check_balance(balance: &i64) -> HealthStatus {
+///     if *balance < 0 {
+///         HealthStatus::Unhealthy(format!("negative balance: {}", balance))
+///     } else {
+///         HealthStatus::Healthy
+///     }
+/// }
+///
+/// # #[tokio::main(flavor = "current_thread")]
+/// # async fn main() {
+/// let store = Store::new(reducer);
+/// let (monitor, mut health) = StateMonitor::new(check_balance);
+/// store.subscribe(monitor).await;
+///
+/// store.dispatch(-10).await;
+/// assert_eq!(health.recv().await, Some(HealthStatus::Unhealthy("negative balance: -10".to_string())));
+/// # }
+/// ```
+pub struct StateMonitor<State> {
+    check: fn(&State) -> HealthStatus,
+    status: Mutex<HealthStatus>,
+    tx: UnboundedSender<HealthStatus>,
+}
+
+impl<State> StateMonitor<State> {
+    /// Create a monitor running `check` on every new state, the status changes are received on the `HealthReceiver`
+    pub fn new(check: fn(&State) -> HealthStatus) -> (Self, HealthReceiver) {
+        let (tx, rx) = unbounded_channel();
+        let monitor = StateMonitor {
+            check,
+            status: Mutex::new(HealthStatus::Healthy),
+            tx,
+        };
+
+        (monitor, ChannelReceiver::new(rx))
+    }
+}
+
+impl<State> Subscriber<State> for StateMonitor<State> {
+    fn notify(&self, state: &State) {
+        let status = (self.check)(state);
+
+        let mut current = self.status.lock().unwrap();
+        if *current != status {
+            *current = status.clone();
+            // Nobody listening anymore is fine, the monitor keeps running until the store is dropped
+            let _ = self.tx.send(status);
+        }
+    }
+}
+
+/// Stream of the health changes reported by a `StateMonitor`.
+///
+/// The stream ends when the monitor is dropped, which happens when the store shuts down.
+pub type HealthReceiver = ChannelReceiver<HealthStatus>;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Store;
+    use futures::FutureExt;
+
+    #[derive(Default)]
+    struct Payments {
+        balance: i64,
+    }
+
+    enum PaymentAction {
+        Deposit(i64),
+        Withdraw(i64),
+    }
+
+    fn payment_reducer(state: Payments, action: PaymentAction) -> Payments {
+        match action {
+            PaymentAction::Deposit(amount) => Payments {
+                balance: state.balance + amount,
+            },
+            PaymentAction::Withdraw(amount) => Payments {
+                balance: state.balance - amount,
+            },
+        }
+    }
+
+    fn check_payments(state: &Payments) -> HealthStatus {
+        match state.balance {
+            balance if balance < 0 => HealthStatus::Unhealthy(format!("negative balance: {}", balance)),
+            0 => HealthStatus::Degraded("empty balance".to_string()),
+            _ => HealthStatus::Healthy,
+        }
+    }
+
+    #[tokio::test]
+    async fn monitor_reports_unhealthy_state() {
+        let store = Store::new(payment_reducer);
+        let (monitor, mut health) = StateMonitor::new(check_payments);
+        store.subscribe(monitor).await;
+
+        store.dispatch(PaymentAction::Deposit(100)).await;
+        store.dispatch(PaymentAction::Withdraw(150)).await;
+
+        assert_eq!(health.recv().await, Some(HealthStatus::Unhealthy("negative balance: -50".to_string())));
+    }
+
+    #[tokio::test]
+    async fn monitor_only_reports_transitions() {
+        let store = Store::new(payment_reducer);
+        let mut health = store.with_state_monitor(check_payments).await;
+
+        store.dispatch(PaymentAction::Deposit(100)).await;
+        store.dispatch(PaymentAction::Deposit(50)).await;
+        store.dispatch(PaymentAction::Withdraw(150)).await;
+        store.dispatch(PaymentAction::Withdraw(10)).await;
+        store.dispatch(PaymentAction::Withdraw(10)).await;
+        store.dispatch(PaymentAction::Deposit(120)).await;
+
+        assert_eq!(health.recv().await, Some(HealthStatus::Degraded("empty balance".to_string())));
+        assert_eq!(health.recv().await, Some(HealthStatus::Unhealthy("negative balance: -10".to_string())));
+        assert_eq!(health.recv().await, Some(HealthStatus::Unhealthy("negative balance: -20".to_string())));
+        assert_eq!(health.recv().await, Some(HealthStatus::Healthy));
+        assert!(health.recv().now_or_never().is_none());
+
+        drop(store);
+        assert_eq!(health.recv().await, None);
+    }
+}
@@ -0,0 +1,143 @@
+use crate::composed_middleware::impl_middleware_bitor;
+use crate::{MiddleWare, StoreApi};
+use async_trait::async_trait;
+use std::sync::{Arc, Mutex};
+
+/// Middleware with mutable state, without putting the state behind a lock yourself.
+///
+/// For every action the `dispatch` function runs with exclusive access to the state.
+/// It returns the action to pass on to the inner store, or None to drop the action.
+/// The lock on the state is only held while the function runs, it's released before the action is dispatched to the inner store.
+/// This is all that's needed for middlewares like rate limiters or deduplication.
+///
+/// ## Example
+/// ```
+/// use redux_rs::{StatefulMiddleware, Store, StoreApi};
+///
+/// fn reducer(state: u32, add: u32) -> u32 {
+///     state + add
+/// }
+///
+/// // Drop an action which repeats the previous one
+/// fn dedup(last: &mut Option<u32>, action: u32) -> Option<u32> {
+///     if *last == Some(action) {
+///         return None;
+///     }
+///
+///     *last = Some(action);
+///     Some(action)
+/// }
+///
+/// # #[tokio::main(flavor = "current_thread")]
+/// # async fn main() {
+/// let store = Store::new(reducer).wrap(StatefulMiddleware::new(None, dedup)).await;
+/// store.dispatch(1u32).await;
+/// store.dispatch(1u32).await;
+///
+/// assert_eq!(store.state_cloned().await, 1);
+/// # }
+/// ```
+pub struct StatefulMiddleware<S, Action> {
+    state: Arc<Mutex<S>>,
+    dispatch: fn(&mut S, Action) -> Option<Action>,
+}
+
+impl<S, Action> StatefulMiddleware<S, Action> {
+    /// Create the middleware with its initial state, `dispatch` runs for every action with exclusive access to the state
+    pub fn new(state: S, dispatch: fn(&mut S, Action) -> Option<Action>) -> Self {
+        StatefulMiddleware {
+            state: Arc::new(Mutex::new(state)),
+            dispatch,
+        }
+    }
+
+    /// Shared handle to the state of the middleware, to inspect it after the middleware is wrapped around a store
+    pub fn state(&self) -> Arc<Mutex<S>> {
+        self.state.clone()
+    }
+}
+
+impl_middleware_bitor!(StatefulMiddleware<S, Action>);
+
+#[async_trait]
+impl<S, State, Action, Inner> MiddleWare<State, Action, Inner> for StatefulMiddleware<S, Action>
+where
+    S: Send,
+    State: Send + 'static,
+    Action: Send + 'static,
+    Inner: StoreApi<State, Action> + Send + Sync,
+{
+    async fn dispatch(&self, action: Action, inner: &Arc<Inner>) {
+        let action = (self.dispatch)(&mut self.state.lock().unwrap(), action);
+
+        if let Some(action) = action {
+            inner.dispatch(action).await;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Store;
+    use std::time::Duration;
+    use tokio::time::Instant;
+
+    struct RateLimit {
+        window_start: Instant,
+        in_window: u32,
+        dropped: u32,
+    }
+
+    const LIMIT: u32 = 3;
+    const WINDOW: Duration = Duration::from_millis(100);
+
+    fn rate_limit(state: &mut RateLimit, action: i32) -> Option<i32> {
+        if state.window_start.elapsed() >= WINDOW {
+            state.window_start = Instant::now();
+            state.in_window = 0;
+        }
+
+        if state.in_window == LIMIT {
+            state.dropped += 1;
+            return None;
+        }
+
+        state.in_window += 1;
+        Some(action)
+    }
+
+    fn counter_reducer(state: i32, add: i32) -> i32 {
+        state + add
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn rate_limiter() {
+        let limiter = StatefulMiddleware::new(
+            RateLimit {
+                window_start: Instant::now(),
+                in_window: 0,
+                dropped: 0,
+            },
+            rate_limit,
+        );
+        let limiter_state = limiter.state();
+        let store = Store::new(counter_reducer).wrap(limiter).await;
+
+        for _ in 0..5 {
+            store.dispatch(1).await;
+        }
+
+        assert_eq!(store.state_cloned().await, 3);
+        assert_eq!(limiter_state.lock().unwrap().dropped, 2);
+
+        // A new window lets actions through again
+        tokio::time::advance(WINDOW).await;
+        for _ in 0..5 {
+            store.dispatch(1).await;
+        }
+
+        assert_eq!(store.state_cloned().await, 6);
+        assert_eq!(limiter_state.lock().unwrap().dropped, 4);
+    }
+}
@@ -1,14 +1,26 @@
 use async_trait::async_trait;
+use std::future::Future;
 use std::marker::PhantomData;
+use std::sync::{Arc, Mutex};
+use tokio::sync::oneshot;
 use tokio::task::JoinHandle;
 
 use crate::{
     middleware::{MiddleWare, StoreApi, StoreWithMiddleware},
-    Reducer, Selector, Subscriber,
+    store_error::ErrorChannel,
+    ActionStream, ConflictError, ErrorReceiver, HealthReceiver, HealthStatus, ObserveActions, Reducer, SelectSubscription, Selector, StateChangeWatcher,
+    StateDiff, StateMonitor, Subscriber, TransitionError, VersionedSnapshot,
 };
 
 mod worker;
-use worker::{Address, Dispatch, Select, StateWorker, Subscribe};
+#[cfg(debug_assertions)]
+use crate::SubscriberInfo;
+#[cfg(debug_assertions)]
+use worker::ListSubscribers;
+use worker::{
+    AddTransform, Address, Dispatch, DispatchBatch, DispatchCas, DispatchRollback, ForceNotify, Intercept, Observe, ReplaceState, RestoreSnapshot, Select,
+    StateVersion, StateWorker, Subscribe, SubscribeBatch, SubscribeSeeded, SubscribeUntil, TakeSnapshot, TransitionTo,
+};
 
 /// The store is the heart of any redux application, it contains the state of the application.
 ///
@@ -42,9 +54,37 @@ where
 
     /// Create a new store with the given root reducer and the provided state
     pub fn new_with_state(root_reducer: RootReducer, state: State) -> Self {
-        let mut worker = StateWorker::new(root_reducer, state);
-        let worker_address = worker.address();
+        let (worker, worker_address) = StateWorker::new(root_reducer, state);
+        Self::spawn(worker, worker_address)
+    }
+
+    /// Create a new store with the provided state, which reports errors on the returned receiver instead of crashing.
+    ///
+    /// When the reducer or a post reduce transform panics, the panic is caught and sent on the receiver together with the action.
+    /// The state is left as it was before the action and the subscribers are not notified, the store keeps handling actions.
+    /// To be able to restore the state it's cloned before every reduction, which has a cost for large states.
+    /// Panics are still reported to the panic hook, which prints them to stderr by default.
+    ///
+    /// This covers every reducer call of the store, including the ones of `dispatch_and_rollback` and `transition_to`.
+    /// Panics in subscribers, selectors and middleware are not caught.
+    /// To report invalid states as well, use `new_with_validator_and_error_channel`.
+    pub fn new_with_error_channel(root_reducer: RootReducer, state: State) -> (Self, ErrorReceiver<Action>)
+    where
+        State: Clone,
+        Action: Clone,
+    {
+        let (channel, receiver) = ErrorChannel::new();
+        (Self::new_with_channel(root_reducer, state, channel), receiver)
+    }
+
+    pub(crate) fn new_with_channel(root_reducer: RootReducer, state: State, channel: ErrorChannel<State, Action>) -> Self {
+        let (mut worker, worker_address) = StateWorker::new(root_reducer, state);
+        worker.set_error_channel(channel);
+
+        Self::spawn(worker, worker_address)
+    }
 
+    fn spawn(mut worker: StateWorker<State, Action, RootReducer>, worker_address: Address<State, Action, RootReducer>) -> Self {
         let _worker_handle = tokio::spawn(async move {
             worker.run().await;
         });
@@ -57,6 +97,46 @@ where
         }
     }
 
+    /// Create a new shared store with the given root reducer and default state.
+    /// `Arc<Store>` implements `StoreApi` as well, so the shared store can be used like any other store.
+    pub fn new_arc(root_reducer: RootReducer) -> Arc<Self>
+    where
+        State: Default,
+    {
+        Arc::new(Self::new(root_reducer))
+    }
+
+    /// Create a new shared store with the given root reducer and the provided state
+    pub fn new_arc_with_state(root_reducer: RootReducer, state: State) -> Arc<Self> {
+        Arc::new(Self::new_with_state(root_reducer, state))
+    }
+
+    /// Create a new store with the state returned by the factory.
+    /// The factory is called exactly once, while the store is constructed. Use `ColdStore` to defer it until the store is first used.
+    pub fn new_with_factory<F>(root_reducer: RootReducer, factory: F) -> Self
+    where
+        F: FnOnce() -> State,
+    {
+        Self::new_with_state(root_reducer, factory())
+    }
+
+    /// Create a new store whose initial state is the result of applying the actions to the provided state.
+    /// The actions are reduced before the store is started, subscribers and observers never see them.
+    pub fn new_from_iter<I>(root_reducer: RootReducer, initial: State, actions: I) -> Self
+    where
+        I: IntoIterator<Item = Action>,
+    {
+        let state = actions.into_iter().fold(initial, |state, action| root_reducer.reduce(state, action));
+        Self::new_with_state(root_reducer, state)
+    }
+
+    /// Add a transform which runs on the new state after every reduction, before subscribers are notified.
+    /// This is useful for post-processing like clamping a value. Multiple transforms run in the order they were added.
+    pub async fn with_post_reduce_transform(self, transform: fn(State) -> State) -> Self {
+        self.worker_address.send(AddTransform::new(transform)).await;
+        self
+    }
+
     /// Dispatch a new action to the store
     ///
     /// Notice that this method takes &self and not &mut self,
@@ -65,9 +145,113 @@ where
         self.worker_address.send(Dispatch::new(action)).await;
     }
 
+    /// Dispatch the actions one by one, every action is reduced and the subscribers are notified before the next one is dispatched
+    pub async fn dispatch_all_and_wait<I>(&self, actions: I)
+    where
+        I: IntoIterator<Item = Action>,
+    {
+        for action in actions {
+            self.dispatch(action).await;
+        }
+    }
+
+    /// Dispatch the actions as a single batch and return the state after the last one.
+    /// Every action is reduced, but the subscribers are only notified once for the whole batch.
+    /// No other work is handled by the store in between the actions of the batch.
+    pub async fn dispatch_batch_and_get(&self, actions: Vec<Action>) -> State
+    where
+        State: Clone,
+    {
+        self.worker_address.send(DispatchBatch::new(actions)).await
+    }
+
+    /// Compute an action from the current state and dispatch it, without any other work in between.
+    ///
+    /// The selector runs on the current state and `compute` turns its result into the action to dispatch, or None to dispatch nothing.
+    /// Unlike a select followed by a dispatch, no other action can be reduced in between, so the action is never based on a stale state.
+    /// Returns true if the action was dispatched.
+    pub async fn dispatch_cas<S, F>(&self, selector: S, compute: F) -> bool
+    where
+        S: Selector<State> + Send + 'static,
+        F: FnOnce(S::Result) -> Option<Action> + Send + 'static,
+    {
+        self.worker_address.send(DispatchCas::new(selector, compute)).await
+    }
+
+    /// Preview the result of an action without committing it.
+    ///
+    /// The action is reduced on a copy of the state, including the post reduce transforms, and `inspect` is called with the result.
+    /// The copy is dropped afterwards, so the state of the store is left untouched.
+    /// Subscribers, action observers and the interceptor of `on_next_dispatch` don't see the action, the state version isn't incremented.
+    /// If the reducer panics on a store created with `new_with_error_channel`, the panic is reported and `inspect` gets the unchanged state.
+    pub async fn dispatch_and_rollback<F, R>(&self, action: Action, inspect: F) -> R
+    where
+        F: FnOnce(&State) -> R + Send + 'static,
+        R: Send + 'static,
+        State: Clone,
+    {
+        self.worker_address.send(DispatchRollback::new(action, inspect)).await
+    }
+
+    /// Bring the store to the target state with the shortest sequence of actions.
+    ///
+    /// The sequence is searched breadth first, taking the actions from `action_graph` at every step,
+    /// and only sequences of at most `max_depth` actions are considered.
+    /// The actions of the sequence are then dispatched one by one, subscribers are notified after each of them.
+    /// If the store is in the target state already nothing is dispatched.
+    /// Returns `TransitionError::NoPlanFound` and leaves the state untouched when the target can't be reached.
+    ///
+    /// The dispatched actions go through the interceptor of `on_next_dispatch` and the panic handling of `new_with_error_channel`.
+    /// If one of them is dropped or fails, the store ends up short of the target and `TransitionError::TargetNotReached` is returned.
+    ///
+    /// The search runs the reducer and the post reduce transforms on copies of the state, so they should be pure.
+    /// It runs inside the store, which handles no other work until the search is done.
+    /// States aren't hashed, every new state is compared to all states visited so far,
+    /// so the search takes quadratic time in the number of reachable states. Keep `max_depth` and the number of actions small.
+    pub async fn transition_to<G>(&self, target: State, action_graph: G, max_depth: usize) -> Result<(), TransitionError>
+    where
+        G: IntoIterator<Item = Action>,
+        State: Clone + PartialEq,
+        Action: Clone,
+    {
+        let actions = action_graph.into_iter().collect();
+        self.worker_address.send(TransitionTo::new(target, actions, max_depth)).await
+    }
+
+    /// Dispatch an action without waiting for it to be reduced, this method returns immediately.
+    ///
+    /// The action is queued in the same mailbox as the other work of the store.
+    /// Work which is queued afterwards, like a select or dispatch, is handled after this action.
+    /// There is no guarantee about when the action is reduced relative to the calling task though,
+    /// subscribers may not have been notified yet when this method returns. Priority dispatches overtake the queued action.
+    pub fn dispatch_event(&self, action: Action) {
+        self.worker_address.send_detached(Dispatch::new(action));
+    }
+
+    /// Dispatch an urgent action, it's reduced before any regular actions which are still queued.
+    /// Use this for critical actions like a shutdown or an emergency stop.
+    pub async fn dispatch_priority(&self, action: Action) {
+        self.worker_address.send_priority(Dispatch::new(action)).await;
+    }
+
+    /// Prepare an action asynchronously, then commit it to the store if the preparation succeeded.
+    ///
+    /// In the first phase `prepare` runs, for example to make a network request.
+    /// If it returns an action, that action is dispatched as a priority dispatch in the second phase,
+    /// so regular dispatches which were queued in the meantime don't get in between. If it returns an error nothing is dispatched.
+    pub async fn two_phase_commit<F, Fut, E>(&self, prepare: F) -> Result<(), E>
+    where
+        F: FnOnce() -> Fut,
+        Fut: Future<Output = Result<Action, E>>,
+    {
+        let action = prepare().await?;
+        self.dispatch_priority(action).await;
+        Ok(())
+    }
+
     /// Select a part of the state, this is more efficient than copying the entire state all the time.
     /// In case you still need a full copy of the state, use the state_cloned method.
-    pub async fn select<S: Selector<State, Result = Result>, Result>(&self, selector: S) -> Result
+    pub async fn select<S, Result>(&self, selector: S) -> Result
     where
         S: Selector<State, Result = Result> + Send + 'static,
         Result: Send + 'static,
@@ -84,12 +268,247 @@ where
         self.select(|state: &State| state.clone()).await
     }
 
+    /// Clone the state into the given target, reusing the allocations of the target where `Clone::clone_from` allows it.
+    /// Calling this repeatedly with the same buffer avoids allocating a new state every time, unlike state_cloned.
+    ///
+    /// The worker runs on another task and needs ownership of the buffer to clone into it, so the buffer is moved out of the target.
+    /// `State: Default` is required for the value which takes its place in the target until the call returns.
+    /// Besides the buffer, every call allocates a small shared slot to hand the buffer to the worker, its size doesn't depend on the state.
+    ///
+    /// If the returned future is dropped early the buffer is put back into the target, holding either its old contents or the cloned state.
+    /// Only when the future is dropped while the worker is cloning into the buffer, the buffer is dropped by the worker
+    /// and the target keeps the default state. Dropping the future never waits for the worker.
+    pub async fn clone_state_into(&self, target: &mut State)
+    where
+        State: Clone + Default,
+    {
+        let slot = Arc::new(Mutex::new(Some(std::mem::take(target))));
+        let _restore = RestoreBuffer { target, slot: slot.clone() };
+
+        self.select(move |state: &State| {
+            if let Some(buffer) = slot.lock().unwrap().as_mut() {
+                buffer.clone_from(state);
+            }
+        })
+        .await;
+    }
+
+    /// Returns the version of the state.
+    /// It's incremented for every reduced action, also when the reducer returns an equal state, and when the state is replaced or restored.
+    pub async fn state_version(&self) -> u64 {
+        self.worker_address.send(StateVersion).await
+    }
+
+    /// Returns a copy of the state together with its version
+    pub async fn snapshot_versioned(&self) -> VersionedSnapshot<State>
+    where
+        State: Clone,
+    {
+        self.worker_address.send(TakeSnapshot::new()).await
+    }
+
+    /// Restore a snapshot taken with snapshot_versioned.
+    /// The snapshot is only restored if the version hasn't been incremented since it was taken, otherwise a `ConflictError` is returned.
+    /// Subscribers are notified when the snapshot is restored.
+    pub async fn restore_versioned(&self, snapshot: VersionedSnapshot<State>) -> Result<(), ConflictError> {
+        self.worker_address.send(RestoreSnapshot::new(snapshot)).await
+    }
+
+    /// Replace the current state with the given state and notify the subscribers.
+    /// No actions are replayed, the store jumps straight to the new state.
+    /// This is the building block for a "jump to state" feature in a debugger.
+    pub async fn time_travel_to(&self, state: State) {
+        self.worker_address.send(ReplaceState::new(state)).await
+    }
+
+    /// Notify all subscribers with the current state without dispatching an action.
+    ///
+    /// This breaks the Redux guarantee that the state only changes through the reducer.
+    /// Only use it to bridge to imperative systems, e.g. when a part of the state is mutated in place through interior mutability.
+    /// The state version is not incremented.
+    pub async fn force_notify_subscribers(&self) {
+        self.worker_address.send(ForceNotify).await
+    }
+
     /// Subscribe to state changes.
     /// Every time an action is dispatched the subscriber will be notified after the state is updated
     pub async fn subscribe<S: Subscriber<State> + Send + 'static>(&self, subscriber: S) {
         self.worker_address.send(Subscribe::new(Box::new(subscriber))).await
     }
 
+    /// Subscribe multiple subscribers at once, this only takes a single round-trip to the store
+    pub async fn subscribe_batch<S: Subscriber<State> + Send + 'static>(&self, subscribers: Vec<S>) {
+        let subscribers = subscribers
+            .into_iter()
+            .map(|subscriber| Box::new(subscriber) as Box<dyn Subscriber<State> + Send>)
+            .collect();
+
+        self.worker_address.send(SubscribeBatch::new(subscribers)).await
+    }
+
+    // Queue the subscribers without waiting, they're registered before any work which is sent afterwards
+    pub(crate) fn subscribe_batch_detached(&self, subscribers: Vec<Box<dyn Subscriber<State> + Send>>) {
+        self.worker_address.send_detached(SubscribeBatch::new(subscribers));
+    }
+
+    /// Subscribe to state changes until the condition is met.
+    /// The subscriber is notified like a regular subscriber, including for the state that meets the condition.
+    /// After that notification the subscriber is removed from the store.
+    pub async fn subscribe_until<S, P>(&self, subscriber: S, condition: P)
+    where
+        S: Subscriber<State> + Send + 'static,
+        P: Fn(&State) -> bool + Send + 'static,
+    {
+        self.worker_address.send(SubscribeUntil::new(Box::new(subscriber), Box::new(condition))).await
+    }
+
+    // Like subscribe_batch_detached, for a subscriber which is removed once its condition is met
+    pub(crate) fn subscribe_until_detached(&self, subscriber: Box<dyn Subscriber<State> + Send>, condition: Box<dyn Fn(&State) -> bool + Send>) {
+        self.worker_address.send_detached(SubscribeUntil::new(subscriber, condition));
+    }
+
+    /// Wait for the first state which satisfies the predicate.
+    ///
+    /// The predicate is only checked when the subscribers are notified, the current state is not checked.
+    /// If the condition already holds, the returned future resolves on the next dispatch.
+    /// The store is subscribed in the background when this method is called, not when the future is first polled.
+    /// Resolves with None if the store is dropped before a state satisfies the predicate.
+    pub fn subscribe_first<P>(&self, predicate: P) -> impl Future<Output = Option<State>>
+    where
+        P: Fn(&State) -> bool + Send + 'static,
+        State: Clone,
+    {
+        let (tx, rx) = oneshot::channel();
+        let tx = Arc::new(Mutex::new(Some(tx)));
+
+        let subscriber_tx = tx.clone();
+        let subscriber = move |state: &State| {
+            if predicate(state) {
+                if let Some(tx) = subscriber_tx.lock().unwrap().take() {
+                    let _ = tx.send(state.clone());
+                }
+            }
+        };
+        self.worker_address.send_detached(SubscribeUntil::new(
+            Box::new(subscriber),
+            Box::new(move |_: &State| tx.lock().unwrap().is_none()),
+        ));
+
+        async move { rx.await.ok() }
+    }
+
+    /// Returns a future which resolves with the state after the next change, or with None if the store is dropped first.
+    ///
+    /// Unlike the other methods the returned watcher doesn't need the Tokio executor to be polled,
+    /// which makes it possible to wait for a change from synchronous code or another async runtime.
+    /// The store is subscribed when this method is called, a change in between is not missed.
+    pub fn watch_for_change(&self) -> StateChangeWatcher<State>
+    where
+        State: Clone,
+    {
+        let (tx, rx) = oneshot::channel();
+        let tx = Mutex::new(Some(tx));
+
+        let subscriber = move |state: &State| {
+            if let Some(tx) = tx.lock().unwrap().take() {
+                let _ = tx.send(state.clone());
+            }
+        };
+        self.worker_address
+            .send_detached(SubscribeUntil::new(Box::new(subscriber), Box::new(|_: &State| true)));
+
+        StateChangeWatcher::new(rx)
+    }
+
+    /// Subscribe to the differences between consecutive states, computed by `D`.
+    ///
+    /// The store keeps a copy of the previous state for the subscriber, starting with the current state.
+    /// After every state change the diff between the previous and the new state is computed,
+    /// the subscriber is only called if the diff isn't None.
+    pub async fn subscribe_diff<D, F>(&self, subscriber: F)
+    where
+        D: StateDiff<State>,
+        F: Fn(D::Diff) + Send + 'static,
+        State: Clone,
+    {
+        let previous: Mutex<Option<State>> = Mutex::new(None);
+        let diff_subscriber = move |state: &State| {
+            let mut previous = previous.lock().unwrap();
+            match previous.as_mut() {
+                Some(previous) => {
+                    if let Some(diff) = D::diff(previous, state) {
+                        subscriber(diff);
+                    }
+                    previous.clone_from(state);
+                }
+                // The subscriber is seeded with the current state, that's the first previous state
+                None => *previous = Some(state.clone()),
+            }
+        };
+
+        self.worker_address
+            .send(SubscribeSeeded::new(Box::new(diff_subscriber), Box::new(|_: &State| false)))
+            .await
+    }
+
+    /// Monitor the health of the state with `check`, see `StateMonitor`.
+    ///
+    /// The check runs after every state change, the returned receiver gets the health changes.
+    pub async fn with_state_monitor(&self, check: fn(&State) -> HealthStatus) -> HealthReceiver {
+        let (monitor, health) = StateMonitor::new(check);
+        self.subscribe(monitor).await;
+
+        health
+    }
+
+    /// Select a value from the state and follow its changes.
+    /// The returned subscription resolves to the current value when awaited and is a stream of the distinct values.
+    /// The stream starts with the current value, unless it was already awaited.
+    ///
+    /// The store is subscribed in the background, dropping the subscription unsubscribes.
+    pub fn select_subscribe<S>(&self, selector: S) -> SelectSubscription<State, S>
+    where
+        S: Selector<State> + Send + 'static,
+        S::Result: Clone + PartialEq + Send + 'static,
+    {
+        let (subscriber, tx, subscription) = SelectSubscription::channel(selector);
+        self.worker_address
+            .send_detached(SubscribeSeeded::new(Box::new(subscriber), Box::new(move |_: &State| tx.is_closed())));
+
+        subscription
+    }
+
+    /// Returns diagnostic information about the registered subscribers, this helps to find subscriber leaks.
+    /// Only available in debug builds.
+    #[cfg(debug_assertions)]
+    pub async fn list_subscribers(&self) -> Vec<SubscriberInfo> {
+        self.worker_address.send(ListSubscribers).await
+    }
+
+    /// Observe every action before it's reduced, unlike subscribers which are notified after the state is updated.
+    /// The stream ends when the store is dropped
+    pub async fn observe_actions(&self) -> ActionStream<Action>
+    where
+        Action: Clone,
+    {
+        let (tx, stream) = ActionStream::channel();
+        let observer = move |action: &Action| tx.send(action.clone()).is_ok();
+
+        self.worker_address.send(Observe::new(Box::new(observer))).await;
+        stream
+    }
+
+    /// Intercept the next dispatched action before it reaches the reducer, this is mostly useful in tests.
+    ///
+    /// The interceptor returns the action which is reduced instead, or None to cancel the dispatch.
+    /// It only runs once, registering a new interceptor replaces the pending one.
+    pub async fn on_next_dispatch<F>(&self, f: F)
+    where
+        F: FnOnce(&Action) -> Option<Action> + Send + 'static,
+    {
+        self.worker_address.send(Intercept::new(Box::new(f))).await
+    }
+
     /// Wrap the store with middleware, see middleware module for more examples
     pub async fn wrap<M, OuterAction>(self, middleware: M) -> StoreWithMiddleware<Self, M, State, Action, OuterAction>
     where
@@ -132,13 +551,51 @@ where
     async fn subscribe<S: Subscriber<State> + Send + 'static>(&self, subscriber: S) {
         Store::subscribe(self, subscriber).await
     }
+
+    async fn subscribe_until<S, P>(&self, subscriber: S, condition: P)
+    where
+        S: Subscriber<State> + Send + Sync + 'static,
+        P: Fn(&State) -> bool + Send + Sync + 'static,
+    {
+        Store::subscribe_until(self, subscriber, condition).await
+    }
+}
+
+#[async_trait]
+impl<State, Action, RootReducer> ObserveActions<Action> for Store<State, Action, RootReducer>
+where
+    Action: Send + Sync + 'static,
+    RootReducer: Reducer<State, Action> + Send + Sync + 'static,
+    State: Send + Sync + 'static,
+{
+    async fn observe_actions(&self) -> ActionStream<Action>
+    where
+        Action: Clone,
+    {
+        Store::observe_actions(self).await
+    }
+}
+
+// Puts the buffer of clone_state_into back into the target, also when the call is cancelled
+struct RestoreBuffer<'a, State> {
+    target: &'a mut State,
+    slot: Arc<Mutex<Option<State>>>,
+}
+
+impl<State> Drop for RestoreBuffer<'_, State> {
+    fn drop(&mut self) {
+        // The slot is only locked by the worker while it clones into the buffer, waiting for that would block the executor
+        if let Some(buffer) = self.slot.try_lock().ok().and_then(|mut slot| slot.take()) {
+            *self.target = buffer;
+        }
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
     use std::sync::atomic::{AtomicI32, Ordering};
-    use std::sync::Arc;
+    use std::task::{Context, Poll};
 
     #[derive(Clone, Debug, PartialEq)]
     struct Counter {
@@ -166,6 +623,7 @@ mod tests {
         }
     }
 
+    #[derive(Clone, Debug, PartialEq)]
     enum CounterAction {
         Increment,
         Decrement,
@@ -257,4 +715,1000 @@ mod tests {
         // Sum should be: 43 + 44 + 43 = 130
         assert_eq!(sum.load(Ordering::Relaxed), 130);
     }
+
+    #[tokio::test]
+    async fn subscribe_until_progress() {
+        let store = Store::new_with_state(|progress: u32, step: u32| progress + step, 0);
+
+        let invocations = Arc::new(AtomicI32::new(0));
+        let captured_invocations = invocations.clone();
+        store
+            .subscribe_until(
+                move |_: &u32| {
+                    captured_invocations.fetch_add(1, Ordering::Relaxed);
+                },
+                |progress: &u32| *progress >= 100,
+            )
+            .await;
+
+        for _ in 0..5 {
+            store.dispatch(20).await;
+        }
+        assert_eq!(invocations.load(Ordering::Relaxed), 5);
+
+        // The subscriber is removed once the progress reached 100
+        store.dispatch(20).await;
+        store.dispatch(20).await;
+        assert_eq!(invocations.load(Ordering::Relaxed), 5);
+    }
+
+    #[tokio::test]
+    async fn clone_state_into_reuses_buffer() {
+        let store = Store::new_with_state(
+            |mut state: Vec<u32>, value: u32| {
+                state.push(value);
+                state
+            },
+            vec![],
+        );
+        store.dispatch(1).await;
+        store.dispatch(2).await;
+
+        let mut target = Vec::with_capacity(64);
+        let buffer = target.as_ptr();
+
+        store.clone_state_into(&mut target).await;
+        assert_eq!(target, vec![1, 2]);
+
+        store.dispatch(3).await;
+        store.clone_state_into(&mut target).await;
+        assert_eq!(target, vec![1, 2, 3]);
+
+        // The allocation of the target is reused, state_cloned would allocate a new vec
+        assert_eq!(target.as_ptr(), buffer);
+    }
+
+    #[tokio::test]
+    async fn clone_state_into_keeps_buffer_on_cancel() {
+        use futures::FutureExt;
+
+        let store = Store::new_with_state(|state: Vec<u32>, value: u32| [state, vec![value]].concat(), vec![1]);
+
+        let mut target = Vec::with_capacity(64);
+        target.push(7);
+        let buffer = target.as_ptr();
+
+        // Dropped before the worker got to it
+        assert!(store.clone_state_into(&mut target).now_or_never().is_none());
+        assert_eq!(target, vec![7]);
+        assert_eq!(target.as_ptr(), buffer);
+    }
+
+    #[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+    async fn clone_state_into_cancel_does_not_wait_for_worker() {
+        // Cloning into the buffer blocks until the test releases it
+        struct Gate {
+            started: Mutex<Option<oneshot::Sender<()>>>,
+            release: Mutex<std::sync::mpsc::Receiver<()>>,
+        }
+
+        #[derive(Default)]
+        struct Gated {
+            value: i32,
+            gate: Option<Arc<Gate>>,
+        }
+
+        impl Clone for Gated {
+            fn clone(&self) -> Self {
+                Gated {
+                    value: self.value,
+                    gate: self.gate.clone(),
+                }
+            }
+
+            fn clone_from(&mut self, source: &Self) {
+                if let Some(gate) = &source.gate {
+                    if let Some(started) = gate.started.lock().unwrap().take() {
+                        let _ = started.send(());
+                    }
+                    let _ = gate.release.lock().unwrap().recv();
+                }
+                *self = source.clone();
+            }
+        }
+
+        let (started_tx, started_rx) = oneshot::channel();
+        let (release_tx, release_rx) = std::sync::mpsc::channel();
+        let gate = Arc::new(Gate {
+            started: Mutex::new(Some(started_tx)),
+            release: Mutex::new(release_rx),
+        });
+        let store = Store::new_with_state(|state: Gated, _: ()| state, Gated { value: 1, gate: Some(gate) });
+
+        // Cancelled while the worker clones into the buffer, this would hang if the drop waited for the worker
+        let mut target = Gated { value: 7, gate: None };
+        tokio::select! {
+            _ = store.clone_state_into(&mut target) => unreachable!("the clone is still blocked"),
+            _ = started_rx => {}
+        }
+        assert_eq!(target.value, 0);
+
+        release_tx.send(()).unwrap();
+        assert_eq!(store.select(|state: &Gated| state.value).await, 1);
+    }
+
+    #[tokio::test]
+    async fn on_next_dispatch_replaces_action() {
+        let store = Store::new(counter_reducer);
+
+        store.on_next_dispatch(|_: &CounterAction| Some(CounterAction::Decrement)).await;
+        store.dispatch(CounterAction::Increment).await;
+        assert_eq!(store.select(|state: &Counter| state.value).await, 41);
+
+        // The interceptor only runs once
+        store.dispatch(CounterAction::Increment).await;
+        assert_eq!(store.select(|state: &Counter| state.value).await, 42);
+
+        // Cancel the next dispatch
+        store.on_next_dispatch(|_: &CounterAction| None).await;
+        store.dispatch(CounterAction::Increment).await;
+        assert_eq!(store.select(|state: &Counter| state.value).await, 42);
+        assert_eq!(store.state_version().await, 2);
+    }
+
+    #[tokio::test]
+    async fn post_reduce_transform_clamps() {
+        fn clamp(state: Counter) -> Counter {
+            Counter::new(state.value.clamp(0, 100))
+        }
+
+        let store = Store::new_with_state(counter_reducer, Counter::new(99))
+            .with_post_reduce_transform(clamp)
+            .await
+            .with_post_reduce_transform(|state: Counter| Counter::new(state.value * 2))
+            .await;
+
+        // The transforms are chained, first clamped then doubled
+        store.dispatch(CounterAction::Increment).await;
+        assert_eq!(store.select(|state: &Counter| state.value).await, 200);
+
+        store.dispatch(CounterAction::Increment).await;
+        assert_eq!(store.select(|state: &Counter| state.value).await, 200);
+    }
+
+    #[tokio::test]
+    async fn post_reduce_transform_stays_at_max() {
+        let store = Store::new_with_state(counter_reducer, Counter::new(100))
+            .with_post_reduce_transform(|state: Counter| Counter::new(state.value.clamp(0, 100)))
+            .await;
+
+        store.dispatch(CounterAction::Increment).await;
+        assert_eq!(store.select(|state: &Counter| state.value).await, 100);
+    }
+
+    #[tokio::test]
+    async fn dispatch_priority_before_queued() {
+        let store = Store::new_with_state(
+            |mut state: Vec<&'static str>, action: &'static str| {
+                state.push(action);
+                state
+            },
+            vec![],
+        );
+
+        // All dispatches are queued before the worker gets to run
+        tokio::join!(store.dispatch("first"), store.dispatch("second"), store.dispatch_priority("stop"));
+
+        let state = store.state_cloned().await;
+        assert_eq!(state[0], "stop");
+        assert_eq!(state.len(), 3);
+    }
+
+    #[cfg(debug_assertions)]
+    #[tokio::test]
+    async fn list_subscribers_unique_ids() {
+        let store = Store::new(counter_reducer);
+
+        store.subscribe(|_: &Counter| {}).await;
+        store.subscribe(|_: &Counter| {}).await;
+        store.subscribe_until(|_: &Counter| {}, |_: &Counter| true).await;
+
+        let subscribers = store.list_subscribers().await;
+        assert_eq!(subscribers.len(), 3);
+
+        let ids: std::collections::HashSet<_> = subscribers.iter().map(|info| info.id).collect();
+        assert_eq!(ids.len(), 3);
+
+        // The subscribe_until subscriber is removed after its condition is met
+        store.dispatch(CounterAction::Increment).await;
+        assert_eq!(store.list_subscribers().await.len(), 2);
+    }
+
+    #[tokio::test]
+    async fn dispatch_event_fire_and_forget() {
+        let store = Store::new(counter_reducer);
+
+        let notified = Arc::new(AtomicI32::new(0));
+        let captured_notified = notified.clone();
+        store
+            .subscribe(move |_: &Counter| {
+                captured_notified.fetch_add(1, Ordering::SeqCst);
+            })
+            .await;
+
+        store.dispatch_event(CounterAction::Increment);
+        store.dispatch_event(CounterAction::Increment);
+
+        // The worker didn't get the chance to run yet
+        assert_eq!(notified.load(Ordering::SeqCst), 0);
+
+        // A select is queued after the events, so it sees their effect
+        assert_eq!(store.select(|state: &Counter| state.value).await, 44);
+        assert_eq!(notified.load(Ordering::SeqCst), 2);
+    }
+
+    #[tokio::test]
+    async fn dispatch_all_and_wait_intermediate_states() {
+        let store = Store::new(counter_reducer);
+
+        let recorded = Arc::new(std::sync::Mutex::new(Vec::new()));
+        let captured_recorded = recorded.clone();
+        store
+            .subscribe(move |state: &Counter| captured_recorded.lock().unwrap().push(state.value))
+            .await;
+
+        store
+            .dispatch_all_and_wait(vec![CounterAction::Increment, CounterAction::Increment, CounterAction::Decrement])
+            .await;
+
+        // Every subscriber call happened before this method returned
+        assert_eq!(*recorded.lock().unwrap(), vec![43, 44, 43]);
+
+        // Through the StoreApi trait
+        StoreApi::dispatch_all_and_wait(&store, vec![CounterAction::Decrement]).await;
+        assert_eq!(*recorded.lock().unwrap(), vec![43, 44, 43, 42]);
+    }
+
+    #[tokio::test]
+    async fn time_travel_to_state() {
+        let store = Store::new(counter_reducer);
+
+        let notified = Arc::new(AtomicI32::new(0));
+        let captured_notified = notified.clone();
+        store
+            .subscribe(move |state: &Counter| captured_notified.store(state.value, Ordering::SeqCst))
+            .await;
+
+        store.dispatch(CounterAction::Increment).await;
+        let before = store.select(|state: &Counter| state.value).await;
+
+        // Jump further than a single action could move the counter
+        store.time_travel_to(Counter::new(10)).await;
+        let after = store.select(|state: &Counter| state.value).await;
+
+        assert_eq!(before, 43);
+        assert_eq!(after, 10);
+        assert_eq!(notified.load(Ordering::SeqCst), 10);
+        assert_eq!(store.state_version().await, 2);
+    }
+
+    #[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+    async fn subscribe_batch_registers_all_at_once() {
+        struct RecordSubscriber(Arc<Mutex<Vec<i32>>>);
+        impl Subscriber<Counter> for RecordSubscriber {
+            fn notify(&self, state: &Counter) {
+                self.0.lock().unwrap().push(state.value);
+            }
+        }
+
+        let store = Arc::new(Store::new(counter_reducer));
+        let notified = Arc::new(Mutex::new(Vec::new()));
+
+        let dispatcher = {
+            let store = store.clone();
+            tokio::spawn(async move {
+                for _ in 0..100 {
+                    store.dispatch(CounterAction::Increment).await;
+                }
+            })
+        };
+        store.subscribe_batch((0..100).map(|_| RecordSubscriber(notified.clone())).collect()).await;
+        dispatcher.await.unwrap();
+        store.dispatch(CounterAction::Increment).await;
+
+        // The batch is registered by a single worker message, so no dispatch sees only part of it
+        let notified = notified.lock().unwrap();
+        let mut values = notified.clone();
+        values.dedup();
+        assert!(values.iter().all(|value| notified.iter().filter(|notified| *notified == value).count() == 100));
+        assert_eq!(values.last(), Some(&143));
+    }
+
+    #[tokio::test]
+    async fn two_phase_commit_payment() {
+        #[derive(Debug, PartialEq)]
+        struct PaymentDeclined;
+
+        async fn charge(amount: i32) -> Result<i32, PaymentDeclined> {
+            if amount <= 100 {
+                Ok(amount)
+            } else {
+                Err(PaymentDeclined)
+            }
+        }
+
+        let store = Store::new_with_state(|paid: i32, amount: i32| paid + amount, 0);
+
+        assert_eq!(store.two_phase_commit(|| charge(30)).await, Ok(()));
+        assert_eq!(store.two_phase_commit(|| charge(500)).await, Err(PaymentDeclined));
+
+        // Only the successful payment was committed
+        assert_eq!(store.state_cloned().await, 30);
+        assert_eq!(store.state_version().await, 1);
+    }
+
+    // A second worker thread keeps driving the timer while the store worker is blocked
+    #[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+    async fn select_with_timeout_slow_worker() {
+        let store = Arc::new(Store::new(counter_reducer));
+
+        // Keep the worker busy with a selector which blocks until it's released
+        let (started_tx, started_rx) = oneshot::channel();
+        let (release_tx, release_rx) = std::sync::mpsc::channel::<()>();
+        let started_tx = Mutex::new(Some(started_tx));
+        let release_rx = Mutex::new(release_rx);
+
+        let slow_store = store.clone();
+        let slow = tokio::spawn(async move {
+            slow_store
+                .select(move |state: &Counter| {
+                    if let Some(started_tx) = started_tx.lock().unwrap().take() {
+                        let _ = started_tx.send(());
+                    }
+                    let _ = release_rx.lock().unwrap().recv();
+                    state.value
+                })
+                .await
+        });
+        started_rx.await.unwrap();
+
+        let timeout = std::time::Duration::from_millis(50);
+        assert_eq!(store.select_with_timeout(|state: &Counter| state.value, timeout).await, None);
+
+        // Once the worker recovered the select succeeds
+        release_tx.send(()).unwrap();
+        assert_eq!(slow.await.unwrap(), 42);
+        assert_eq!(store.select_with_timeout(|state: &Counter| state.value, timeout).await, Some(42));
+    }
+
+    #[tokio::test]
+    async fn force_notify_subscribers_without_dispatch() {
+        let store = Store::new(counter_reducer);
+
+        let notified = Arc::new(Mutex::new(Vec::new()));
+        let notified_clone = notified.clone();
+        store
+            .subscribe(move |state: &Counter| {
+                notified_clone.lock().unwrap().push(state.value);
+            })
+            .await;
+
+        store.force_notify_subscribers().await;
+
+        assert_eq!(*notified.lock().unwrap(), vec![42]);
+        assert_eq!(store.state_version().await, 0);
+    }
+
+    #[tokio::test]
+    async fn select_subscribe_yields_distinct_values() {
+        let store = Store::new(counter_reducer);
+
+        let mut subscription = store.select_subscribe(|state: &Counter| state.value / 2);
+        assert_eq!((&mut subscription).await, 21);
+
+        store.dispatch(CounterAction::Increment).await;
+        store.dispatch(CounterAction::Increment).await;
+        store.dispatch(CounterAction::Increment).await;
+        store.dispatch(CounterAction::Decrement).await;
+        store.dispatch(CounterAction::Decrement).await;
+        store.dispatch(CounterAction::Decrement).await;
+        store.dispatch(CounterAction::Decrement).await;
+        drop(store);
+
+        let mut values = Vec::new();
+        while let Some(value) = subscription.next().await {
+            values.push(value);
+        }
+
+        // The value is 21, 22, 22, 22, 21, 21, 20 after every dispatch, the current value was already awaited
+        assert_eq!(values, vec![22, 21, 20]);
+    }
+
+    #[tokio::test]
+    async fn dispatch_batch_and_get_notifies_once() {
+        let actions = || {
+            vec![
+                CounterAction::Increment,
+                CounterAction::Increment,
+                CounterAction::Decrement,
+                CounterAction::Increment,
+                CounterAction::Increment,
+            ]
+        };
+
+        let sequential = Store::new(counter_reducer);
+        sequential.dispatch_all_and_wait(actions()).await;
+
+        let store = Store::new(counter_reducer);
+        let notifications = Arc::new(AtomicI32::new(0));
+        let notifications_clone = notifications.clone();
+        store
+            .subscribe(move |_: &Counter| {
+                notifications_clone.fetch_add(1, Ordering::SeqCst);
+            })
+            .await;
+
+        let state = store.dispatch_batch_and_get(actions()).await;
+
+        assert_eq!(state, sequential.state_cloned().await);
+        assert_eq!(state, Counter::new(45));
+        assert_eq!(notifications.load(Ordering::SeqCst), 1);
+        assert_eq!(store.state_version().await, 5);
+    }
+
+    #[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+    async fn select_zip_sees_one_snapshot() {
+        let store = Arc::new(Store::new(counter_reducer));
+
+        let dispatcher = {
+            let store = store.clone();
+            tokio::spawn(async move {
+                for _ in 0..500 {
+                    store.dispatch(CounterAction::Increment).await;
+                }
+            })
+        };
+
+        for _ in 0..500 {
+            let (value, doubled) = store.select_zip(|state: &Counter| state.value, |state: &Counter| state.value * 2).await;
+            assert_eq!(doubled, value * 2);
+        }
+
+        dispatcher.await.unwrap();
+        assert_eq!(store.select_zip(ValueSelector, |state: &Counter| state.value).await, (542, 542));
+    }
+
+    #[tokio::test]
+    async fn new_from_iter_folds_actions() {
+        let actions = std::iter::repeat_with(|| CounterAction::Increment).take(10);
+        let store = Store::new_from_iter(counter_reducer, Counter::new(0), actions);
+
+        assert_eq!(store.state_version().await, 0);
+        assert_eq!(store.select(ValueSelector).await, 10);
+    }
+
+    #[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+    async fn dispatch_cas_only_one_wins() {
+        let store = Arc::new(Store::new_with_state(counter_reducer, Counter::new(1)));
+
+        let decrement_from_one = |store: Arc<Store<Counter, CounterAction, _>>| async move {
+            store
+                .dispatch_cas(ValueSelector, |value| if value == 1 { Some(CounterAction::Decrement) } else { None })
+                .await
+        };
+
+        let first = tokio::spawn(decrement_from_one(store.clone()));
+        let second = tokio::spawn(decrement_from_one(store.clone()));
+        let (first, second) = (first.await.unwrap(), second.await.unwrap());
+
+        assert!(first ^ second, "exactly one decrement should succeed, got {} and {}", first, second);
+        assert_eq!(store.select(ValueSelector).await, 0);
+        assert_eq!(store.state_version().await, 1);
+    }
+
+    #[tokio::test]
+    async fn subscribe_first_resolves_with_matching_state() {
+        let store = Store::new(counter_reducer);
+
+        let first = store.subscribe_first(|counter: &Counter| counter.value >= 44);
+        store.dispatch(CounterAction::Increment).await;
+        store.dispatch(CounterAction::Increment).await;
+        store.dispatch(CounterAction::Increment).await;
+
+        assert_eq!(first.await, Some(Counter::new(44)));
+    }
+
+    #[tokio::test]
+    async fn subscribe_first_resolves_with_none_when_store_is_dropped() {
+        let store = Store::new(counter_reducer);
+
+        let first = store.subscribe_first(|counter: &Counter| counter.value > 100);
+        store.dispatch(CounterAction::Increment).await;
+        drop(store);
+
+        assert_eq!(first.await, None);
+    }
+
+    #[tokio::test]
+    async fn subscribe_first_waits_for_next_dispatch_when_already_met() {
+        let store = Store::new(counter_reducer);
+
+        let first = store.subscribe_first(|counter: &Counter| counter.value > 0);
+        tokio::pin!(first);
+        assert!(tokio::time::timeout(std::time::Duration::from_millis(20), &mut first).await.is_err());
+
+        store.dispatch(CounterAction::Decrement).await;
+        assert_eq!(first.await, Some(Counter::new(41)));
+    }
+
+    #[tokio::test]
+    async fn new_with_factory_calls_factory_once() {
+        let calls = Arc::new(AtomicI32::new(0));
+
+        let factory_calls = calls.clone();
+        let store = Store::new_with_factory(counter_reducer, move || {
+            factory_calls.fetch_add(1, Ordering::SeqCst);
+            Counter::new(7)
+        });
+        assert_eq!(calls.load(Ordering::SeqCst), 1);
+
+        assert_eq!(store.state_cloned().await, Counter::new(7));
+        store.dispatch(CounterAction::Increment).await;
+        assert_eq!(store.state_cloned().await, Counter::new(8));
+        assert_eq!(calls.load(Ordering::SeqCst), 1);
+    }
+
+    #[test]
+    fn watch_for_change_without_tokio_executor() {
+        let runtime = tokio::runtime::Builder::new_multi_thread().worker_threads(1).build().unwrap();
+
+        let store = Arc::new(runtime.block_on(async { Store::new(counter_reducer) }));
+        let watcher = store.watch_for_change();
+
+        let dispatcher = store.clone();
+        runtime.spawn(async move { dispatcher.dispatch(CounterAction::Increment).await });
+
+        assert_eq!(futures::executor::block_on(watcher), Some(Counter::new(43)));
+    }
+
+    #[test]
+    fn watch_for_change_resolves_with_none_when_store_is_dropped() {
+        let runtime = tokio::runtime::Builder::new_multi_thread().worker_threads(1).build().unwrap();
+
+        let store = runtime.block_on(async { Store::new(counter_reducer) });
+        let watcher = store.watch_for_change();
+        drop(store);
+
+        assert_eq!(futures::executor::block_on(watcher), None);
+    }
+
+    #[test]
+    fn poll_for_change_is_pending_until_dispatch() {
+        let runtime = tokio::runtime::Builder::new_multi_thread().worker_threads(1).build().unwrap();
+
+        let store = runtime.block_on(async { Store::new(counter_reducer) });
+        let mut watcher = store.watch_for_change();
+
+        let mut cx = Context::from_waker(futures::task::noop_waker_ref());
+        assert!(watcher.poll_for_change(&mut cx).is_pending());
+
+        runtime.block_on(store.dispatch(CounterAction::Decrement));
+        assert_eq!(watcher.poll_for_change(&mut cx), Poll::Ready(Some(Counter::new(41))));
+    }
+
+    // Only uses the StoreApi methods, so it runs against every kind of store
+    async fn check_store_api<S: StoreApi<Counter, CounterAction> + Sync>(store: S) {
+        let values = Arc::new(Mutex::new(Vec::new()));
+        let subscriber_values = values.clone();
+        store
+            .subscribe(move |counter: &Counter| subscriber_values.lock().unwrap().push(counter.value))
+            .await;
+
+        store.dispatch(CounterAction::Increment).await;
+        store.dispatch(CounterAction::Increment).await;
+
+        assert_eq!(store.select(ValueSelector).await, 44);
+        assert_eq!(store.state_cloned().await, Counter::new(44));
+        assert_eq!(*values.lock().unwrap(), vec![43, 44]);
+    }
+
+    #[tokio::test]
+    async fn new_arc_implements_store_api() {
+        check_store_api(Store::new(counter_reducer)).await;
+        check_store_api(Store::new_arc(counter_reducer)).await;
+        check_store_api(Store::new_arc_with_state(counter_reducer, Counter::new(42))).await;
+
+        let store = Store::new_arc_with_state(counter_reducer, Counter::new(0));
+        let shared = store.clone();
+        tokio::spawn(async move { shared.dispatch(CounterAction::Increment).await }).await.unwrap();
+        assert_eq!(store.select(ValueSelector).await, 1);
+    }
+
+    #[tokio::test]
+    async fn dispatch_and_rollback_previews_checkout() {
+        #[derive(Clone)]
+        struct Cart {
+            prices: Vec<u32>,
+            discount_percent: u32,
+        }
+
+        impl Cart {
+            fn total(&self) -> u32 {
+                self.prices.iter().sum::<u32>() * (100 - self.discount_percent) / 100
+            }
+        }
+
+        enum CartAction {
+            AddItem(u32),
+            ApplyDiscount(u32),
+        }
+
+        fn cart_reducer(mut cart: Cart, action: CartAction) -> Cart {
+            match action {
+                CartAction::AddItem(price) => cart.prices.push(price),
+                CartAction::ApplyDiscount(percent) => cart.discount_percent = percent,
+            }
+            cart
+        }
+
+        let store = Store::new_with_state(
+            cart_reducer,
+            Cart {
+                prices: vec![],
+                discount_percent: 0,
+            },
+        );
+        store.dispatch(CartAction::AddItem(60)).await;
+        store.dispatch(CartAction::AddItem(40)).await;
+
+        let notifications = Arc::new(AtomicI32::new(0));
+        let subscriber_notifications = notifications.clone();
+        store
+            .subscribe(move |_: &Cart| {
+                subscriber_notifications.fetch_add(1, Ordering::SeqCst);
+            })
+            .await;
+        let version = store.state_version().await;
+
+        let preview = store.dispatch_and_rollback(CartAction::ApplyDiscount(20), |cart: &Cart| cart.total()).await;
+        assert_eq!(preview, 80);
+
+        assert_eq!(store.select(|cart: &Cart| cart.total()).await, 100);
+        assert_eq!(store.state_version().await, version);
+        assert_eq!(notifications.load(Ordering::SeqCst), 0);
+    }
+
+    fn fragile_reducer(counter: Counter, action: CounterAction) -> Counter {
+        match action {
+            CounterAction::Decrement if counter.value == 0 => panic!("counter can't go below zero"),
+            action => counter_reducer(counter, action),
+        }
+    }
+
+    #[tokio::test]
+    async fn error_channel_reports_reducer_panics() {
+        use futures::StreamExt;
+
+        let (store, mut errors) = Store::new_with_error_channel(fragile_reducer, Counter::new(0));
+
+        let notifications = Arc::new(AtomicI32::new(0));
+        let subscriber_notifications = notifications.clone();
+        store
+            .subscribe(move |_: &Counter| {
+                subscriber_notifications.fetch_add(1, Ordering::SeqCst);
+            })
+            .await;
+
+        store.dispatch(CounterAction::Decrement).await;
+        let error = errors.select_next_some().await;
+        assert!(matches!(error.action, CounterAction::Decrement));
+        assert_eq!(error.description, "counter can't go below zero");
+        assert!(error.timestamp <= std::time::SystemTime::now());
+
+        assert_eq!(store.state_cloned().await, Counter::new(0));
+        assert_eq!(store.state_version().await, 0);
+        assert_eq!(notifications.load(Ordering::SeqCst), 0);
+
+        store.dispatch(CounterAction::Increment).await;
+        store.dispatch(CounterAction::Decrement).await;
+        store.dispatch(CounterAction::Increment).await;
+        assert_eq!(store.state_cloned().await, Counter::new(1));
+        assert_eq!(notifications.load(Ordering::SeqCst), 3);
+    }
+
+    #[tokio::test]
+    async fn error_channel_reports_rollback_panics() {
+        let (store, mut errors) = Store::new_with_error_channel(fragile_reducer, Counter::new(0));
+
+        let preview = store.dispatch_and_rollback(CounterAction::Decrement, |counter: &Counter| counter.value).await;
+        assert_eq!(preview, 0);
+        assert_eq!(errors.recv().await.unwrap().description, "counter can't go below zero");
+
+        // The worker survived the panic
+        store.dispatch(CounterAction::Increment).await;
+        assert_eq!(store.state_cloned().await, Counter::new(1));
+    }
+
+    #[tokio::test]
+    async fn subscribe_diff_receives_changed_field() {
+        #[derive(Clone, Default)]
+        struct Profile {
+            name: String,
+            email: String,
+            age: u32,
+            city: String,
+            score: u32,
+        }
+
+        enum ProfileAction {
+            SetCity(String),
+            AddScore(u32),
+        }
+
+        fn profile_reducer(mut profile: Profile, action: ProfileAction) -> Profile {
+            match action {
+                ProfileAction::SetCity(city) => profile.city = city,
+                ProfileAction::AddScore(score) => profile.score += score,
+            }
+            profile
+        }
+
+        #[derive(Debug, PartialEq)]
+        enum ProfileChange {
+            Name(String),
+            Email(String),
+            Age(u32),
+            City(String),
+            Score(u32),
+        }
+
+        struct ProfileDiff;
+
+        impl StateDiff<Profile> for ProfileDiff {
+            type Diff = Vec<ProfileChange>;
+
+            fn diff(old: &Profile, new: &Profile) -> Option<Self::Diff> {
+                let mut changes = Vec::new();
+                if old.name != new.name {
+                    changes.push(ProfileChange::Name(new.name.clone()));
+                }
+                if old.email != new.email {
+                    changes.push(ProfileChange::Email(new.email.clone()));
+                }
+                if old.age != new.age {
+                    changes.push(ProfileChange::Age(new.age));
+                }
+                if old.city != new.city {
+                    changes.push(ProfileChange::City(new.city.clone()));
+                }
+                if old.score != new.score {
+                    changes.push(ProfileChange::Score(new.score));
+                }
+
+                (!changes.is_empty()).then_some(changes)
+            }
+        }
+
+        let store = Store::new(profile_reducer);
+        let diffs = Arc::new(Mutex::new(Vec::new()));
+        let subscriber_diffs = diffs.clone();
+        store
+            .subscribe_diff::<ProfileDiff, _>(move |diff| subscriber_diffs.lock().unwrap().push(diff))
+            .await;
+
+        store.dispatch(ProfileAction::SetCity("Ghent".to_string())).await;
+        store.dispatch(ProfileAction::AddScore(0)).await;
+        store.dispatch(ProfileAction::AddScore(5)).await;
+
+        assert_eq!(
+            *diffs.lock().unwrap(),
+            vec![vec![ProfileChange::City("Ghent".to_string())], vec![ProfileChange::Score(5)]]
+        );
+    }
+
+    #[derive(Clone, Copy, Debug, PartialEq)]
+    enum Job {
+        Idle,
+        Running,
+        Done,
+    }
+
+    #[derive(Clone, Copy, Debug, PartialEq)]
+    enum JobAction {
+        Start,
+        Finish,
+        Reset,
+    }
+
+    fn job_reducer(state: Job, action: JobAction) -> Job {
+        match (state, action) {
+            (Job::Idle, JobAction::Start) => Job::Running,
+            (Job::Running, JobAction::Finish) => Job::Done,
+            (Job::Done, JobAction::Reset) => Job::Idle,
+            (state, _) => state,
+        }
+    }
+
+    const JOB_ACTIONS: [JobAction; 3] = [JobAction::Reset, JobAction::Finish, JobAction::Start];
+
+    #[tokio::test]
+    async fn transition_to_dispatches_shortest_plan() {
+        let store = Store::new_with_state(job_reducer, Job::Idle);
+        let seen = Arc::new(Mutex::new(Vec::new()));
+        let seen_clone = seen.clone();
+        store.subscribe(move |state: &Job| seen_clone.lock().unwrap().push(*state)).await;
+
+        assert_eq!(store.transition_to(Job::Done, JOB_ACTIONS, 5).await, Ok(()));
+        assert_eq!(store.state_cloned().await, Job::Done);
+        assert_eq!(*seen.lock().unwrap(), vec![Job::Running, Job::Done]);
+
+        assert_eq!(store.transition_to(Job::Running, JOB_ACTIONS, 5).await, Ok(()));
+        assert_eq!(*seen.lock().unwrap(), vec![Job::Running, Job::Done, Job::Idle, Job::Running]);
+
+        assert_eq!(store.transition_to(Job::Running, JOB_ACTIONS, 5).await, Ok(()));
+        assert_eq!(seen.lock().unwrap().len(), 4);
+    }
+
+    #[tokio::test]
+    async fn transition_to_reports_interrupted_plan() {
+        let store = Store::new_with_state(job_reducer, Job::Idle);
+        store.on_next_dispatch(|_: &JobAction| None).await;
+
+        // Start is dropped, so Finish has no effect either
+        assert_eq!(store.transition_to(Job::Done, JOB_ACTIONS, 5).await, Err(TransitionError::TargetNotReached));
+        assert_eq!(store.state_cloned().await, Job::Idle);
+    }
+
+    #[tokio::test]
+    async fn transition_to_survives_panicking_search() {
+        let (store, mut errors) = Store::new_with_error_channel(fragile_reducer, Counter::new(0));
+
+        let actions = [CounterAction::Decrement, CounterAction::Increment];
+        assert_eq!(store.transition_to(Counter::new(2), actions, 3).await, Ok(()));
+        assert_eq!(errors.recv().await.unwrap().description, "counter can't go below zero");
+        assert_eq!(store.state_cloned().await, Counter::new(2));
+    }
+
+    #[tokio::test]
+    async fn transition_to_fails_without_plan() {
+        let store = Store::new_with_state(job_reducer, Job::Idle);
+
+        assert_eq!(store.transition_to(Job::Done, JOB_ACTIONS, 1).await, Err(TransitionError::NoPlanFound));
+        assert_eq!(
+            store.transition_to(Job::Done, [JobAction::Start, JobAction::Reset], 5).await,
+            Err(TransitionError::NoPlanFound)
+        );
+        assert_eq!(store.state_cloned().await, Job::Idle);
+    }
+
+    struct Request {
+        response: Mutex<Option<oneshot::Receiver<String>>>,
+    }
+
+    fn request_reducer(state: Request, _action: ()) -> Request {
+        state
+    }
+
+    #[tokio::test]
+    async fn select_future_awaits_selected_future() {
+        let (tx, rx) = oneshot::channel();
+        let store = Store::new_with_state(
+            request_reducer,
+            Request {
+                response: Mutex::new(Some(rx)),
+            },
+        );
+
+        tokio::spawn(async move {
+            tokio::task::yield_now().await;
+            tx.send("response".to_string()).unwrap();
+        });
+
+        let response = store
+            .select_future(|state: &Request| state.response.lock().unwrap().take().expect("response is only selected once"))
+            .await;
+        assert_eq!(response, Ok("response".to_string()));
+    }
+
+    #[tokio::test]
+    async fn counter_state_matches() {
+        let store = Store::new(counter_reducer);
+
+        assert!(store.state_matches(|state: &Counter| state.value == 42).await);
+        assert!(!store.state_matches(|state: &Counter| state.value > 42).await);
+        assert!(store.state_not_matches(|state: &Counter| state.value > 42).await);
+        assert!(!store.state_not_matches(|state: &Counter| state.value == 42).await);
+
+        store.dispatch(CounterAction::Increment).await;
+        assert!(store.state_matches(|state: &Counter| state.value > 42).await);
+    }
+
+    #[tokio::test]
+    async fn counter_subscribe_try_select_sync() {
+        let store = Store::new(counter_reducer);
+
+        let sum = Arc::new(AtomicI32::new(0));
+
+        // Use the selector on the state handed to the subscriber
+        let captured_sum = sum.clone();
+        store
+            .subscribe(move |state: &Counter| {
+                captured_sum.fetch_add(crate::try_select_sync(state, ValueSelector), Ordering::Relaxed);
+            })
+            .await;
+
+        store.dispatch(CounterAction::Increment).await;
+        store.dispatch(CounterAction::Decrement).await;
+
+        // Sum should be: 43 + 42 = 85
+        assert_eq!(sum.load(Ordering::Relaxed), 85);
+        assert_eq!(ValueSelector.select_on(&Counter::new(7)), 7);
+    }
+
+    #[tokio::test]
+    async fn counter_observe_actions() {
+        let store = Store::new(counter_reducer);
+        let mut actions = store.observe_actions().await;
+
+        store.dispatch(CounterAction::Increment).await;
+        assert_eq!(43, store.select(ValueSelector).await);
+        store.dispatch(CounterAction::Decrement).await;
+        store.dispatch(CounterAction::Increment).await;
+
+        assert_eq!(actions.recv().await, Some(CounterAction::Increment));
+        assert_eq!(actions.recv().await, Some(CounterAction::Decrement));
+        assert_eq!(actions.recv().await, Some(CounterAction::Increment));
+
+        // The stream ends once the store is gone
+        drop(store);
+        assert_eq!(actions.recv().await, None);
+    }
+
+    #[tokio::test]
+    async fn with_state_aggregate() {
+        fn push_reducer(mut state: Vec<i32>, value: i32) -> Vec<i32> {
+            state.push(value);
+            state
+        }
+
+        let store = Store::new_with_state(push_reducer, (1..=1000).collect::<Vec<i32>>());
+        store.dispatch(1001).await;
+
+        // The closure borrows the vec inside the store, only the sum is returned
+        let sum = store.with_state(|state: &Vec<i32>| state.iter().sum::<i32>()).await;
+        assert_eq!(sum, 1001 * 1002 / 2);
+    }
+
+    #[tokio::test]
+    async fn snapshot_versioned_conflict() {
+        let store = Store::new(counter_reducer);
+        assert_eq!(store.state_version().await, 0);
+
+        let first = store.snapshot_versioned().await;
+        let second = store.snapshot_versioned().await;
+        assert_eq!(
+            first,
+            VersionedSnapshot {
+                version: 0,
+                state: Counter::new(42)
+            }
+        );
+
+        store.dispatch(CounterAction::Increment).await;
+        assert_eq!(store.state_version().await, 1);
+
+        // The state changed in between, restoring must not overwrite it
+        assert_eq!(
+            store.restore_versioned(second).await,
+            Err(ConflictError::VersionMismatch { current: 1, snapshot: 0 })
+        );
+        assert_eq!(store.state_cloned().await, Counter::new(43));
+
+        // A fresh snapshot can be restored
+        let mut fresh = store.snapshot_versioned().await;
+        fresh.state = first.state;
+        assert_eq!(store.restore_versioned(fresh).await, Ok(()));
+        assert_eq!(store.state_cloned().await, Counter::new(42));
+        assert_eq!(store.state_version().await, 2);
+    }
 }
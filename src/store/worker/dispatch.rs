@@ -1,4 +1,6 @@
 use crate::store::worker::Work;
+use crate::{Selector, TransitionError};
+use std::marker::PhantomData;
 
 pub struct Dispatch<Action>
 where
@@ -26,3 +28,120 @@ where
 {
     type Result = ();
 }
+
+pub struct DispatchBatch<State, Action> {
+    actions: Vec<Action>,
+    _types: PhantomData<fn() -> State>,
+}
+
+impl<State, Action> DispatchBatch<State, Action> {
+    pub fn new(actions: Vec<Action>) -> Self {
+        DispatchBatch {
+            actions,
+            _types: Default::default(),
+        }
+    }
+
+    pub fn into_actions(self) -> Vec<Action> {
+        self.actions
+    }
+}
+
+impl<State, Action> Work for DispatchBatch<State, Action>
+where
+    State: Send,
+    Action: Send,
+{
+    type Result = State;
+}
+
+pub struct DispatchCas<State, Action, S, F> {
+    selector: S,
+    compute: F,
+    _types: PhantomData<fn(&State) -> Action>,
+}
+
+impl<State, Action, S, F> DispatchCas<State, Action, S, F>
+where
+    S: Selector<State>,
+    F: FnOnce(S::Result) -> Option<Action>,
+{
+    pub fn new(selector: S, compute: F) -> Self {
+        DispatchCas {
+            selector,
+            compute,
+            _types: Default::default(),
+        }
+    }
+
+    pub fn into_parts(self) -> (S, F) {
+        (self.selector, self.compute)
+    }
+}
+
+impl<State, Action, S, F> Work for DispatchCas<State, Action, S, F>
+where
+    State: Send,
+    Action: Send,
+    S: Selector<State> + Send,
+    F: FnOnce(S::Result) -> Option<Action> + Send,
+{
+    type Result = bool;
+}
+
+pub struct DispatchRollback<State, Action, F> {
+    action: Action,
+    inspect: F,
+    _types: PhantomData<fn(&State)>,
+}
+
+impl<State, Action, F, R> DispatchRollback<State, Action, F>
+where
+    F: FnOnce(&State) -> R,
+{
+    pub fn new(action: Action, inspect: F) -> Self {
+        DispatchRollback {
+            action,
+            inspect,
+            _types: Default::default(),
+        }
+    }
+
+    pub fn into_parts(self) -> (Action, F) {
+        (self.action, self.inspect)
+    }
+}
+
+impl<State, Action, F, R> Work for DispatchRollback<State, Action, F>
+where
+    State: Send,
+    Action: Send,
+    F: FnOnce(&State) -> R + Send,
+    R: Send,
+{
+    type Result = R;
+}
+
+pub struct TransitionTo<State, Action> {
+    target: State,
+    actions: Vec<Action>,
+    max_depth: usize,
+}
+
+impl<State, Action> TransitionTo<State, Action> {
+    pub fn new(target: State, actions: Vec<Action>, max_depth: usize) -> Self {
+        TransitionTo { target, actions, max_depth }
+    }
+
+    pub fn into_parts(self) -> (State, Vec<Action>, usize) {
+        (self.target, self.actions, self.max_depth)
+    }
+}
+
+impl<State, Action> Work for TransitionTo<State, Action>
+where
+    State: Send,
+    Action: Send,
+{
+    type Result = Result<(), TransitionError>;
+}
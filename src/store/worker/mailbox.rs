@@ -3,6 +3,8 @@ use crate::store::worker::{
     work::{StateWorkerMessage, UnitOfWork, Work},
     StateWorker,
 };
+use std::future::poll_fn;
+use std::task::Poll;
 use tokio::sync::{
     mpsc::{unbounded_channel, UnboundedReceiver, UnboundedSender},
     oneshot::channel,
@@ -16,7 +18,7 @@ where
     RootReducer: Send,
 {
     rx: UnboundedReceiver<Message<State, Action, RootReducer>>,
-    tx: UnboundedSender<Message<State, Action, RootReducer>>,
+    priority_rx: UnboundedReceiver<Message<State, Action, RootReducer>>,
 }
 
 impl<State, Action, RootReducer> Mailbox<State, Action, RootReducer>
@@ -24,17 +26,23 @@ where
     State: Send,
     RootReducer: Send,
 {
-    pub fn new() -> Self {
+    // The mailbox doesn't keep a sender itself, it's closed as soon as the last address is dropped
+    pub fn new() -> (Self, Address<State, Action, RootReducer>) {
         let (tx, rx) = unbounded_channel();
-        Mailbox { rx, tx }
-    }
-
-    pub fn address(&self) -> Address<State, Action, RootReducer> {
-        Address::new(self.tx.clone())
+        let (priority_tx, priority_rx) = unbounded_channel();
+        (Mailbox { rx, priority_rx }, Address::new(tx, priority_tx))
     }
 
+    // Priority messages are always received before the queued regular messages
     pub async fn recv(&mut self) -> Option<Message<State, Action, RootReducer>> {
-        self.rx.recv().await
+        poll_fn(|cx| {
+            if let Poll::Ready(Some(message)) = self.priority_rx.poll_recv(cx) {
+                return Poll::Ready(Some(message));
+            }
+
+            self.rx.poll_recv(cx)
+        })
+        .await
     }
 }
 
@@ -45,6 +53,7 @@ where
     RootReducer: Send,
 {
     tx: UnboundedSender<Message<State, Action, RootReducer>>,
+    priority_tx: UnboundedSender<Message<State, Action, RootReducer>>,
 }
 
 impl<State, Action, RootReducer> Address<State, Action, RootReducer>
@@ -52,17 +61,41 @@ where
     State: Send,
     RootReducer: Send,
 {
-    fn new(tx: UnboundedSender<Message<State, Action, RootReducer>>) -> Self {
-        Address { tx }
+    fn new(tx: UnboundedSender<Message<State, Action, RootReducer>>, priority_tx: UnboundedSender<Message<State, Action, RootReducer>>) -> Self {
+        Address { tx, priority_tx }
     }
 
     pub async fn send<W: Work + 'static>(&self, work: W) -> W::Result
     where
         StateWorker<State, Action, RootReducer>: HandleWork<W>,
     {
-        let (tx, rx) = channel();
+        Self::send_on(&self.tx, work).await
+    }
+
+    pub async fn send_priority<W: Work + 'static>(&self, work: W) -> W::Result
+    where
+        StateWorker<State, Action, RootReducer>: HandleWork<W>,
+    {
+        Self::send_on(&self.priority_tx, work).await
+    }
+
+    // Send the work without waiting for the result
+    pub fn send_detached<W: Work + 'static>(&self, work: W)
+    where
+        StateWorker<State, Action, RootReducer>: HandleWork<W>,
+    {
+        let (tx, _) = channel();
         let message = StateWorkerMessage::new(work, tx);
         let _ = self.tx.send(Box::new(message));
+    }
+
+    async fn send_on<W: Work + 'static>(sender: &UnboundedSender<Message<State, Action, RootReducer>>, work: W) -> W::Result
+    where
+        StateWorker<State, Action, RootReducer>: HandleWork<W>,
+    {
+        let (tx, rx) = channel();
+        let message = StateWorkerMessage::new(work, tx);
+        let _ = sender.send(Box::new(message));
         rx.await.unwrap()
     }
 }
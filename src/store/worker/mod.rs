@@ -1,17 +1,32 @@
 mod dispatch;
 mod mailbox;
+mod observe;
 mod select;
+mod snapshot;
 mod subscribe;
+mod transform;
 mod work;
 
-pub use dispatch::Dispatch;
+pub use dispatch::{Dispatch, DispatchBatch, DispatchCas, DispatchRollback, TransitionTo};
 pub use mailbox::{Address, Mailbox};
+pub use observe::{Intercept, Observe};
 pub use select::Select;
-pub use subscribe::Subscribe;
+pub use snapshot::{ReplaceState, RestoreSnapshot, StateVersion, TakeSnapshot};
+#[cfg(debug_assertions)]
+pub use subscribe::ListSubscribers;
+pub use subscribe::{ForceNotify, Subscribe, SubscribeBatch, SubscribeSeeded, SubscribeUntil};
+pub use transform::AddTransform;
 pub use work::Work;
 
-use crate::{Reducer, Selector, Subscriber};
+use crate::action_stream::ActionObserver;
+use crate::store_error::ErrorChannel;
+use crate::transition::find_plan;
+use crate::{ConflictError, Reducer, Selector, Subscriber, SubscriberInfo, SubscriptionId, TransitionError, VersionedSnapshot};
 use async_trait::async_trait;
+use observe::Interceptor;
+use std::panic::{self, AssertUnwindSafe};
+use std::time::Instant;
+use subscribe::UntilSubscriber;
 use work::HandleWork;
 
 pub struct StateWorker<State, Action, RootReducer>
@@ -22,8 +37,17 @@ where
     mailbox: Mailbox<State, Action, RootReducer>,
     root_reducer: RootReducer,
     state: Option<State>,
+    version: u64,
+    transforms: Vec<fn(State) -> State>,
 
     subscribers: Vec<Box<dyn Subscriber<State> + Send>>,
+    until_subscribers: Vec<UntilSubscriber<State>>,
+    // Parallel to subscribers
+    subscriber_infos: Vec<SubscriberInfo>,
+    next_subscription_id: u64,
+    action_observers: Vec<ActionObserver<Action>>,
+    interceptor: Option<Interceptor<Action>>,
+    error_channel: Option<ErrorChannel<State, Action>>,
 }
 
 impl<State, Action, RootReducer> StateWorker<State, Action, RootReducer>
@@ -32,18 +56,31 @@ where
     State: Send,
     RootReducer: Send,
 {
-    pub fn new(root_reducer: RootReducer, state: State) -> Self {
-        Self {
-            mailbox: Mailbox::new(),
+    pub fn new(root_reducer: RootReducer, state: State) -> (Self, Address<State, Action, RootReducer>) {
+        let (mailbox, address) = Mailbox::new();
+
+        let worker = Self {
+            mailbox,
             root_reducer,
             state: Some(state),
+            version: 0,
+            transforms: Default::default(),
 
             subscribers: Default::default(),
-        }
+            until_subscribers: Default::default(),
+            subscriber_infos: Default::default(),
+            next_subscription_id: 0,
+            action_observers: Default::default(),
+            interceptor: None,
+            error_channel: None,
+        };
+
+        (worker, address)
     }
 
-    pub fn address(&self) -> Address<State, Action, RootReducer> {
-        self.mailbox.address()
+    // Catch panics in the reducer and the transforms and report them on the channel, instead of stopping the worker
+    pub fn set_error_channel(&mut self, channel: ErrorChannel<State, Action>) {
+        self.error_channel = Some(channel);
     }
 
     pub async fn run(&mut self) {
@@ -51,6 +88,93 @@ where
             work.execute(self).await;
         }
     }
+
+    fn register_subscriber(&mut self) -> SubscriberInfo {
+        let id = SubscriptionId(self.next_subscription_id);
+        self.next_subscription_id += 1;
+
+        SubscriberInfo {
+            id,
+            registered_at: Instant::now(),
+        }
+    }
+
+    fn reduce_unchecked(&self, state: State, action: Action) -> State {
+        let state = self.root_reducer.reduce(state, action);
+        self.transforms.iter().fold(state, |state, transform| transform(state))
+    }
+
+    // Runs the reducer and transforms like reduce_unchecked.
+    // With an error channel a panic is caught and reported, None is returned instead of unwinding the worker.
+    // Every reducer call of the worker goes through here, so no panic of a reducer can kill the worker of such a store.
+    fn try_reduce(&self, state: State, action: Action) -> Option<State> {
+        let channel = match &self.error_channel {
+            None => return Some(self.reduce_unchecked(state, action)),
+            Some(channel) => channel,
+        };
+
+        // The reducer consumes the action, a copy is kept to report it
+        let reported_action = (channel.clone_action)(&action);
+        match panic::catch_unwind(AssertUnwindSafe(|| self.reduce_unchecked(state, action))) {
+            Ok(new_state) => Some(new_state),
+            Err(payload) => {
+                channel.report_panic(reported_action, payload.as_ref());
+                None
+            }
+        }
+    }
+
+    // Runs the action through the interceptor, observers, reducer and transforms without notifying the subscribers.
+    // Returns false if the interceptor dropped the action, or if the reducer panicked and the panic was reported.
+    fn reduce(&mut self, action: Action) -> bool {
+        let mut action = action;
+
+        if let Some(interceptor) = self.interceptor.take() {
+            match interceptor(&action) {
+                Some(replacement) => action = replacement,
+                None => return false,
+            }
+        }
+
+        // Observers are dropped as soon as their stream is closed
+        if !self.action_observers.is_empty() {
+            self.action_observers.retain(|observer| observer(&action));
+        }
+
+        let old_state = self.state.take().unwrap();
+        // With an error channel a copy is kept to restore the state if the reducer panics
+        let previous_state = self.error_channel.as_ref().map(|channel| (channel.clone_state)(&old_state));
+        let new_state = match self.try_reduce(old_state, action) {
+            Some(new_state) => new_state,
+            None => {
+                self.state = previous_state;
+                return false;
+            }
+        };
+
+        self.state = Some(new_state);
+        self.version += 1;
+
+        true
+    }
+
+    fn notify_subscribers(&mut self) {
+        if !self.subscribers.is_empty() {
+            let state = self.state.as_ref().unwrap();
+            for subscriber in &self.subscribers {
+                subscriber.notify(state)
+            }
+        }
+
+        // Subscribers are notified of the state meeting their condition, then they are removed
+        if !self.until_subscribers.is_empty() {
+            let state = self.state.as_ref().unwrap();
+            self.until_subscribers.retain(|until| {
+                until.subscriber.notify(state);
+                !(until.condition)(state)
+            });
+        }
+    }
 }
 
 #[async_trait]
@@ -62,19 +186,115 @@ where
     Action: Send,
 {
     async fn handle_work(&mut self, work: Dispatch<Action>) {
-        let action = work.into_action();
+        if self.reduce(work.into_action()) {
+            self.notify_subscribers();
+        }
+    }
+}
 
-        let old_state = self.state.take().unwrap();
-        let new_state = self.root_reducer.reduce(old_state, action);
+#[async_trait]
+impl<State, Action, RootReducer> HandleWork<DispatchBatch<State, Action>> for StateWorker<State, Action, RootReducer>
+where
+    RootReducer: Reducer<State, Action>,
+    State: Clone + Send,
+    RootReducer: Send,
+    Action: Send,
+{
+    async fn handle_work(&mut self, work: DispatchBatch<State, Action>) -> State {
+        let mut reduced = false;
+        for action in work.into_actions() {
+            reduced |= self.reduce(action);
+        }
 
-        self.state = Some(new_state);
+        // Subscribers are notified once for the whole batch
+        if reduced {
+            self.notify_subscribers();
+        }
 
-        if !self.subscribers.is_empty() {
-            let new_state = self.state.as_ref().unwrap();
-            for subscriber in &self.subscribers {
-                subscriber.notify(new_state)
+        self.state.clone().unwrap()
+    }
+}
+
+#[async_trait]
+impl<State, Action, RootReducer, S, F> HandleWork<DispatchCas<State, Action, S, F>> for StateWorker<State, Action, RootReducer>
+where
+    RootReducer: Reducer<State, Action>,
+    State: Send,
+    RootReducer: Send,
+    Action: Send,
+    S: Selector<State> + Send + 'static,
+    F: FnOnce(S::Result) -> Option<Action> + Send + 'static,
+{
+    async fn handle_work(&mut self, work: DispatchCas<State, Action, S, F>) -> bool {
+        let (selector, compute) = work.into_parts();
+
+        let selected = selector.select(self.state.as_ref().unwrap());
+        let action = match compute(selected) {
+            Some(action) => action,
+            None => return false,
+        };
+
+        let reduced = self.reduce(action);
+        if reduced {
+            self.notify_subscribers();
+        }
+
+        reduced
+    }
+}
+
+#[async_trait]
+impl<State, Action, RootReducer, F, R> HandleWork<DispatchRollback<State, Action, F>> for StateWorker<State, Action, RootReducer>
+where
+    RootReducer: Reducer<State, Action>,
+    State: Send + Clone,
+    RootReducer: Send,
+    Action: Send,
+    F: FnOnce(&State) -> R + Send + 'static,
+    R: Send,
+{
+    async fn handle_work(&mut self, work: DispatchRollback<State, Action, F>) -> R {
+        let (action, inspect) = work.into_parts();
+
+        // The action is reduced on a copy, so rolling back is a matter of dropping the copy
+        let state = self.state.as_ref().unwrap().clone();
+        match self.try_reduce(state, action) {
+            Some(state) => inspect(&state),
+            // The panic is reported, the action behaves as if it didn't change anything
+            None => inspect(self.state.as_ref().unwrap()),
+        }
+    }
+}
+
+#[async_trait]
+impl<State, Action, RootReducer> HandleWork<TransitionTo<State, Action>> for StateWorker<State, Action, RootReducer>
+where
+    RootReducer: Reducer<State, Action>,
+    State: Send + Clone + PartialEq,
+    RootReducer: Send,
+    Action: Send + Clone,
+{
+    async fn handle_work(&mut self, work: TransitionTo<State, Action>) -> Result<(), TransitionError> {
+        let (target, actions, max_depth) = work.into_parts();
+
+        // The search runs on copies of the state, the store only changes once a plan is found
+        let plan = find_plan(self.state.as_ref().unwrap(), &target, &actions, max_depth, |state, action| {
+            self.try_reduce(state, action)
+        })
+        .ok_or(TransitionError::NoPlanFound)?;
+
+        for index in plan {
+            if self.reduce(actions[index].clone()) {
+                self.notify_subscribers();
             }
         }
+
+        // An action of the plan can still be dropped by the interceptor, or its reducer can panic
+        if *self.state.as_ref().unwrap() != target {
+            return Err(TransitionError::TargetNotReached);
+        }
+
+        Ok(())
     }
 }
 
@@ -103,6 +323,193 @@ where
 {
     async fn handle_work(&mut self, work: Subscribe<State>) {
         let subscriber = work.into_subscriber();
+        let info = self.register_subscriber();
+
         self.subscribers.push(subscriber);
+        self.subscriber_infos.push(info);
+    }
+}
+
+#[async_trait]
+impl<State, Action, RootReducer> HandleWork<SubscribeBatch<State>> for StateWorker<State, Action, RootReducer>
+where
+    RootReducer: Reducer<State, Action>,
+    State: Send,
+    RootReducer: Send,
+{
+    async fn handle_work(&mut self, work: SubscribeBatch<State>) {
+        for subscriber in work.into_subscribers() {
+            let info = self.register_subscriber();
+
+            self.subscribers.push(subscriber);
+            self.subscriber_infos.push(info);
+        }
+    }
+}
+
+#[async_trait]
+impl<State, Action, RootReducer> HandleWork<SubscribeUntil<State>> for StateWorker<State, Action, RootReducer>
+where
+    RootReducer: Reducer<State, Action>,
+    State: Send,
+    RootReducer: Send,
+{
+    async fn handle_work(&mut self, work: SubscribeUntil<State>) {
+        let (subscriber, condition) = work.into_parts();
+        let info = self.register_subscriber();
+
+        self.until_subscribers.push(UntilSubscriber { subscriber, condition, info });
+    }
+}
+
+#[async_trait]
+impl<State, Action, RootReducer> HandleWork<SubscribeSeeded<State>> for StateWorker<State, Action, RootReducer>
+where
+    RootReducer: Reducer<State, Action>,
+    State: Send,
+    RootReducer: Send,
+{
+    async fn handle_work(&mut self, work: SubscribeSeeded<State>) {
+        let (subscriber, condition) = work.into_parts();
+
+        let state = self.state.as_ref().unwrap();
+        subscriber.notify(state);
+        if condition(state) {
+            return;
+        }
+
+        let info = self.register_subscriber();
+        self.until_subscribers.push(UntilSubscriber { subscriber, condition, info });
+    }
+}
+
+#[async_trait]
+impl<State, Action, RootReducer> HandleWork<ForceNotify> for StateWorker<State, Action, RootReducer>
+where
+    RootReducer: Reducer<State, Action>,
+    State: Send,
+    RootReducer: Send,
+{
+    async fn handle_work(&mut self, _work: ForceNotify) {
+        self.notify_subscribers();
+    }
+}
+
+#[async_trait]
+impl<State, Action, RootReducer> HandleWork<AddTransform<State>> for StateWorker<State, Action, RootReducer>
+where
+    RootReducer: Reducer<State, Action>,
+    State: Send,
+    RootReducer: Send,
+{
+    async fn handle_work(&mut self, work: AddTransform<State>) {
+        self.transforms.push(work.into_transform());
+    }
+}
+
+#[cfg(debug_assertions)]
+#[async_trait]
+impl<State, Action, RootReducer> HandleWork<ListSubscribers> for StateWorker<State, Action, RootReducer>
+where
+    RootReducer: Reducer<State, Action>,
+    State: Send,
+    RootReducer: Send,
+{
+    async fn handle_work(&mut self, _work: ListSubscribers) -> Vec<SubscriberInfo> {
+        let until_infos = self.until_subscribers.iter().map(|until| until.info.clone());
+        self.subscriber_infos.iter().cloned().chain(until_infos).collect()
+    }
+}
+
+#[async_trait]
+impl<State, Action, RootReducer> HandleWork<Observe<Action>> for StateWorker<State, Action, RootReducer>
+where
+    RootReducer: Reducer<State, Action>,
+    State: Send,
+    RootReducer: Send,
+    Action: Send,
+{
+    async fn handle_work(&mut self, work: Observe<Action>) {
+        let observer = work.into_observer();
+        self.action_observers.push(observer);
+    }
+}
+
+#[async_trait]
+impl<State, Action, RootReducer> HandleWork<Intercept<Action>> for StateWorker<State, Action, RootReducer>
+where
+    RootReducer: Reducer<State, Action>,
+    State: Send,
+    RootReducer: Send,
+    Action: Send,
+{
+    async fn handle_work(&mut self, work: Intercept<Action>) {
+        self.interceptor = Some(work.into_interceptor());
+    }
+}
+
+#[async_trait]
+impl<State, Action, RootReducer> HandleWork<TakeSnapshot<State>> for StateWorker<State, Action, RootReducer>
+where
+    RootReducer: Reducer<State, Action>,
+    State: Clone + Send,
+    RootReducer: Send,
+{
+    async fn handle_work(&mut self, _work: TakeSnapshot<State>) -> VersionedSnapshot<State> {
+        VersionedSnapshot {
+            version: self.version,
+            state: self.state.as_ref().unwrap().clone(),
+        }
+    }
+}
+
+#[async_trait]
+impl<State, Action, RootReducer> HandleWork<RestoreSnapshot<State>> for StateWorker<State, Action, RootReducer>
+where
+    RootReducer: Reducer<State, Action>,
+    State: Send,
+    RootReducer: Send,
+{
+    async fn handle_work(&mut self, work: RestoreSnapshot<State>) -> Result<(), ConflictError> {
+        let snapshot = work.into_snapshot();
+        if snapshot.version != self.version {
+            return Err(ConflictError::VersionMismatch {
+                current: self.version,
+                snapshot: snapshot.version,
+            });
+        }
+
+        self.state = Some(snapshot.state);
+        self.version += 1;
+
+        self.notify_subscribers();
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl<State, Action, RootReducer> HandleWork<ReplaceState<State>> for StateWorker<State, Action, RootReducer>
+where
+    RootReducer: Reducer<State, Action>,
+    State: Send,
+    RootReducer: Send,
+{
+    async fn handle_work(&mut self, work: ReplaceState<State>) {
+        self.state = Some(work.into_state());
+        self.version += 1;
+
+        self.notify_subscribers();
+    }
+}
+
+#[async_trait]
+impl<State, Action, RootReducer> HandleWork<StateVersion> for StateWorker<State, Action, RootReducer>
+where
+    RootReducer: Reducer<State, Action>,
+    State: Send,
+    RootReducer: Send,
+{
+    async fn handle_work(&mut self, _work: StateVersion) -> u64 {
+        self.version
     }
 }
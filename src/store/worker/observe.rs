@@ -0,0 +1,46 @@
+use crate::action_stream::ActionObserver;
+use crate::store::worker::Work;
+
+pub struct Observe<Action> {
+    observer: ActionObserver<Action>,
+}
+
+impl<Action> Observe<Action> {
+    pub fn new(observer: ActionObserver<Action>) -> Self {
+        Observe { observer }
+    }
+
+    pub fn into_observer(self) -> ActionObserver<Action> {
+        self.observer
+    }
+}
+
+impl<Action> Work for Observe<Action>
+where
+    Action: Send,
+{
+    type Result = ();
+}
+
+pub type Interceptor<Action> = Box<dyn FnOnce(&Action) -> Option<Action> + Send>;
+
+pub struct Intercept<Action> {
+    interceptor: Interceptor<Action>,
+}
+
+impl<Action> Intercept<Action> {
+    pub fn new(interceptor: Interceptor<Action>) -> Self {
+        Intercept { interceptor }
+    }
+
+    pub fn into_interceptor(self) -> Interceptor<Action> {
+        self.interceptor
+    }
+}
+
+impl<Action> Work for Intercept<Action>
+where
+    Action: Send,
+{
+    type Result = ();
+}
@@ -0,0 +1,68 @@
+use crate::store::worker::Work;
+use crate::{ConflictError, VersionedSnapshot};
+use std::marker::PhantomData;
+
+pub struct TakeSnapshot<State> {
+    _types: PhantomData<fn() -> State>,
+}
+
+impl<State> TakeSnapshot<State> {
+    pub fn new() -> Self {
+        TakeSnapshot { _types: Default::default() }
+    }
+}
+
+impl<State> Work for TakeSnapshot<State>
+where
+    State: Send,
+{
+    type Result = VersionedSnapshot<State>;
+}
+
+pub struct RestoreSnapshot<State> {
+    snapshot: VersionedSnapshot<State>,
+}
+
+impl<State> RestoreSnapshot<State> {
+    pub fn new(snapshot: VersionedSnapshot<State>) -> Self {
+        RestoreSnapshot { snapshot }
+    }
+
+    pub fn into_snapshot(self) -> VersionedSnapshot<State> {
+        self.snapshot
+    }
+}
+
+impl<State> Work for RestoreSnapshot<State>
+where
+    State: Send,
+{
+    type Result = Result<(), ConflictError>;
+}
+
+pub struct ReplaceState<State> {
+    state: State,
+}
+
+impl<State> ReplaceState<State> {
+    pub fn new(state: State) -> Self {
+        ReplaceState { state }
+    }
+
+    pub fn into_state(self) -> State {
+        self.state
+    }
+}
+
+impl<State> Work for ReplaceState<State>
+where
+    State: Send,
+{
+    type Result = ();
+}
+
+pub struct StateVersion;
+
+impl Work for StateVersion {
+    type Result = u64;
+}
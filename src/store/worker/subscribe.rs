@@ -1,5 +1,5 @@
 use crate::store::worker::Work;
-use crate::Subscriber;
+use crate::{Subscriber, SubscriberInfo};
 use std::marker::PhantomData;
 
 pub struct Subscribe<State> {
@@ -26,3 +26,94 @@ where
 {
     type Result = ();
 }
+
+pub struct SubscribeBatch<State> {
+    subscribers: Vec<Box<dyn Subscriber<State> + Send>>,
+}
+
+impl<State> SubscribeBatch<State> {
+    pub fn new(subscribers: Vec<Box<dyn Subscriber<State> + Send>>) -> Self {
+        SubscribeBatch { subscribers }
+    }
+
+    pub fn into_subscribers(self) -> Vec<Box<dyn Subscriber<State> + Send>> {
+        self.subscribers
+    }
+}
+
+impl<State> Work for SubscribeBatch<State>
+where
+    State: Send,
+{
+    type Result = ();
+}
+
+pub type Condition<State> = Box<dyn Fn(&State) -> bool + Send>;
+
+// A subscriber which is removed once its condition is met
+pub struct UntilSubscriber<State> {
+    pub subscriber: Box<dyn Subscriber<State> + Send>,
+    pub condition: Condition<State>,
+    // Only read by list_subscribers, which is a debug-only method
+    #[cfg_attr(not(debug_assertions), allow(dead_code))]
+    pub info: SubscriberInfo,
+}
+
+pub struct SubscribeUntil<State> {
+    subscriber: Box<dyn Subscriber<State> + Send>,
+    condition: Condition<State>,
+}
+
+impl<State> SubscribeUntil<State> {
+    pub fn new(subscriber: Box<dyn Subscriber<State> + Send>, condition: Condition<State>) -> Self {
+        SubscribeUntil { subscriber, condition }
+    }
+
+    pub fn into_parts(self) -> (Box<dyn Subscriber<State> + Send>, Condition<State>) {
+        (self.subscriber, self.condition)
+    }
+}
+
+impl<State> Work for SubscribeUntil<State>
+where
+    State: Send,
+{
+    type Result = ();
+}
+
+// Like SubscribeUntil, but the subscriber is notified of the current state right away
+pub struct SubscribeSeeded<State> {
+    subscriber: Box<dyn Subscriber<State> + Send>,
+    condition: Condition<State>,
+}
+
+impl<State> SubscribeSeeded<State> {
+    pub fn new(subscriber: Box<dyn Subscriber<State> + Send>, condition: Condition<State>) -> Self {
+        SubscribeSeeded { subscriber, condition }
+    }
+
+    pub fn into_parts(self) -> (Box<dyn Subscriber<State> + Send>, Condition<State>) {
+        (self.subscriber, self.condition)
+    }
+}
+
+impl<State> Work for SubscribeSeeded<State>
+where
+    State: Send,
+{
+    type Result = ();
+}
+
+#[cfg(debug_assertions)]
+pub struct ListSubscribers;
+
+#[cfg(debug_assertions)]
+impl Work for ListSubscribers {
+    type Result = Vec<SubscriberInfo>;
+}
+
+pub struct ForceNotify;
+
+impl Work for ForceNotify {
+    type Result = ();
+}
@@ -0,0 +1,22 @@
+use crate::store::worker::Work;
+
+pub struct AddTransform<State> {
+    transform: fn(State) -> State,
+}
+
+impl<State> AddTransform<State> {
+    pub fn new(transform: fn(State) -> State) -> Self {
+        AddTransform { transform }
+    }
+
+    pub fn into_transform(self) -> fn(State) -> State {
+        self.transform
+    }
+}
+
+impl<State> Work for AddTransform<State>
+where
+    State: Send,
+{
+    type Result = ();
+}
@@ -0,0 +1,483 @@
+use crate::{ActionMappedStore, MappedStore, MetaEnvelope, Selector, StoreApi, TaggedAction};
+use async_trait::async_trait;
+use std::sync::{Arc, Mutex};
+use tokio::sync::oneshot;
+
+/// Convenience methods built on top of the `StoreApi` primitives.
+///
+/// Every `StoreApi` implements this trait, so bringing it into scope is all you need.
+///
+/// ## Example
+/// ```
+/// use redux_rs::{Store, StoreApiExt};
+///
+/// #[derive(Default)]
+/// struct Counter(i8);
+///
+/// enum Action {
+///     Increment,
+///     Decrement,
+/// }
+///
+/// fn reducer(state: Counter, action: Action) -> Counter {
+///     match action {
+///         Action::Increment => Counter(state.0 + 1),
+///         Action::Decrement => Counter(state.0 - 1),
+///     }
+/// }
+///
+/// # #[tokio::main(flavor = "current_thread")]
+/// # async fn main() {
+/// let store = Store::new(reducer);
+/// store.dispatch_sequence(vec![Action::Increment, Action::Increment, Action::Decrement]).await;
+///
+/// assert_eq!(store.select_field(|state: &Counter| state.0).await, 1);
+/// assert!(store.is_state(|state: &Counter| state.0 > 0).await);
+/// # }
+/// ```
+#[async_trait]
+pub trait StoreApiExt<State, Action>: StoreApi<State, Action>
+where
+    Action: Send + 'static,
+    State: Send + 'static,
+{
+    /// Dispatch all actions in order, every action is processed before the next one is dispatched.
+    async fn dispatch_sequence<I>(&self, actions: I)
+    where
+        I: IntoIterator<Item = Action> + Send,
+        I::IntoIter: Send,
+        Self: Sync,
+    {
+        for action in actions {
+            self.dispatch(action).await;
+        }
+    }
+
+    /// Select a single field of the state with a plain function or closure.
+    async fn select_field<T, F>(&self, f: F) -> T
+    where
+        F: Fn(&State) -> T + Send + 'static,
+        T: Send + 'static,
+        Self: Sync,
+    {
+        self.select(f).await
+    }
+
+    /// Dispatch an action wrapped in a `MetaEnvelope` together with its metadata.
+    async fn dispatch_with_meta<A, Meta>(&self, action: A, meta: Meta)
+    where
+        MetaEnvelope<A, Meta>: Into<Action>,
+        A: Send,
+        Meta: Send,
+        Self: Sync,
+    {
+        self.dispatch(MetaEnvelope::new(action, meta)).await
+    }
+
+    /// Dispatch an action together with a static tag, see `TaggedAction`.
+    async fn dispatch_tagged<A>(&self, action: A, tag: &'static str)
+    where
+        TaggedAction<A>: Into<Action>,
+        A: Send,
+        Self: Sync,
+    {
+        self.dispatch(TaggedAction::new(action, tag)).await
+    }
+
+    /// Dispatch an action and wait until the state satisfies the condition, the matching state is returned.
+    ///
+    /// The condition is checked for every state change after the dispatch, including the ones caused by other dispatches.
+    /// This makes it possible to wait for thunks which dispatch actions in the background.
+    /// If the condition is never met this method never returns, combine it with a timeout where needed.
+    async fn dispatch_and_wait_for_effect<A, F>(&self, action: A, condition: F) -> State
+    where
+        A: Into<Action> + Send,
+        F: Fn(&State) -> bool + Send + Sync + 'static,
+        State: Clone,
+        Self: Sync,
+    {
+        let (tx, rx) = oneshot::channel();
+        let tx = Mutex::new(Some(tx));
+        let condition = Arc::new(condition);
+
+        let subscriber_condition = condition.clone();
+        let subscriber = move |state: &State| {
+            if subscriber_condition(state) {
+                if let Some(tx) = tx.lock().unwrap().take() {
+                    let _ = tx.send(state.clone());
+                }
+            }
+        };
+
+        self.subscribe_until(subscriber, move |state: &State| condition(state)).await;
+        self.dispatch(action).await;
+
+        rx.await.expect("subscribers live as long as the store")
+    }
+
+    /// Check whether the current state satisfies the predicate.
+    async fn is_state<P>(&self, predicate: P) -> bool
+    where
+        P: Fn(&State) -> bool + Send + 'static,
+        Self: Sync,
+    {
+        self.select(predicate).await
+    }
+
+    /// Run three selectors on the same state and return all results, like `StoreApi::select_zip` does for two.
+    /// The selectors run in a single round-trip to the store, no action can be reduced in between them.
+    async fn select_zip3<S1, S2, S3>(&self, s1: S1, s2: S2, s3: S3) -> (S1::Result, S2::Result, S3::Result)
+    where
+        S1: Selector<State> + Send + 'static,
+        S2: Selector<State> + Send + 'static,
+        S3: Selector<State> + Send + 'static,
+        S1::Result: Send + 'static,
+        S2::Result: Send + 'static,
+        S3::Result: Send + 'static,
+        Self: Sync,
+    {
+        self.select(move |state: &State| (s1.select(state), s2.select(state), s3.select(state))).await
+    }
+
+    /// Like `select_zip3`, with four selectors.
+    async fn select_zip4<S1, S2, S3, S4>(&self, s1: S1, s2: S2, s3: S3, s4: S4) -> (S1::Result, S2::Result, S3::Result, S4::Result)
+    where
+        S1: Selector<State> + Send + 'static,
+        S2: Selector<State> + Send + 'static,
+        S3: Selector<State> + Send + 'static,
+        S4: Selector<State> + Send + 'static,
+        S1::Result: Send + 'static,
+        S2::Result: Send + 'static,
+        S3::Result: Send + 'static,
+        S4::Result: Send + 'static,
+        Self: Sync,
+    {
+        self.select(move |state: &State| (s1.select(state), s2.select(state), s3.select(state), s4.select(state)))
+            .await
+    }
+
+    /// Like `select_zip3`, with five selectors.
+    async fn select_zip5<S1, S2, S3, S4, S5>(&self, s1: S1, s2: S2, s3: S3, s4: S4, s5: S5) -> (S1::Result, S2::Result, S3::Result, S4::Result, S5::Result)
+    where
+        S1: Selector<State> + Send + 'static,
+        S2: Selector<State> + Send + 'static,
+        S3: Selector<State> + Send + 'static,
+        S4: Selector<State> + Send + 'static,
+        S5: Selector<State> + Send + 'static,
+        S1::Result: Send + 'static,
+        S2::Result: Send + 'static,
+        S3::Result: Send + 'static,
+        S4::Result: Send + 'static,
+        S5::Result: Send + 'static,
+        Self: Sync,
+    {
+        self.select(move |state: &State| (s1.select(state), s2.select(state), s3.select(state), s4.select(state), s5.select(state)))
+            .await
+    }
+
+    /// Like `select_zip3`, with six selectors.
+    async fn select_zip6<S1, S2, S3, S4, S5, S6>(
+        &self,
+        s1: S1,
+        s2: S2,
+        s3: S3,
+        s4: S4,
+        s5: S5,
+        s6: S6,
+    ) -> (S1::Result, S2::Result, S3::Result, S4::Result, S5::Result, S6::Result)
+    where
+        S1: Selector<State> + Send + 'static,
+        S2: Selector<State> + Send + 'static,
+        S3: Selector<State> + Send + 'static,
+        S4: Selector<State> + Send + 'static,
+        S5: Selector<State> + Send + 'static,
+        S6: Selector<State> + Send + 'static,
+        S1::Result: Send + 'static,
+        S2::Result: Send + 'static,
+        S3::Result: Send + 'static,
+        S4::Result: Send + 'static,
+        S5::Result: Send + 'static,
+        S6::Result: Send + 'static,
+        Self: Sync,
+    {
+        self.select(move |state: &State| {
+            (
+                s1.select(state),
+                s2.select(state),
+                s3.select(state),
+                s4.select(state),
+                s5.select(state),
+                s6.select(state),
+            )
+        })
+        .await
+    }
+
+    /// Like `select_zip3`, with seven selectors.
+    #[allow(clippy::too_many_arguments)]
+    async fn select_zip7<S1, S2, S3, S4, S5, S6, S7>(
+        &self,
+        s1: S1,
+        s2: S2,
+        s3: S3,
+        s4: S4,
+        s5: S5,
+        s6: S6,
+        s7: S7,
+    ) -> (S1::Result, S2::Result, S3::Result, S4::Result, S5::Result, S6::Result, S7::Result)
+    where
+        S1: Selector<State> + Send + 'static,
+        S2: Selector<State> + Send + 'static,
+        S3: Selector<State> + Send + 'static,
+        S4: Selector<State> + Send + 'static,
+        S5: Selector<State> + Send + 'static,
+        S6: Selector<State> + Send + 'static,
+        S7: Selector<State> + Send + 'static,
+        S1::Result: Send + 'static,
+        S2::Result: Send + 'static,
+        S3::Result: Send + 'static,
+        S4::Result: Send + 'static,
+        S5::Result: Send + 'static,
+        S6::Result: Send + 'static,
+        S7::Result: Send + 'static,
+        Self: Sync,
+    {
+        self.select(move |state: &State| {
+            (
+                s1.select(state),
+                s2.select(state),
+                s3.select(state),
+                s4.select(state),
+                s5.select(state),
+                s6.select(state),
+                s7.select(state),
+            )
+        })
+        .await
+    }
+
+    /// Like `select_zip3`, with eight selectors.
+    #[allow(clippy::too_many_arguments)]
+    async fn select_zip8<S1, S2, S3, S4, S5, S6, S7, S8>(
+        &self,
+        s1: S1,
+        s2: S2,
+        s3: S3,
+        s4: S4,
+        s5: S5,
+        s6: S6,
+        s7: S7,
+        s8: S8,
+    ) -> (S1::Result, S2::Result, S3::Result, S4::Result, S5::Result, S6::Result, S7::Result, S8::Result)
+    where
+        S1: Selector<State> + Send + 'static,
+        S2: Selector<State> + Send + 'static,
+        S3: Selector<State> + Send + 'static,
+        S4: Selector<State> + Send + 'static,
+        S5: Selector<State> + Send + 'static,
+        S6: Selector<State> + Send + 'static,
+        S7: Selector<State> + Send + 'static,
+        S8: Selector<State> + Send + 'static,
+        S1::Result: Send + 'static,
+        S2::Result: Send + 'static,
+        S3::Result: Send + 'static,
+        S4::Result: Send + 'static,
+        S5::Result: Send + 'static,
+        S6::Result: Send + 'static,
+        S7::Result: Send + 'static,
+        S8::Result: Send + 'static,
+        Self: Sync,
+    {
+        self.select(move |state: &State| {
+            (
+                s1.select(state),
+                s2.select(state),
+                s3.select(state),
+                s4.select(state),
+                s5.select(state),
+                s6.select(state),
+                s7.select(state),
+                s8.select(state),
+            )
+        })
+        .await
+    }
+
+    /// View the store through a different state type, `to_new` maps the state for every select and subscriber.
+    /// The mapped store dispatches the same actions as the original store.
+    fn map_state<NewState>(self, to_new: fn(&State) -> NewState) -> MappedStore<Self, State, NewState>
+    where
+        Self: Sized,
+    {
+        MappedStore::new(self, to_new)
+    }
+
+    /// Accept a different action type, every action is converted with `to_inner` before it's dispatched to this store.
+    /// This is useful to expose only a part of the actions, e.g. the actions of a single feature of the root action.
+    fn map_action<OuterAction, F>(self, to_inner: F) -> ActionMappedStore<Self, OuterAction, F>
+    where
+        F: Fn(OuterAction) -> Action + Send + Sync + 'static,
+        Self: Sized,
+    {
+        ActionMappedStore::new(self, to_inner)
+    }
+}
+
+impl<T, State, Action> StoreApiExt<State, Action> for T
+where
+    T: StoreApi<State, Action> + ?Sized,
+    Action: Send + 'static,
+    State: Send + 'static,
+{
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{MiddleWare, Store};
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::Arc;
+
+    #[derive(Default)]
+    struct Counter {
+        value: i32,
+    }
+
+    enum CounterAction {
+        Increment,
+        Decrement,
+    }
+
+    fn counter_reducer(state: Counter, action: CounterAction) -> Counter {
+        match action {
+            CounterAction::Increment => Counter { value: state.value + 1 },
+            CounterAction::Decrement => Counter { value: state.value - 1 },
+        }
+    }
+
+    struct CountingMiddleware {
+        count: Arc<AtomicUsize>,
+    }
+
+    #[async_trait]
+    impl<Inner> MiddleWare<Counter, CounterAction, Inner> for CountingMiddleware
+    where
+        Inner: StoreApi<Counter, CounterAction> + Send + Sync,
+    {
+        async fn dispatch(&self, action: CounterAction, inner: &Arc<Inner>) {
+            self.count.fetch_add(1, Ordering::Relaxed);
+            inner.dispatch(action).await;
+        }
+    }
+
+    async fn exercise<Api>(store: &Api)
+    where
+        Api: StoreApi<Counter, CounterAction> + Sync,
+    {
+        store
+            .dispatch_sequence(vec![CounterAction::Increment, CounterAction::Increment, CounterAction::Decrement])
+            .await;
+
+        assert_eq!(store.select_field(|state: &Counter| state.value).await, 1);
+        assert!(store.is_state(|state: &Counter| state.value == 1).await);
+        assert!(!store.is_state(|state: &Counter| state.value == 2).await);
+    }
+
+    #[tokio::test]
+    async fn store_ext() {
+        let store = Store::new(counter_reducer);
+        exercise(&store).await;
+    }
+
+    #[tokio::test]
+    async fn store_with_middleware_ext() {
+        let count = Arc::new(AtomicUsize::new(0));
+        let store = Store::new(counter_reducer).wrap(CountingMiddleware { count: count.clone() }).await;
+        exercise(&store).await;
+
+        // Every action of the sequence went through the middleware
+        assert_eq!(count.load(Ordering::Relaxed), 3);
+    }
+
+    #[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+    async fn select_zip_variants_see_one_state() {
+        let store = Store::new_arc(counter_reducer);
+
+        let dispatcher = store.clone();
+        let dispatches = tokio::spawn(async move {
+            for _ in 0..200 {
+                dispatcher.dispatch(CounterAction::Increment).await;
+            }
+        });
+
+        for _ in 0..20 {
+            let (a, b, c) = store
+                .select_zip3(|s: &Counter| s.value, |s: &Counter| s.value * 2, |s: &Counter| s.value * 3)
+                .await;
+            assert_eq!((b, c), (a * 2, a * 3));
+
+            let (a, b, c, d) = store
+                .select_zip4(
+                    |s: &Counter| s.value,
+                    |s: &Counter| s.value * 2,
+                    |s: &Counter| s.value * 3,
+                    |s: &Counter| s.value * 4,
+                )
+                .await;
+            assert_eq!((b, c, d), (a * 2, a * 3, a * 4));
+
+            let (a, b, c, d, e) = store
+                .select_zip5(
+                    |s: &Counter| s.value,
+                    |s: &Counter| s.value * 2,
+                    |s: &Counter| s.value * 3,
+                    |s: &Counter| s.value * 4,
+                    |s: &Counter| s.value * 5,
+                )
+                .await;
+            assert_eq!((b, c, d, e), (a * 2, a * 3, a * 4, a * 5));
+
+            let (a, b, c, d, e, f) = store
+                .select_zip6(
+                    |s: &Counter| s.value,
+                    |s: &Counter| s.value * 2,
+                    |s: &Counter| s.value * 3,
+                    |s: &Counter| s.value * 4,
+                    |s: &Counter| s.value * 5,
+                    |s: &Counter| s.value * 6,
+                )
+                .await;
+            assert_eq!((b, c, d, e, f), (a * 2, a * 3, a * 4, a * 5, a * 6));
+
+            let (a, b, c, d, e, f, g) = store
+                .select_zip7(
+                    |s: &Counter| s.value,
+                    |s: &Counter| s.value * 2,
+                    |s: &Counter| s.value * 3,
+                    |s: &Counter| s.value * 4,
+                    |s: &Counter| s.value * 5,
+                    |s: &Counter| s.value * 6,
+                    |s: &Counter| s.value * 7,
+                )
+                .await;
+            assert_eq!((b, c, d, e, f, g), (a * 2, a * 3, a * 4, a * 5, a * 6, a * 7));
+
+            let (a, b, c, d, e, f, g, h) = store
+                .select_zip8(
+                    |s: &Counter| s.value,
+                    |s: &Counter| s.value * 2,
+                    |s: &Counter| s.value * 3,
+                    |s: &Counter| s.value * 4,
+                    |s: &Counter| s.value * 5,
+                    |s: &Counter| s.value * 6,
+                    |s: &Counter| s.value * 7,
+                    |s: &Counter| s.value * 8,
+                )
+                .await;
+            assert_eq!((b, c, d, e, f, g, h), (a * 2, a * 3, a * 4, a * 5, a * 6, a * 7, a * 8));
+        }
+
+        dispatches.await.unwrap();
+        assert_eq!(store.select_zip3(|s: &Counter| s.value, |_: &Counter| (), |_: &Counter| ()).await.0, 200);
+    }
+}
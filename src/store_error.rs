@@ -0,0 +1,96 @@
+use crate::ChannelReceiver;
+use std::any::Any;
+use std::error::Error;
+use std::fmt::{Debug, Display, Formatter};
+use std::sync::Arc;
+use std::time::SystemTime;
+use tokio::sync::mpsc::unbounded_channel;
+
+/// An error which happened inside the store while handling an action, see `Store::new_with_error_channel`
+#[derive(Clone, Debug)]
+pub struct StoreError<Action> {
+    /// The action which caused the error
+    pub action: Action,
+    /// Description of the error, the panic message or the violated invariant
+    pub description: String,
+    /// The moment the error happened
+    pub timestamp: SystemTime,
+}
+
+impl<Action> Display for StoreError<Action> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "store error: {}", self.description)
+    }
+}
+
+impl<Action> Error for StoreError<Action> where Action: Debug {}
+
+/// Stream of the errors of a store, see `Store::new_with_error_channel`.
+///
+/// The stream ends when the store is dropped.
+pub type ErrorReceiver<Action> = ChannelReceiver<StoreError<Action>>;
+
+type Report<Action> = Arc<dyn Fn(Action, String) + Send + Sync>;
+
+// Sending half of the error channel, kept by the worker and by a validating reducer.
+// The worker has no Clone or Send bounds on the action, so the clone functions are captured when the channel is created
+// and the sender is boxed.
+pub(crate) struct ErrorChannel<State, Action> {
+    pub clone_state: fn(&State) -> State,
+    pub clone_action: fn(&Action) -> Action,
+    report: Report<Action>,
+}
+
+impl<State, Action> ErrorChannel<State, Action>
+where
+    State: Clone,
+    Action: Clone + Send + 'static,
+{
+    pub fn new() -> (Self, ErrorReceiver<Action>) {
+        let (tx, rx) = unbounded_channel();
+        let report = move |action: Action, description: String| {
+            let _ = tx.send(StoreError {
+                action,
+                description,
+                timestamp: SystemTime::now(),
+            });
+        };
+
+        let channel = ErrorChannel {
+            clone_state: State::clone,
+            clone_action: Action::clone,
+            report: Arc::new(report),
+        };
+
+        (channel, ChannelReceiver::new(rx))
+    }
+}
+
+impl<State, Action> ErrorChannel<State, Action> {
+    pub fn report_panic(&self, action: Action, payload: &(dyn Any + Send)) {
+        (self.report)(action, panic_message(payload).to_string())
+    }
+
+    pub fn report_error(&self, action: Action, error: &dyn Error) {
+        (self.report)(action, error.to_string())
+    }
+
+    // A second sender for the same receiver
+    pub fn share(&self) -> Self {
+        ErrorChannel {
+            clone_state: self.clone_state,
+            clone_action: self.clone_action,
+            report: self.report.clone(),
+        }
+    }
+}
+
+pub(crate) fn panic_message(payload: &(dyn Any + Send)) -> &str {
+    if let Some(message) = payload.downcast_ref::<&str>() {
+        message
+    } else if let Some(message) = payload.downcast_ref::<String>() {
+        message
+    } else {
+        "unknown panic"
+    }
+}
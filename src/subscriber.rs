@@ -1,3 +1,6 @@
+use std::sync::Mutex;
+use std::time::Instant;
+
 /// # Subscriber trait
 /// A subscriber is what gets called every time a new state is calculated.
 /// You create a subscriber by implementing the `Subscriber` trait or by creating a function with the signature `Fn(&State)`
@@ -38,6 +41,17 @@ pub trait Subscriber<State> {
     fn notify(&self, state: &State);
 }
 
+/// Identifies a subscriber registered to a store
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct SubscriptionId(pub(crate) u64);
+
+/// Diagnostic information about a registered subscriber, see `Store::list_subscribers`
+#[derive(Clone, Debug, PartialEq)]
+pub struct SubscriberInfo {
+    pub id: SubscriptionId,
+    pub registered_at: Instant,
+}
+
 impl<F, State> Subscriber<State> for F
 where
     F: Fn(&State),
@@ -46,3 +60,155 @@ where
         self(state);
     }
 }
+
+/// Subscriber which collects the states and passes them to the inner subscriber in batches of `buffer_size`.
+///
+/// The last, partial batch is passed on when the subscriber is dropped, which happens when the store shuts down.
+/// This is useful to write state changes to a database in batches.
+///
+/// ## Example
+/// ```
+/// use redux_rs::{BufferedSubscriber, Store};
+///
+/// fn reducer(state: u32, add: u32) -> u32 {
+///     state + add
+/// }
+///
+/// # #[tokio::main(flavor = "current_thread")]
+/// # async fn main() {
+/// let store = Store::new(reducer);
+/// store
+///     .subscribe(BufferedSubscriber::new(|batch: &Vec<u32>| println!("Saving {} states", batch.len()), 100))
+///     .await;
+/// # }
+/// ```
+pub struct BufferedSubscriber<State, S>
+where
+    S: Subscriber<Vec<State>>,
+{
+    inner: S,
+    buffer_size: usize,
+    buffer: Mutex<Vec<State>>,
+}
+
+impl<State, S> BufferedSubscriber<State, S>
+where
+    S: Subscriber<Vec<State>>,
+{
+    /// Panics if `buffer_size` is 0
+    pub fn new(inner: S, buffer_size: usize) -> Self {
+        assert!(buffer_size > 0, "the buffer size of a BufferedSubscriber must be at least 1");
+
+        BufferedSubscriber {
+            inner,
+            buffer_size,
+            buffer: Mutex::new(Vec::with_capacity(buffer_size)),
+        }
+    }
+}
+
+impl<State, S> Subscriber<State> for BufferedSubscriber<State, S>
+where
+    State: Clone,
+    S: Subscriber<Vec<State>>,
+{
+    fn notify(&self, state: &State) {
+        let mut buffer = self.buffer.lock().unwrap();
+        buffer.push(state.clone());
+
+        if buffer.len() == self.buffer_size {
+            let batch = std::mem::replace(&mut *buffer, Vec::with_capacity(self.buffer_size));
+            self.inner.notify(&batch);
+        }
+    }
+}
+
+impl<State, S> Drop for BufferedSubscriber<State, S>
+where
+    S: Subscriber<Vec<State>>,
+{
+    fn drop(&mut self) {
+        let buffer = self.buffer.get_mut().unwrap_or_else(|poisoned| poisoned.into_inner());
+        if !buffer.is_empty() {
+            let batch = std::mem::take(buffer);
+            self.inner.notify(&batch);
+        }
+    }
+}
+
+/// Computes the difference between two states, see `Store::subscribe_diff`.
+///
+/// Implement it on a marker type for every kind of diff you are interested in.
+///
+/// ## Example
+/// ```
+/// use redux_rs::StateDiff;
+///
+/// struct User {
+///     name: String,
+///     age: u8,
+/// }
+///
+/// struct NameChanged;
+///
+/// impl StateDiff<User> for NameChanged {
+///     type Diff = String;
+///
+///     fn diff(old: &User, new: &User) -> Option<String> {
+///         (old.name != new.name).then(|| new.name.clone())
+///     }
+/// }
+/// ```
+pub trait StateDiff<State> {
+    /// The difference between two states
+    type Diff;
+
+    /// Returns the difference between the old and the new state, or None if nothing changed
+    fn diff(old: &State, new: &State) -> Option<Self::Diff>;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Store;
+    use std::sync::Arc;
+    use std::time::Duration;
+
+    fn counter_reducer(state: u32, add: u32) -> u32 {
+        state + add
+    }
+
+    #[tokio::test]
+    async fn batches_are_flushed_on_shutdown() {
+        let batches = Arc::new(Mutex::new(Vec::new()));
+        let batches_clone = batches.clone();
+
+        let store = Store::new(counter_reducer);
+        store
+            .subscribe(BufferedSubscriber::new(
+                move |batch: &Vec<u32>| batches_clone.lock().unwrap().push(batch.clone()),
+                10,
+            ))
+            .await;
+
+        for _ in 0..25 {
+            store.dispatch(1u32).await;
+        }
+        assert_eq!(batches.lock().unwrap().len(), 2);
+
+        // The subscriber is dropped once the worker of the store stops
+        drop(store);
+        tokio::time::timeout(Duration::from_secs(1), async {
+            while batches.lock().unwrap().len() < 3 {
+                tokio::task::yield_now().await;
+            }
+        })
+        .await
+        .unwrap();
+
+        let batches = batches.lock().unwrap();
+        let sizes: Vec<usize> = batches.iter().map(Vec::len).collect();
+        assert_eq!(sizes, vec![10, 10, 5]);
+        assert_eq!(batches[2], vec![21, 22, 23, 24, 25]);
+    }
+}
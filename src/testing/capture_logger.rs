@@ -0,0 +1,38 @@
+use log::{LevelFilter, Log, Metadata, Record};
+use std::sync::Mutex;
+
+// `log` only allows a single logger per process, so all tests checking log output share this one.
+// Tests run in parallel, the captured messages are kept per target so every test only sees its own module's logs.
+static LOGS: Mutex<Vec<(String, String)>> = Mutex::new(Vec::new());
+
+struct CaptureLogger;
+
+impl Log for CaptureLogger {
+    fn enabled(&self, _metadata: &Metadata) -> bool {
+        true
+    }
+
+    fn log(&self, record: &Record) {
+        LOGS.lock().unwrap().push((record.target().to_string(), record.args().to_string()));
+    }
+
+    fn flush(&self) {}
+}
+
+static LOGGER: CaptureLogger = CaptureLogger;
+
+/// Install the capturing logger, calling this more than once is fine
+pub(crate) fn capture_logs() {
+    let _ = log::set_logger(&LOGGER);
+    log::set_max_level(LevelFilter::Trace);
+}
+
+/// The messages logged with the target so far
+pub(crate) fn captured_logs(target: &str) -> Vec<String> {
+    LOGS.lock()
+        .unwrap()
+        .iter()
+        .filter(|(log_target, _)| log_target == target)
+        .map(|(_, message)| message.clone())
+        .collect()
+}
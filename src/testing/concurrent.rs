@@ -0,0 +1,194 @@
+use crate::StoreApi;
+use std::marker::PhantomData;
+use std::sync::{Arc, Mutex};
+
+struct Dispatchers<Action> {
+    n_tasks: usize,
+    actions_per_task: usize,
+    action_fn: fn(usize) -> Action,
+}
+
+/// Harness to stress test a store by dispatching from many tasks at the same time.
+///
+/// Every state the store goes through is recorded by a subscriber,
+/// so invariants can be checked on each intermediate state instead of only on the final one.
+///
+/// Requires the `testing` feature.
+///
+/// ## Example
+/// ```
+/// use redux_rs::{ConcurrentHarness, Store};
+/// use std::sync::Arc;
+///
+/// fn reducer(state: u32, add: u32) -> u32 {
+///     state + add
+/// }
+///
+/// # #[tokio::main]
+/// # async fn main() {
+/// let mut harness = ConcurrentHarness::new(Arc::new(Store::new(reducer)));
+/// harness.spawn_dispatchers(10, 10, |_| 1);
+///
+/// let states = harness.run().await;
+/// assert_eq!(states.last(), Some(&100));
+/// harness.assert_no_races(|state| *state <= 100);
+/// # }
+/// ```
+pub struct ConcurrentHarness<S, State, Action> {
+    store: Arc<S>,
+    dispatchers: Vec<Dispatchers<Action>>,
+    snapshots: Arc<Mutex<Vec<State>>>,
+    subscribed: bool,
+    _types: PhantomData<fn(State)>,
+}
+
+impl<S, State, Action> ConcurrentHarness<S, State, Action>
+where
+    S: StoreApi<State, Action> + Send + Sync + 'static,
+    State: Clone + Send + 'static,
+    Action: Send + 'static,
+{
+    /// Stress test the store, it's shared by all dispatcher tasks
+    pub fn new(store: Arc<S>) -> Self {
+        ConcurrentHarness {
+            store,
+            dispatchers: Vec::new(),
+            snapshots: Arc::new(Mutex::new(Vec::new())),
+            subscribed: false,
+            _types: Default::default(),
+        }
+    }
+
+    /// Add `n_tasks` tasks which each dispatch `actions_per_task` actions on `run`.
+    /// The actions are created by `action_fn`, it gets the index of the action within its task.
+    pub fn spawn_dispatchers(&mut self, n_tasks: usize, actions_per_task: usize, action_fn: fn(usize) -> Action) -> &mut Self {
+        self.dispatchers.push(Dispatchers {
+            n_tasks,
+            actions_per_task,
+            action_fn,
+        });
+        self
+    }
+
+    /// Run all dispatcher tasks concurrently and wait until they're done.
+    /// Returns every state the store went through, in order, including the states of earlier runs.
+    ///
+    /// The dispatchers are consumed, the next run only runs the dispatchers spawned after this one.
+    pub async fn run(&mut self) -> Vec<State> {
+        if !self.subscribed {
+            let snapshots = self.snapshots.clone();
+            self.store.subscribe(move |state: &State| snapshots.lock().unwrap().push(state.clone())).await;
+            self.subscribed = true;
+        }
+
+        let mut tasks = Vec::new();
+        for dispatchers in self.dispatchers.drain(..) {
+            for _ in 0..dispatchers.n_tasks {
+                let store = self.store.clone();
+                let actions_per_task = dispatchers.actions_per_task;
+                let action_fn = dispatchers.action_fn;
+
+                tasks.push(tokio::spawn(async move {
+                    for index in 0..actions_per_task {
+                        store.dispatch(action_fn(index)).await;
+                    }
+                }));
+            }
+        }
+
+        for task in tasks {
+            task.await.expect("dispatcher task panicked");
+        }
+
+        // Dispatches are handled in order, so once this select returns all subscribers are notified
+        self.store.select(|_: &State| ()).await;
+
+        self.snapshots.lock().unwrap().clone()
+    }
+
+    /// Panics if the invariant doesn't hold for one of the states recorded so far
+    pub fn assert_no_races<Invariant>(&self, invariant: Invariant)
+    where
+        Invariant: Fn(&State) -> bool,
+    {
+        let snapshots = self.snapshots.lock().unwrap();
+        if let Some(index) = snapshots.iter().position(|state| !invariant(state)) {
+            panic!("invariant violated by state {} of {}", index + 1, snapshots.len());
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{MiddleWare, Store};
+    use async_trait::async_trait;
+
+    #[derive(Clone, Debug, Default, PartialEq)]
+    struct Counter(u32);
+
+    enum CounterAction {
+        Increment,
+        Add(u32),
+    }
+
+    fn counter_reducer(state: Counter, action: CounterAction) -> Counter {
+        match action {
+            CounterAction::Increment => Counter(state.0 + 1),
+            CounterAction::Add(value) => Counter(state.0 + value),
+        }
+    }
+
+    struct YieldMiddleware;
+
+    #[async_trait]
+    impl<Inner> MiddleWare<Counter, CounterAction, Inner> for YieldMiddleware
+    where
+        Inner: StoreApi<Counter, CounterAction> + Send + Sync,
+    {
+        async fn dispatch(&self, action: CounterAction, inner: &Arc<Inner>) {
+            tokio::task::yield_now().await;
+            inner.dispatch(action).await;
+        }
+    }
+
+    #[tokio::test(flavor = "multi_thread", worker_threads = 4)]
+    async fn concurrent_increments_are_not_lost() {
+        let mut harness = ConcurrentHarness::new(Arc::new(Store::new(counter_reducer)));
+        harness.spawn_dispatchers(100, 100, |_| CounterAction::Increment);
+
+        let states = harness.run().await;
+
+        assert_eq!(states.len(), 10_000);
+        assert_eq!(states.last(), Some(&Counter(10_000)));
+        harness.assert_no_races(|state| state.0 >= 1 && state.0 <= 10_000);
+        assert!(states.iter().enumerate().all(|(index, state)| state.0 as usize == index + 1));
+    }
+
+    #[tokio::test(flavor = "multi_thread", worker_threads = 4)]
+    async fn runs_dispatchers_through_middleware() {
+        let store = Store::new(counter_reducer).wrap(YieldMiddleware).await;
+        let mut harness = ConcurrentHarness::new(Arc::new(store));
+        harness
+            .spawn_dispatchers(10, 10, |_| CounterAction::Increment)
+            .spawn_dispatchers(5, 4, |index| CounterAction::Add(index as u32));
+
+        let states = harness.run().await;
+        assert_eq!(states.last(), Some(&Counter(100 + 5 * 6)));
+
+        harness.spawn_dispatchers(1, 1, |_| CounterAction::Increment);
+        let states = harness.run().await;
+        assert_eq!(states.len(), 100 + 20 + 1);
+        assert_eq!(states.last(), Some(&Counter(131)));
+    }
+
+    #[tokio::test]
+    #[should_panic(expected = "invariant violated by state 3 of 5")]
+    async fn reports_the_violating_state() {
+        let mut harness = ConcurrentHarness::new(Arc::new(Store::new(counter_reducer)));
+        harness.spawn_dispatchers(1, 5, |_| CounterAction::Increment);
+        harness.run().await;
+
+        harness.assert_no_races(|state| state.0 < 3);
+    }
+}
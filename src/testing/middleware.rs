@@ -0,0 +1,230 @@
+use crate::{MiddleWare, Selector, StoreApi, Subscriber};
+use async_trait::async_trait;
+use std::fmt::Debug;
+use std::sync::{Arc, Mutex};
+use tokio::sync::Notify;
+
+/// Store which records the actions dispatched to it instead of reducing them, see `MiddlewareTestBuilder`.
+///
+/// The state never changes, so subscribers are kept but never notified.
+/// Waiting for a state change, like `StoreApiExt::dispatch_and_wait_for_effect` does, never returns.
+/// Use `wait_for_dispatched` to wait for actions dispatched in the background instead.
+///
+/// Requires the `testing` feature.
+pub struct MockStore<State, Action> {
+    state: Mutex<State>,
+    dispatched: Mutex<Vec<Action>>,
+    dispatch_notify: Notify,
+    subscribers: Mutex<Vec<Box<dyn Subscriber<State> + Send>>>,
+}
+
+impl<State, Action> MockStore<State, Action> {
+    /// Create a mock store, selectors run on the given state
+    pub fn new(state: State) -> Self {
+        MockStore {
+            state: Mutex::new(state),
+            dispatched: Mutex::new(Vec::new()),
+            dispatch_notify: Notify::new(),
+            subscribers: Mutex::new(Vec::new()),
+        }
+    }
+
+    /// The actions dispatched to the store so far
+    pub fn dispatched(&self) -> Vec<Action>
+    where
+        Action: Clone,
+    {
+        self.dispatched.lock().unwrap().clone()
+    }
+
+    /// Wait until the store received at least `count` actions in total
+    pub async fn wait_for_dispatched(&self, count: usize) {
+        loop {
+            // Created before the check, so a dispatch in between isn't missed
+            let dispatched = self.dispatch_notify.notified();
+            if self.dispatched_count() >= count {
+                return;
+            }
+            dispatched.await;
+        }
+    }
+
+    fn dispatched_count(&self) -> usize {
+        self.dispatched.lock().unwrap().len()
+    }
+}
+
+impl<State, Action> Default for MockStore<State, Action>
+where
+    State: Default,
+{
+    fn default() -> Self {
+        Self::new(State::default())
+    }
+}
+
+#[async_trait]
+impl<State, Action> StoreApi<State, Action> for MockStore<State, Action>
+where
+    State: Send + 'static,
+    Action: Send + 'static,
+{
+    async fn dispatch<A: Into<Action> + Send>(&self, action: A) {
+        self.dispatched.lock().unwrap().push(action.into());
+        self.dispatch_notify.notify_waiters();
+    }
+
+    async fn select<S: Selector<State, Result = Result>, Result>(&self, selector: S) -> Result
+    where
+        S: Selector<State, Result = Result> + Send + 'static,
+        Result: Send + 'static,
+    {
+        selector.select(&self.state.lock().unwrap())
+    }
+
+    async fn subscribe<S: Subscriber<State> + Send + 'static>(&self, subscriber: S) {
+        self.subscribers.lock().unwrap().push(Box::new(subscriber));
+    }
+}
+
+/// Harness to unit test a middleware on its own, the middleware is wrapped around a `MockStore`.
+///
+/// Requires the `testing` feature.
+///
+/// ## Example
+/// ```
+/// use redux_rs::{middleware_from_fn, MiddlewareTestBuilder, MockStore, StoreApi};
+/// use std::sync::Arc;
+///
+/// # #[tokio::main(flavor = "current_thread")]
+/// # async fn main() {
+/// // Drop the small values
+/// let filter = middleware_from_fn(|value: u8, inner: Arc<MockStore<(), u8>>| async move {
+///     if value > 10 {
+///         inner.dispatch(value).await;
+///     }
+/// });
+/// let mut test = MiddlewareTestBuilder::<_, (), u8>::new(filter);
+///
+/// test.dispatch(5u8).await.assert_action_dropped();
+/// test.dispatch(20u8).await.assert_inner_dispatched(vec![20]);
+/// # }
+/// ```
+pub struct MiddlewareTestBuilder<M, State, Action> {
+    middleware: M,
+    inner: Arc<MockStore<State, Action>>,
+    initialized: bool,
+    // The number of actions the inner store received before the last dispatch
+    dispatched_before: usize,
+}
+
+impl<M, State, Action> MiddlewareTestBuilder<M, State, Action>
+where
+    State: Send + 'static,
+    Action: Send + 'static,
+{
+    /// Test the middleware, it's wrapped around a mock store with the default state
+    pub fn new(middleware: M) -> Self
+    where
+        State: Default,
+    {
+        MiddlewareTestBuilder {
+            middleware,
+            inner: Arc::new(MockStore::default()),
+            initialized: false,
+            dispatched_before: 0,
+        }
+    }
+
+    /// Wrap the middleware around the given mock store instead
+    pub fn with_inner(mut self, inner: MockStore<State, Action>) -> Self {
+        self.inner = Arc::new(inner);
+        self
+    }
+
+    /// The mock store the middleware is wrapped around
+    pub fn inner(&self) -> &Arc<MockStore<State, Action>> {
+        &self.inner
+    }
+
+    /// Dispatch an action to the middleware, the middleware is initialized first if this is the first dispatch
+    pub async fn dispatch<OuterAction>(&mut self, action: OuterAction) -> &mut Self
+    where
+        M: MiddleWare<State, OuterAction, MockStore<State, Action>, Action> + Send + Sync,
+        OuterAction: Send + 'static,
+    {
+        if !self.initialized {
+            self.middleware.init(&self.inner).await;
+            self.initialized = true;
+        }
+
+        self.dispatched_before = self.inner.dispatched_count();
+        self.middleware.dispatch(action, &self.inner).await;
+        self
+    }
+
+    /// Wait until the inner store received at least `count` actions since the start of the test,
+    /// for middlewares which dispatch in the background
+    pub async fn wait_for_inner_dispatched(&self, count: usize) -> &Self {
+        self.inner.wait_for_dispatched(count).await;
+        self
+    }
+
+    /// Panics if the inner store didn't receive exactly the expected actions, counted since the start of the test
+    pub fn assert_inner_dispatched(&self, expected_actions: Vec<Action>) -> &Self
+    where
+        Action: Clone + Debug + PartialEq,
+    {
+        assert_eq!(self.inner.dispatched(), expected_actions);
+        self
+    }
+
+    /// Panics if the last dispatched action, or any action in its place, reached the inner store
+    pub fn assert_action_dropped(&self) -> &Self {
+        let dispatched = self.inner.dispatched_count() - self.dispatched_before;
+        assert_eq!(
+            dispatched, 0,
+            "the last action was not dropped, the inner store received {} action(s)",
+            dispatched
+        );
+        self
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct Double;
+
+    #[async_trait]
+    impl<Inner> MiddleWare<i32, i32, Inner> for Double
+    where
+        Inner: StoreApi<i32, i32> + Send + Sync,
+    {
+        async fn dispatch(&self, action: i32, inner: &Arc<Inner>) {
+            if action != 0 {
+                inner.dispatch(action).await;
+                inner.dispatch(action).await;
+            }
+        }
+    }
+
+    #[tokio::test]
+    async fn records_inner_dispatches() {
+        let mut test = MiddlewareTestBuilder::new(Double).with_inner(MockStore::new(5));
+
+        test.dispatch(1).await.assert_inner_dispatched(vec![1, 1]);
+        test.dispatch(0).await.assert_action_dropped();
+        test.dispatch(2).await.assert_inner_dispatched(vec![1, 1, 2, 2]);
+
+        assert_eq!(test.inner().select(|state: &i32| *state).await, 5);
+    }
+
+    #[tokio::test]
+    #[should_panic(expected = "the last action was not dropped")]
+    async fn dropped_assertion_fails() {
+        let mut test = MiddlewareTestBuilder::<_, i32, i32>::new(Double);
+        test.dispatch(1).await.assert_action_dropped();
+    }
+}
@@ -0,0 +1,223 @@
+#[cfg(all(test, feature = "middleware_logger"))]
+pub(crate) mod capture_logger;
+mod concurrent;
+mod middleware;
+#[cfg(feature = "proptest")]
+mod proptest_integration;
+
+pub use concurrent::ConcurrentHarness;
+pub use middleware::{MiddlewareTestBuilder, MockStore};
+#[cfg(feature = "proptest")]
+pub use proptest_integration::run_state_machine_test;
+
+use crate::{reduce_immediately, Reducer};
+use std::fmt::Debug;
+use std::marker::PhantomData;
+
+/// Harness to unit test a reducer without spinning up a store.
+/// Reducers are pure functions, so the harness is synchronous and doesn't need a runtime.
+///
+/// Requires the `testing` feature.
+///
+/// ## Example
+/// ```
+/// use redux_rs::ReducerTestHarness;
+///
+/// fn reducer(state: u8, add: u8) -> u8 {
+///     state + add
+/// }
+///
+/// let mut harness = ReducerTestHarness::new(reducer, 0);
+/// assert_eq!(*harness.dispatch(2), 2);
+/// assert_eq!(*harness.sequence(vec![1, 2, 3]), 8);
+/// harness.assert_state_eq(8);
+/// ```
+pub struct ReducerTestHarness<State, Action, R> {
+    reducer: R,
+    // Only `None` while the reducer runs
+    state: Option<State>,
+    _types: PhantomData<fn(Action)>,
+}
+
+impl<State, Action, R> ReducerTestHarness<State, Action, R>
+where
+    R: Reducer<State, Action>,
+{
+    /// Create a harness for the reducer starting from the initial state
+    pub fn new(reducer: R, initial_state: State) -> Self {
+        ReducerTestHarness {
+            reducer,
+            state: Some(initial_state),
+            _types: Default::default(),
+        }
+    }
+
+    /// Run the reducer with the action and return the new state
+    pub fn dispatch(&mut self, action: Action) -> &State {
+        let state = self.state.take().expect("state is always set outside of a reduction");
+        self.state.insert(self.reducer.reduce(state, action))
+    }
+
+    /// Run the reducer with all actions in order and return the final state
+    pub fn sequence(&mut self, actions: Vec<Action>) -> &State {
+        for action in actions {
+            self.dispatch(action);
+        }
+
+        self.state()
+    }
+
+    /// The current state
+    pub fn state(&self) -> &State {
+        self.state.as_ref().expect("state is always set outside of a reduction")
+    }
+
+    /// Panics if the current state doesn't equal the expected state
+    pub fn assert_state_eq(&self, expected: State)
+    where
+        State: PartialEq + Debug,
+    {
+        assert_eq!(*self.state(), expected);
+    }
+}
+
+/// Apply the reducer to the state and action, without any store.
+/// This is the async counterpart of calling `Reducer::reduce`, for tests which are async anyway.
+///
+/// Requires the `testing` feature.
+pub async fn reduce_pure<State, Action, R>(reducer: R, state: State, action: Action) -> State
+where
+    R: Reducer<State, Action>,
+{
+    reducer.reduce(state, action)
+}
+
+/// Apply the actions one by one to the initial state and return the final state, see `reduce_immediately`
+///
+/// Requires the `testing` feature.
+pub fn reduce_sequence<State, Action, R>(reducer: R, initial: State, actions: Vec<Action>) -> State
+where
+    R: Reducer<State, Action>,
+{
+    reduce_immediately(reducer, initial, actions)
+}
+
+#[cfg(feature = "proptest")]
+impl<State, Action, R> ReducerTestHarness<State, Action, R>
+where
+    R: Reducer<State, Action>,
+    State: Clone + Debug,
+    Action: Debug,
+{
+    /// Run the reducer on sequences of actions generated by `actions_strategy`, every sequence starts from the initial state.
+    /// The property is checked after every single action, failing sequences are shrunk and reported in the panic message.
+    ///
+    /// Requires the `proptest` feature.
+    pub fn property_test<S, P>(reducer: R, initial: State, actions_strategy: S, property: P)
+    where
+        S: proptest::strategy::Strategy<Value = Vec<Action>>,
+        P: Fn(&State) -> bool,
+    {
+        let cases = proptest::test_runner::Config::default().cases;
+        run_state_machine_test(reducer, initial, actions_strategy, property, cases);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Debug, PartialEq)]
+    struct Counter {
+        value: i32,
+    }
+
+    #[derive(Clone, Copy, Debug)]
+    enum CounterAction {
+        Increment,
+        Decrement,
+    }
+
+    fn counter_reducer(state: Counter, action: CounterAction) -> Counter {
+        match action {
+            CounterAction::Increment => Counter { value: state.value + 1 },
+            CounterAction::Decrement => Counter { value: state.value - 1 },
+        }
+    }
+
+    #[test]
+    fn counter_table_driven() {
+        use CounterAction::*;
+
+        let cases: Vec<(i32, Vec<CounterAction>, i32)> = vec![
+            (0, vec![], 0),
+            (0, vec![Increment], 1),
+            (0, vec![Decrement], -1),
+            (0, vec![Increment, Increment], 2),
+            (0, vec![Increment, Decrement], 0),
+            (5, vec![Decrement, Decrement, Decrement], 2),
+            (-3, vec![Increment, Increment, Increment], 0),
+            (10, vec![Increment; 5], 15),
+            (10, vec![Decrement; 10], 0),
+            (i32::MAX - 1, vec![Increment], i32::MAX),
+        ];
+
+        for (initial, actions, expected) in cases {
+            let mut harness = ReducerTestHarness::new(counter_reducer, Counter { value: initial });
+            assert_eq!(harness.sequence(actions).value, expected);
+            harness.assert_state_eq(Counter { value: expected });
+        }
+    }
+
+    #[test]
+    fn counter_sequences() {
+        use CounterAction::*;
+
+        assert_eq!(reduce_sequence(counter_reducer, Counter { value: 0 }, vec![]), Counter { value: 0 });
+        assert_eq!(reduce_sequence(counter_reducer, Counter { value: 0 }, vec![Increment; 3]), Counter { value: 3 });
+        assert_eq!(
+            reduce_sequence(counter_reducer, Counter { value: 5 }, vec![Decrement, Increment, Decrement]),
+            Counter { value: 4 }
+        );
+    }
+
+    #[tokio::test]
+    async fn counter_pure() {
+        assert_eq!(
+            reduce_pure(counter_reducer, Counter { value: 0 }, CounterAction::Increment).await,
+            Counter { value: 1 }
+        );
+        assert_eq!(
+            reduce_pure(counter_reducer, Counter { value: 0 }, CounterAction::Decrement).await,
+            Counter { value: -1 }
+        );
+    }
+
+    #[test]
+    fn dispatch_returns_new_state() {
+        let mut harness = ReducerTestHarness::new(counter_reducer, Counter { value: 0 });
+
+        assert_eq!(harness.dispatch(CounterAction::Increment).value, 1);
+        assert_eq!(harness.dispatch(CounterAction::Increment).value, 2);
+        assert_eq!(harness.dispatch(CounterAction::Decrement).value, 1);
+    }
+
+    #[cfg(feature = "proptest")]
+    #[test]
+    fn counter_stays_within_bounds() {
+        use proptest::prelude::*;
+
+        #[derive(Clone, Debug)]
+        struct Bounded(u8);
+
+        fn bounded_reducer(state: Bounded, action: CounterAction) -> Bounded {
+            match action {
+                CounterAction::Increment => Bounded(state.0.saturating_add(1).min(10)),
+                CounterAction::Decrement => Bounded(state.0.saturating_sub(1)),
+            }
+        }
+
+        let action = prop_oneof![Just(CounterAction::Increment), Just(CounterAction::Decrement)];
+        ReducerTestHarness::property_test(bounded_reducer, Bounded(0), prop::collection::vec(action, 0..50), |state| state.0 <= 10);
+    }
+}
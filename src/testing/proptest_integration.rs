@@ -0,0 +1,114 @@
+use crate::Reducer;
+use proptest::strategy::Strategy;
+use proptest::test_runner::{Config, TestRunner};
+use std::fmt::Debug;
+
+/// Run the reducer on `cases` sequences of actions generated by `actions`, every sequence starts from the initial state.
+/// Panics with the shrunk failing sequence if the invariant doesn't hold after any action.
+///
+/// Use the `reducer_state_machine_test!` macro or `ReducerTestHarness::property_test` instead of calling this directly.
+#[doc(hidden)]
+pub fn run_state_machine_test<State, Action, R, S, P>(reducer: R, initial: State, actions: S, invariant: P, cases: u32)
+where
+    R: Reducer<State, Action>,
+    State: Clone + Debug,
+    Action: Debug,
+    S: Strategy<Value = Vec<Action>>,
+    P: Fn(&State) -> bool,
+{
+    let mut runner = TestRunner::new(Config { cases, ..Config::default() });
+    let result = runner.run(&actions, |actions| {
+        let mut state = initial.clone();
+        for action in actions {
+            state = reducer.reduce(state, action);
+            proptest::prop_assert!(invariant(&state), "invariant doesn't hold for state {:?}", state);
+        }
+
+        Ok(())
+    });
+
+    if let Err(err) = result {
+        panic!("{}", err);
+    }
+}
+
+/// Property test a reducer as a state machine: random sequences of actions are reduced and the invariant is checked after every action.
+///
+/// `reducer_state_machine_test!(reducer, initial_state, actions_strategy, invariant)` runs 256 sequences,
+/// add `cases = n` as the last argument to run `n` sequences instead.
+/// The strategy generates the action sequences, for example `proptest::collection::vec(action_strategy, 0..100)`.
+/// Failing sequences are shrunk to a minimal example and reported in the panic message.
+///
+/// Requires the `proptest` feature.
+///
+/// ## Example
+/// ```
+/// use proptest::prelude::*;
+/// use redux_rs::reducer_state_machine_test;
+///
+/// fn reducer(state: u8, add: u8) -> u8 {
+///     state.saturating_add(add)
+/// }
+///
+/// reducer_state_machine_test!(reducer, 0, prop::collection::vec(any::<u8>(), 0..20), |state: &u8| *state <= u8::MAX, cases = 100);
+/// ```
+#[macro_export]
+macro_rules! reducer_state_machine_test {
+    ($reducer:expr, $initial:expr, $actions:expr, $invariant:expr) => {
+        $crate::reducer_state_machine_test!($reducer, $initial, $actions, $invariant, cases = 256)
+    };
+    ($reducer:expr, $initial:expr, $actions:expr, $invariant:expr, cases = $cases:expr) => {
+        $crate::run_state_machine_test($reducer, $initial, $actions, $invariant, $cases)
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use proptest::prelude::*;
+
+    #[derive(Clone, Debug)]
+    struct Counter {
+        counter: i32,
+    }
+
+    #[derive(Clone, Debug)]
+    enum CounterAction {
+        Increment,
+        Decrement,
+    }
+
+    fn actions() -> impl Strategy<Value = Vec<CounterAction>> {
+        prop::collection::vec(prop_oneof![Just(CounterAction::Increment), Just(CounterAction::Decrement)], 0..50)
+    }
+
+    fn non_negative(state: &Counter) -> bool {
+        state.counter >= 0
+    }
+
+    fn floored_reducer(state: Counter, action: CounterAction) -> Counter {
+        match action {
+            CounterAction::Increment => Counter { counter: state.counter + 1 },
+            CounterAction::Decrement => Counter {
+                counter: (state.counter - 1).max(0),
+            },
+        }
+    }
+
+    fn buggy_reducer(state: Counter, action: CounterAction) -> Counter {
+        match action {
+            CounterAction::Increment => Counter { counter: state.counter + 1 },
+            CounterAction::Decrement => Counter { counter: state.counter - 1 },
+        }
+    }
+
+    #[test]
+    fn counter_never_negative() {
+        reducer_state_machine_test!(floored_reducer, Counter { counter: 0 }, actions(), non_negative, cases = 1000);
+    }
+
+    #[test]
+    #[should_panic(expected = "invariant doesn't hold")]
+    fn finds_negative_counter() {
+        reducer_state_machine_test!(buggy_reducer, Counter { counter: 0 }, actions(), non_negative);
+    }
+}
@@ -0,0 +1,159 @@
+use crate::{Selector, StoreApi, Subscriber};
+use std::marker::PhantomData;
+use tokio::runtime::{Handle, RuntimeFlavor};
+
+/// A blocking facade around a store, for use from synchronous, thread based code.
+///
+/// Every call is driven to completion on the provided runtime, the calling thread is blocked until the store has responded.
+/// All state access is still serialized through the store worker.
+///
+/// NOTE: The blocking methods must not be called from within an async context, they will panic if you do.
+///
+/// NOTE: The runtime must be a multi threaded runtime.
+/// On a `current_thread` runtime (which is what `#[tokio::test]` uses by default) nothing drives the store worker
+/// while another thread is blocked on it, so every call would deadlock.
+///
+/// ## Example
+/// ```
+/// use redux_rs::{Store, ThreadSafeStore};
+/// use std::sync::Arc;
+///
+/// #[derive(Default)]
+/// struct Counter(i8);
+///
+/// fn reducer(state: Counter, _action: ()) -> Counter {
+///     Counter(state.0 + 1)
+/// }
+///
+/// let runtime = tokio::runtime::Runtime::new().unwrap();
+/// let store = runtime.block_on(async { Store::new(reducer) });
+/// let store = Arc::new(ThreadSafeStore::new_from_handle(store, runtime.handle().clone()));
+///
+/// let thread_store = store.clone();
+/// std::thread::spawn(move || thread_store.dispatch_blocking(())).join().unwrap();
+///
+/// assert_eq!(store.select_blocking(|state: &Counter| state.0), 1);
+/// ```
+pub struct ThreadSafeStore<Inner, State, Action>
+where
+    Inner: StoreApi<State, Action> + Send + Sync,
+    State: Send + 'static,
+    Action: Send + 'static,
+{
+    inner: Inner,
+    runtime_handle: Handle,
+
+    _types: PhantomData<fn() -> (State, Action)>,
+}
+
+impl<Inner, State, Action> ThreadSafeStore<Inner, State, Action>
+where
+    Inner: StoreApi<State, Action> + Send + Sync,
+    State: Send + 'static,
+    Action: Send + 'static,
+{
+    /// Wrap a store, all work is driven by the runtime behind the given handle
+    ///
+    /// # Panics
+    /// Panics if the handle belongs to a `current_thread` runtime.
+    pub fn new_from_handle(inner: Inner, runtime_handle: Handle) -> Self {
+        assert!(
+            runtime_handle.runtime_flavor() != RuntimeFlavor::CurrentThread,
+            "ThreadSafeStore requires a multi threaded runtime, a current_thread runtime would deadlock"
+        );
+
+        ThreadSafeStore {
+            inner,
+            runtime_handle,
+            _types: Default::default(),
+        }
+    }
+
+    /// Dispatch an action and block until it has been processed
+    pub fn dispatch_blocking(&self, action: Action) {
+        self.runtime_handle.block_on(self.inner.dispatch(action))
+    }
+
+    /// Select a part of the state, blocking until the result is available
+    pub fn select_blocking<S, Result>(&self, selector: S) -> Result
+    where
+        S: Selector<State, Result = Result> + Send + 'static,
+        Result: Send + 'static,
+    {
+        self.runtime_handle.block_on(self.inner.select(selector))
+    }
+
+    /// Returns a cloned version of the state, blocking until it is available
+    pub fn state_cloned_blocking(&self) -> State
+    where
+        State: Clone,
+    {
+        self.runtime_handle.block_on(self.inner.state_cloned())
+    }
+
+    /// Subscribe to state changes, blocking until the subscriber is registered
+    pub fn subscribe_blocking<S: Subscriber<State> + Send + 'static>(&self, subscriber: S) {
+        self.runtime_handle.block_on(self.inner.subscribe(subscriber))
+    }
+
+    /// Access the wrapped store, for use from async code
+    pub fn inner(&self) -> &Inner {
+        &self.inner
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Store;
+    use std::sync::Arc;
+    use std::thread;
+
+    #[derive(Default)]
+    struct Counter {
+        value: i32,
+    }
+
+    enum CounterAction {
+        Increment,
+    }
+
+    fn counter_reducer(state: Counter, action: CounterAction) -> Counter {
+        match action {
+            CounterAction::Increment => Counter { value: state.value + 1 },
+        }
+    }
+
+    #[test]
+    fn dispatch_from_threads() {
+        let runtime = tokio::runtime::Runtime::new().unwrap();
+        let store = runtime.block_on(async { Store::new(counter_reducer) });
+        let store = Arc::new(ThreadSafeStore::new_from_handle(store, runtime.handle().clone()));
+
+        let handles: Vec<_> = (0..4)
+            .map(|_| {
+                let store = store.clone();
+                thread::spawn(move || {
+                    for _ in 0..10 {
+                        store.dispatch_blocking(CounterAction::Increment);
+                    }
+                })
+            })
+            .collect();
+
+        for handle in handles {
+            handle.join().unwrap();
+        }
+
+        assert_eq!(store.select_blocking(|state: &Counter| state.value), 40);
+    }
+
+    #[test]
+    #[should_panic(expected = "ThreadSafeStore requires a multi threaded runtime")]
+    fn reject_current_thread_runtime() {
+        let runtime = tokio::runtime::Builder::new_current_thread().build().unwrap();
+        let store = runtime.block_on(async { Store::new(counter_reducer) });
+
+        ThreadSafeStore::new_from_handle(store, runtime.handle().clone());
+    }
+}
@@ -0,0 +1,97 @@
+use std::collections::VecDeque;
+use std::error::Error;
+use std::fmt::{Display, Formatter};
+
+/// Returned by `Store::transition_to` when the target state can't be reached
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum TransitionError {
+    /// None of the action sequences within the depth limit leads to the target state
+    NoPlanFound,
+    /// A plan was found, but one of its actions was intercepted or its reducer panicked, so the target wasn't reached
+    TargetNotReached,
+}
+
+impl Display for TransitionError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            TransitionError::NoPlanFound => write!(f, "no sequence of actions leads to the target state"),
+            TransitionError::TargetNotReached => write!(f, "the store didn't reach the target state"),
+        }
+    }
+}
+
+impl Error for TransitionError {}
+
+// Breadth first search for the shortest sequence of actions from `start` to `target`.
+// Returns the indices of the actions to take, an empty plan if `start` is the target already.
+// A step for which `reduce` returns None, e.g. because the reducer panicked, is a dead end.
+// States have no Hash bound, so the visited states are compared one by one.
+pub(crate) fn find_plan<State, Action>(
+    start: &State,
+    target: &State,
+    actions: &[Action],
+    max_depth: usize,
+    mut reduce: impl FnMut(State, Action) -> Option<State>,
+) -> Option<Vec<usize>>
+where
+    State: Clone + PartialEq,
+    Action: Clone,
+{
+    if start == target {
+        return Some(Vec::new());
+    }
+
+    let mut visited = vec![start.clone()];
+    let mut queue = VecDeque::from([(start.clone(), Vec::new())]);
+
+    while let Some((state, plan)) = queue.pop_front() {
+        if plan.len() >= max_depth {
+            continue;
+        }
+
+        for (index, action) in actions.iter().enumerate() {
+            let next = match reduce(state.clone(), action.clone()) {
+                Some(next) if !visited.contains(&next) => next,
+                _ => continue,
+            };
+
+            let mut next_plan = plan.clone();
+            next_plan.push(index);
+            if next == *target {
+                return Some(next_plan);
+            }
+
+            visited.push(next.clone());
+            queue.push_back((next, next_plan));
+        }
+    }
+
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn add(state: i32, action: i32) -> Option<i32> {
+        Some(state + action)
+    }
+
+    #[test]
+    fn finds_shortest_plan() {
+        assert_eq!(find_plan(&0, &7, &[1, 5], 10, add), Some(vec![0, 0, 1]));
+        assert_eq!(find_plan(&3, &3, &[1], 10, add), Some(vec![]));
+    }
+
+    #[test]
+    fn respects_depth_limit() {
+        assert_eq!(find_plan(&0, &3, &[1], 2, add), None);
+        assert_eq!(find_plan(&0, &3, &[1], 3, add), Some(vec![0, 0, 0]));
+    }
+
+    #[test]
+    fn skips_failing_steps() {
+        let reduce = |state: i32, action: i32| if action == 5 { None } else { Some(state + action) };
+        assert_eq!(find_plan(&0, &7, &[1, 5], 10, reduce), Some(vec![0; 7]));
+    }
+}
@@ -0,0 +1,228 @@
+use crate::store_error::ErrorChannel;
+use crate::{ErrorReceiver, Reducer, Store};
+use std::error::Error;
+use std::fmt::{Display, Formatter};
+
+/// Returned by a state validator when the new state breaks an invariant
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct InvariantViolation(pub String);
+
+impl Display for InvariantViolation {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "state invariant violated: {}", self.0)
+    }
+}
+
+impl Error for InvariantViolation {}
+
+/// Reducer which validates every new state, see `Store::new_with_validator`
+pub struct ValidatedReducer<RootReducer, State, Action> {
+    root_reducer: RootReducer,
+    validator: fn(&State) -> Result<(), InvariantViolation>,
+    // Set by `Store::new_with_validator_and_error_channel`, violations are reported there as well
+    errors: Option<ErrorChannel<State, Action>>,
+}
+
+impl<RootReducer, State, Action> Reducer<State, Action> for ValidatedReducer<RootReducer, State, Action>
+where
+    RootReducer: Reducer<State, Action>,
+    State: Clone,
+    Action: From<InvariantViolation>,
+{
+    fn reduce(&self, state: State, action: Action) -> State {
+        let previous = state.clone();
+        let reported_action = self.errors.as_ref().map(|errors| (errors.clone_action)(&action));
+        let state = self.root_reducer.reduce(state, action);
+
+        match (self.validator)(&state) {
+            Ok(()) => state,
+            Err(violation) => {
+                if let (Some(errors), Some(action)) = (&self.errors, reported_action) {
+                    errors.report_error(action, &violation);
+                }
+
+                // Roll back and let the reducer handle the violation, as long as that results in a valid state
+                let handled = self.root_reducer.reduce(previous.clone(), Action::from(violation));
+                match (self.validator)(&handled) {
+                    Ok(()) => handled,
+                    Err(_) => previous,
+                }
+            }
+        }
+    }
+}
+
+impl<State, Action, RootReducer> Store<State, Action, ValidatedReducer<RootReducer, State, Action>>
+where
+    Action: From<InvariantViolation> + Send + 'static,
+    RootReducer: Reducer<State, Action> + Send + 'static,
+    State: Clone + Send + 'static,
+{
+    /// Create a new store with the default state, which validates every new state.
+    ///
+    /// When the validator returns an error the state is rolled back to the previous state,
+    /// afterwards the violation is dispatched as an action so the reducer can record it.
+    /// This is done in the same reduction, so subscribers are notified once.
+    /// If the reducer doesn't handle the violation action in a valid way the previous state is kept.
+    ///
+    /// ## Example
+    /// ```
+    /// use redux_rs::{InvariantViolation, Store};
+    ///
+    /// enum Action {
+    ///     Add(i32),
+    ///     Violated(InvariantViolation),
+    /// }
+    ///
+    /// impl From<InvariantViolation> for Action {
+    ///     fn from(violation: InvariantViolation) -> Self {
+    ///         Action::Violated(violation)
+    ///     }
+    /// }
+    ///
+    /// fn reducer(state: i32, action: Action) -> i32 {
+    ///     match action {
+    ///         Action::Add(value) => state + value,
+    ///         Action::Violated(_) => state,
+    ///     }
+    /// }
+    ///
+    /// fn positive(state: &i32) -> Result<(), InvariantViolation> {
+    ///     if *state >= 0 {
+    ///         Ok(())
+    ///     } else {
+    ///         Err(InvariantViolation("negative".to_string()))
+    ///     }
+    /// }
+    ///
+    /// # #[tokio::main(flavor = "current_thread")]
+    /// # async fn main() {
+    /// let store = Store::new_with_validator(reducer, positive);
+    /// store.dispatch(Action::Add(-1)).await;
+    /// assert_eq!(store.state_cloned().await, 0);
+    /// # }
+    /// ```
+    pub fn new_with_validator(root_reducer: RootReducer, validator: fn(&State) -> Result<(), InvariantViolation>) -> Self
+    where
+        State: Default,
+    {
+        Store::new(ValidatedReducer {
+            root_reducer,
+            validator,
+            errors: None,
+        })
+    }
+
+    /// Create a new store with the provided state, which validates every new state and reports errors on the returned receiver.
+    ///
+    /// Violations are handled like in `new_with_validator`, in addition every violation is sent on the receiver
+    /// together with the action which caused it. Reducer panics are caught and reported like in `Store::new_with_error_channel`.
+    pub fn new_with_validator_and_error_channel(
+        root_reducer: RootReducer,
+        state: State,
+        validator: fn(&State) -> Result<(), InvariantViolation>,
+    ) -> (Self, ErrorReceiver<Action>)
+    where
+        Action: Clone,
+    {
+        let (channel, receiver) = ErrorChannel::new();
+        let reducer = ValidatedReducer {
+            root_reducer,
+            validator,
+            errors: Some(channel.share()),
+        };
+
+        (Store::new_with_channel(reducer, state, channel), receiver)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Clone, Copy, Debug, Default, PartialEq)]
+    enum Light {
+        #[default]
+        Red,
+        Green,
+        Yellow,
+    }
+
+    #[derive(Clone, Debug, Default, PartialEq)]
+    struct TrafficLight {
+        light: Light,
+        previous: Option<Light>,
+        violations: Vec<String>,
+    }
+
+    #[derive(Clone, Debug)]
+    enum LightAction {
+        Switch(Light),
+        StateInvariantViolated(InvariantViolation),
+    }
+
+    impl From<InvariantViolation> for LightAction {
+        fn from(violation: InvariantViolation) -> Self {
+            LightAction::StateInvariantViolated(violation)
+        }
+    }
+
+    fn light_reducer(state: TrafficLight, action: LightAction) -> TrafficLight {
+        match action {
+            LightAction::Switch(light) => TrafficLight {
+                light,
+                previous: Some(state.light),
+                ..state
+            },
+            LightAction::StateInvariantViolated(violation) => {
+                let mut violations = state.violations;
+                violations.push(violation.0);
+                TrafficLight { violations, ..state }
+            }
+        }
+    }
+
+    fn legal_transition(state: &TrafficLight) -> Result<(), InvariantViolation> {
+        match (state.previous, state.light) {
+            (None, _) | (Some(Light::Red), Light::Red) => Ok(()),
+            (Some(Light::Red), Light::Yellow) | (Some(Light::Yellow), Light::Green) | (Some(Light::Green), Light::Red) => Ok(()),
+            (Some(from), to) => Err(InvariantViolation(format!("{:?} -> {:?}", from, to))),
+        }
+    }
+
+    #[tokio::test]
+    async fn traffic_light_rolls_back() {
+        let store = Store::new_with_validator(light_reducer, legal_transition);
+
+        store.dispatch(LightAction::Switch(Light::Green)).await;
+        let state = store.state_cloned().await;
+        assert_eq!(state.light, Light::Red);
+        assert_eq!(state.violations, vec!["Red -> Green".to_string()]);
+
+        store.dispatch(LightAction::Switch(Light::Yellow)).await;
+        store.dispatch(LightAction::Switch(Light::Green)).await;
+        assert_eq!(store.select(|state: &TrafficLight| state.light).await, Light::Green);
+
+        store.dispatch(LightAction::Switch(Light::Yellow)).await;
+        let state = store.state_cloned().await;
+        assert_eq!(state.light, Light::Green);
+        assert_eq!(state.violations, vec!["Red -> Green".to_string(), "Green -> Yellow".to_string()]);
+    }
+
+    #[tokio::test]
+    async fn error_channel_reports_violations() {
+        let (store, mut errors) = Store::new_with_validator_and_error_channel(light_reducer, TrafficLight::default(), legal_transition);
+
+        store.dispatch(LightAction::Switch(Light::Green)).await;
+        store.dispatch(LightAction::Switch(Light::Yellow)).await;
+        assert_eq!(store.select(|state: &TrafficLight| state.light).await, Light::Yellow);
+
+        let error = errors.recv().await.unwrap();
+        assert!(matches!(error.action, LightAction::Switch(Light::Green)));
+        assert_eq!(error.description, "state invariant violated: Red -> Green");
+
+        // The valid transition isn't reported
+        drop(store);
+        assert!(errors.recv().await.is_none());
+    }
+}